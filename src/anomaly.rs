@@ -0,0 +1,94 @@
+//! Streaming anomaly detection by z-score against a running [`Gaussian`] baseline
+//!
+//! The scorer's state is just a [`Gaussian`], so baselines learned on separate shards merge with
+//! [`Semigroup::op`] into one that scores exactly as if it had seen every point.
+use std::f64::consts::SQRT_2;
+
+use num_traits::Float;
+
+use crate::gaussian::Gaussian;
+use crate::special::{lit, wide};
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// How unusual a point is relative to the baseline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Score<T = f64> {
+    /// Standard deviations from the baseline mean
+    pub z: T,
+    /// Two-sided tail probability of a point at least this far from the mean, were the baseline normal
+    pub tail: T,
+}
+
+/// Scores points against the mean and variance of those observed so far
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnomalyScorer<T: Float = f64> {
+    baseline: Gaussian<T>,
+}
+
+impl<T: Float> Default for AnomalyScorer<T> {
+    fn default() -> Self {
+        AnomalyScorer {
+            baseline: Gaussian::default(),
+        }
+    }
+}
+
+impl<T: Float> AnomalyScorer<T> {
+    /// A scorer that hasn't observed anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The distribution of the points observed so far.
+    pub fn baseline(&self) -> &Gaussian<T> {
+        &self.baseline
+    }
+    /// Add a point to the baseline.
+    pub fn observe(&mut self, x: T) {
+        self.baseline += x;
+    }
+    /// Score `x` against the baseline, or `None` if fewer than 2 points have been observed.
+    pub fn score(&self, x: T) -> Option<Score<T>> {
+        let sd = self.baseline.try_variance()?.sqrt();
+        let d = x - self.baseline.mean();
+        // Don't let a constant baseline turn its own value into 0 / 0.
+        let z = if d == T::zero() { T::zero() } else { d / sd };
+        let tail = lit(libm::erfc(wide(z.abs()) / SQRT_2));
+        Some(Score { z, tail })
+    }
+    /// Score `x` against the baseline, then add it; the usual way to process a stream.
+    pub fn score_and_observe(&mut self, x: T) -> Option<Score<T>> {
+        let score = self.score(x);
+        self.observe(x);
+        score
+    }
+}
+
+/// Start from an existing baseline.
+impl<T: Float> From<Gaussian<T>> for AnomalyScorer<T> {
+    fn from(baseline: Gaussian<T>) -> Self {
+        AnomalyScorer { baseline }
+    }
+}
+
+/// Merge the baselines.
+impl<T: Float> Semigroup for AnomalyScorer<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        AnomalyScorer {
+            baseline: Semigroup::op(&x.baseline, &y.baseline),
+        }
+    }
+}
+
+/// Merge another baseline into this one.
+impl<T: Float> SemigroupAssign for AnomalyScorer<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.baseline.op_assign(&other.baseline);
+    }
+}
+
+/// A scorer that hasn't observed anything.
+impl<T: Float> Monoid for AnomalyScorer<T> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,265 @@
+//! Statistics of paired data
+//!
+//! [`Covariance`] keeps the means and second co-moments of `(x, y)` pairs, merging with the same parallel
+//! formula as [`Gaussian`](crate::gaussian::Gaussian), so shards of paired observations can be summarized
+//! independently and combined.  Those are also the sufficient statistics for a least-squares line, so
+//! [`Ols`] fits `y = a + bx` across shards the same way.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Running means, variances, and covariance of `(x, y)` pairs
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Covariance {
+    /// Count of pairs (stored as a float for convenience)
+    n: f64,
+    /// Mean of the `x`s
+    mx: f64,
+    /// Mean of the `y`s
+    my: f64,
+    /// Sum of squared deviations of the `x`s
+    sxx: f64,
+    /// Sum of squared deviations of the `y`s
+    syy: f64,
+    /// Sum of products of deviations
+    sxy: f64,
+}
+
+impl PartialEq for Covariance {
+    fn eq(&self, other: &Covariance) -> bool {
+        (self.n == other.n)
+            && close(self.mx, other.mx)
+            && close(self.my, other.my)
+            && close(self.sxx, other.sxx)
+            && close(self.syy, other.syy)
+            && close(self.sxy, other.sxy)
+    }
+}
+
+impl Covariance {
+    /// Construct from a single pair.
+    pub fn new(x: f64, y: f64) -> Covariance {
+        Covariance {
+            n: 1.0,
+            mx: x,
+            my: y,
+            ..Default::default()
+        }
+    }
+    /// The number of pairs seen.
+    pub fn count(&self) -> f64 {
+        self.n
+    }
+    /// The mean of the `x`s.
+    pub fn mean_x(&self) -> f64 {
+        self.mx
+    }
+    /// The mean of the `y`s.
+    pub fn mean_y(&self) -> f64 {
+        self.my
+    }
+    /// The (sample) variance of the `x`s.
+    pub fn variance_x(&self) -> f64 {
+        assert!(self.n > 1.0, "Variance requires more than 1 sample.");
+        self.sxx / (self.n - 1.0)
+    }
+    /// The (sample) variance of the `y`s.
+    pub fn variance_y(&self) -> f64 {
+        assert!(self.n > 1.0, "Variance requires more than 1 sample.");
+        self.syy / (self.n - 1.0)
+    }
+    /// The (sample) covariance.
+    pub fn covariance(&self) -> f64 {
+        assert!(self.n > 1.0, "Covariance requires more than 1 sample.");
+        self.sxy / (self.n - 1.0)
+    }
+    /// Pearson's correlation coefficient; NaN if either coordinate is constant.
+    pub fn pearson_r(&self) -> f64 {
+        self.sxy / (self.sxx * self.syy).sqrt()
+    }
+}
+
+/// We can add a new pair.
+impl Add<(f64, f64)> for Covariance {
+    type Output = Self;
+    fn add(mut self, p: (f64, f64)) -> Self::Output {
+        self += p;
+        self
+    }
+}
+
+/// We can add a new pair.
+impl AddAssign<(f64, f64)> for Covariance {
+    fn add_assign(&mut self, (x, y): (f64, f64)) {
+        self.n += 1.0;
+        let (dx, dy) = (x - self.mx, y - self.my);
+        self.mx += dx / self.n;
+        self.my += dy / self.n;
+        // Using one old and one new deviation keeps this exact for the diagonal terms.
+        self.sxx += dx * (x - self.mx);
+        self.syy += dy * (y - self.my);
+        self.sxy += dx * (y - self.my);
+    }
+}
+
+/// Accumulate the pairs one at a time.
+impl FromIterator<(f64, f64)> for Covariance {
+    fn from_iter<I: IntoIterator<Item = (f64, f64)>>(iter: I) -> Self {
+        let mut c = Self::default();
+        for p in iter {
+            c += p
+        }
+        c
+    }
+}
+
+/// Accumulate the pairs one at a time.
+impl<'a> FromIterator<&'a (f64, f64)> for Covariance {
+    fn from_iter<I: IntoIterator<Item = &'a (f64, f64)>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Join together two summaries.
+impl Semigroup for Covariance {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// Join another summary into this one.
+impl SemigroupAssign for Covariance {
+    fn op_assign(&mut self, other: &Self) {
+        let n = self.n + other.n;
+        if n == 0.0 {
+            *self = Self::default();
+            return;
+        }
+        let (dx, dy) = (other.mx - self.mx, other.my - self.my);
+        let w = self.n * other.n / n;
+        self.sxx += other.sxx + dx * dx * w;
+        self.syy += other.syy + dy * dy * w;
+        self.sxy += other.sxy + dx * dy * w;
+        self.mx += dx * (other.n / n);
+        self.my += dy * (other.n / n);
+        self.n = n;
+    }
+}
+
+/// The empty summary.
+impl Monoid for Covariance {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for Covariance {}
+
+/// Simple linear regression, fitting `y = a + bx` by ordinary least squares
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ols(Covariance);
+
+impl Ols {
+    /// Construct from a single pair.
+    pub fn new(x: f64, y: f64) -> Ols {
+        Ols(Covariance::new(x, y))
+    }
+    /// The underlying means and co-moments.
+    pub fn covariance(&self) -> &Covariance {
+        &self.0
+    }
+    /// The number of pairs seen.
+    pub fn count(&self) -> f64 {
+        self.0.n
+    }
+    /// The fitted slope, `b`; NaN if every `x` is the same.
+    pub fn slope(&self) -> f64 {
+        self.0.sxy / self.0.sxx
+    }
+    /// The fitted intercept, `a`.
+    pub fn intercept(&self) -> f64 {
+        self.0.my - self.slope() * self.0.mx
+    }
+    /// The fitted value at `x`.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.0.my + self.slope() * (x - self.0.mx)
+    }
+    /// The coefficient of determination, the fraction of the variance in `y` explained by the fit.
+    pub fn r_squared(&self) -> f64 {
+        self.0.sxy * self.0.sxy / (self.0.sxx * self.0.syy)
+    }
+    /// The unbiased estimate of the residual variance.
+    pub fn residual_variance(&self) -> f64 {
+        assert!(
+            self.0.n > 2.0,
+            "Residual variance requires more than 2 samples."
+        );
+        let unexplained = self.0.syy - self.0.sxy * self.0.sxy / self.0.sxx;
+        unexplained.max(0.0) / (self.0.n - 2.0)
+    }
+}
+
+/// Fit a line to existing summary statistics.
+impl From<Covariance> for Ols {
+    fn from(c: Covariance) -> Self {
+        Ols(c)
+    }
+}
+
+/// We can add a new pair.
+impl Add<(f64, f64)> for Ols {
+    type Output = Self;
+    fn add(self, p: (f64, f64)) -> Self::Output {
+        Ols(self.0 + p)
+    }
+}
+
+/// We can add a new pair.
+impl AddAssign<(f64, f64)> for Ols {
+    fn add_assign(&mut self, p: (f64, f64)) {
+        self.0 += p;
+    }
+}
+
+/// Accumulate the pairs one at a time.
+impl FromIterator<(f64, f64)> for Ols {
+    fn from_iter<I: IntoIterator<Item = (f64, f64)>>(iter: I) -> Self {
+        Ols(iter.into_iter().collect())
+    }
+}
+
+/// Accumulate the pairs one at a time.
+impl<'a> FromIterator<&'a (f64, f64)> for Ols {
+    fn from_iter<I: IntoIterator<Item = &'a (f64, f64)>>(iter: I) -> Self {
+        Ols(iter.into_iter().collect())
+    }
+}
+
+/// Merge the sufficient statistics.
+impl Semigroup for Ols {
+    fn op(x: &Self, y: &Self) -> Self {
+        Ols(Semigroup::op(&x.0, &y.0))
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for Ols {
+    fn op_assign(&mut self, other: &Self) {
+        self.0.op_assign(&other.0);
+    }
+}
+
+/// No data.
+impl Monoid for Ols {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for Ols {}
@@ -0,0 +1,645 @@
+//! Mergeable sketches for counting distinct items
+//!
+//! [`HyperLogLog`] follows Flajolet et al., [HyperLogLog: the analysis of a near-optimal cardinality
+//! estimation algorithm](https://algo.inria.fr/flajolet/Publications/FlFuGaMe07.pdf), with the refinements of
+//! Heule, Nunkesser & Hall, [HyperLogLog in Practice](https://research.google/pubs/pub40671/) ("HLL++"): a
+//! 64-bit hash, so no large-range correction is needed, and a sparse representation at higher precision for
+//! small cardinalities.  In place of HLL++'s empirical bias tables, estimates use Ertl's [improved
+//! estimator](https://arxiv.org/abs/1702.01284), which is unbiased across the whole range without them.
+//! Each register keeps the maximum of what it's seen, so merging is register-wise max and is idempotent.
+//!
+//! [`Kmv`] follows Beyer et al., [On Synopses for Distinct-Value Estimation Under Multiset
+//! Operations](https://dl.acm.org/doi/10.1145/1247480.1247504): it keeps the `K` smallest hash values seen,
+//! whose spread gives the cardinality.  It's larger than a [`HyperLogLog`] of similar accuracy, but two
+//! sketches' minima are a uniform sample of their union, so it also estimates set similarity.
+//!
+//! [`ThetaSketch`] follows Dasgupta et al., [A Framework for Estimating Stream Expression
+//! Cardinalities](https://arxiv.org/abs/1510.01455): rather than a fixed number of hashes, it keeps every
+//! hash below a threshold `θ`, lowering `θ` whenever it would keep too many.  Those hashes are a uniform
+//! sample of the set at rate `θ`, so after cutting two sketches down to the smaller threshold, their
+//! intersection and difference are samples of the intersection and difference of the sets, and estimate
+//! those too.
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::hashing;
+use crate::traits::{Band, CommutativeMonoid, Monoid, Semigroup, SemigroupAssign, Semilattice};
+
+/// Precision of the sparse representation
+const SPARSE: u32 = 25;
+
+/// A distinct-count sketch with `2^P` registers, for `P` from 4 to 18
+///
+/// The relative standard error is about `1.04 / √(2^P)`, so 0.8% at the default `P = 14`, for 16KiB.  Until
+/// it has seen roughly `2^P / 4` distinct items the sketch stores only the registers they touched, at
+/// precision 25, which is both smaller and nearly exact; it is promoted to the dense form automatically
+/// once that would take less memory, including during a merge.  The representation depends only on the
+/// items seen, so equal sets give equal sketches however they were combined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HyperLogLog<const P: u8 = 14>(Registers);
+
+/// Storage for a [`HyperLogLog`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Registers {
+    /// Entries `index << 6 | rank` at precision [`SPARSE`], sorted by index
+    Sparse(Vec<u32>),
+    /// A rank for each of the `2^P` registers
+    Dense(Vec<u8>),
+}
+
+impl<const P: u8> Default for HyperLogLog<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const P: u8> HyperLogLog<P> {
+    /// Compile-time check on the precision
+    const VALID: () = assert!(4 <= P && P <= 18, "Precision must lie between 4 and 18.");
+    /// An empty sketch.
+    pub fn new() -> Self {
+        let () = Self::VALID;
+        HyperLogLog(Registers::Sparse(Vec::new()))
+    }
+    /// Record an item.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        let h = hashing::hash(t, 0);
+        match &mut self.0 {
+            Registers::Sparse(entries) => {
+                let (index, rank) = split(h, SPARSE);
+                let entry = (index as u32) << 6 | u32::from(rank);
+                match entries.binary_search_by_key(&index, |e| (e >> 6) as usize) {
+                    Ok(i) => entries[i] = entries[i].max(entry),
+                    Err(i) => entries.insert(i, entry),
+                }
+                self.promote_if_full();
+            }
+            Registers::Dense(ranks) => {
+                let (index, rank) = split(h, u32::from(P));
+                ranks[index] = ranks[index].max(rank);
+            }
+        }
+    }
+    /// Whether no items have been seen.
+    pub fn is_empty(&self) -> bool {
+        match &self.0 {
+            Registers::Sparse(entries) => entries.is_empty(),
+            Registers::Dense(ranks) => ranks.iter().all(|&r| r == 0),
+        }
+    }
+    /// Whether the sketch is still in its compact sparse form.
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.0, Registers::Sparse(_))
+    }
+    /// The approximate number of bytes of register storage.
+    pub fn size_bytes(&self) -> usize {
+        match &self.0 {
+            Registers::Sparse(entries) => 4 * entries.len(),
+            Registers::Dense(ranks) => ranks.len(),
+        }
+    }
+    /// The relative standard error of [`estimate`](HyperLogLog::estimate) once the sketch is dense.
+    pub fn standard_error() -> f64 {
+        1.04 / f64::from(1u32 << P).sqrt()
+    }
+    /// An estimate of the number of distinct items seen.
+    pub fn estimate(&self) -> f64 {
+        match &self.0 {
+            Registers::Sparse(entries) => {
+                let q = 64 - SPARSE;
+                let mut counts = vec![0; q as usize + 2];
+                counts[0] = (1 << SPARSE) - entries.len() as u64;
+                for e in entries {
+                    counts[(e & 0x3f) as usize] += 1;
+                }
+                ertl(&counts, SPARSE)
+            }
+            Registers::Dense(ranks) => {
+                let q = 64 - u32::from(P);
+                let mut counts = vec![0; q as usize + 2];
+                for &r in ranks {
+                    counts[r as usize] += 1;
+                }
+                ertl(&counts, u32::from(P))
+            }
+        }
+    }
+    /// Switch to the dense form if the sparse one has grown as large.
+    fn promote_if_full(&mut self) {
+        if let Registers::Sparse(entries) = &self.0 {
+            if 4 * entries.len() > 1 << P {
+                self.0 = Registers::Dense(densify::<P>(entries));
+            }
+        }
+    }
+}
+
+/// Split a hash into a register index from its top `p` bits, and the rank (one more than the number of
+/// leading zeros) of the rest.
+fn split(h: u64, p: u32) -> (usize, u8) {
+    let q = 64 - p;
+    let rank = (h << p).leading_zeros().min(q) + 1;
+    ((h >> q) as usize, rank as u8)
+}
+
+/// The dense registers at precision `P` equivalent to sparse `entries`.
+///
+/// A sparse index carries `SPARSE − P` more bits than a dense one; if they're all zero, they add to the
+/// rank of the remaining bits, otherwise they determine it.
+fn densify<const P: u8>(entries: &[u32]) -> Vec<u8> {
+    let extra = SPARSE - u32::from(P);
+    let mut ranks = vec![0; 1 << P];
+    for e in entries {
+        let index = e >> 6;
+        let low = index & ((1 << extra) - 1);
+        let rank = if low == 0 {
+            extra + (e & 0x3f)
+        } else {
+            extra - (32 - low.leading_zeros()) + 1
+        };
+        let r = &mut ranks[(index >> extra) as usize];
+        *r = (*r).max(rank as u8);
+    }
+    ranks
+}
+
+/// Ertl's improved estimate from `counts[k]`, the number of the `2^p` registers with rank `k`.
+fn ertl(counts: &[u64], p: u32) -> f64 {
+    let m = f64::from(1u32 << p);
+    let q = counts.len() - 2;
+    if counts[0] as f64 == m {
+        return 0.0;
+    }
+    let mut z = m * tau(1.0 - counts[q + 1] as f64 / m);
+    for &c in counts[1..=q].iter().rev() {
+        z = 0.5 * (z + c as f64);
+    }
+    z += m * sigma(counts[0] as f64 / m);
+    m * m / (2.0 * std::f64::consts::LN_2 * z)
+}
+
+/// `σ(x) = x + Σ_{k ≥ 1} x^(2^k) 2^(k − 1)`, for `x` in `[0, 1)`
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let (mut y, mut z) = (1.0, x);
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if z == previous {
+            return z;
+        }
+    }
+}
+
+/// `τ(x) = (1 − x − Σ_{k ≥ 1} (1 − x^(2^−k))² 2^−k) / 3`, for `x` in `[0, 1]`
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let (mut y, mut z) = (1.0, 1.0 - x);
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z == previous {
+            return z / 3.0;
+        }
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const P: u8> Add<T> for HyperLogLog<P> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const P: u8> AddAssign<T> for HyperLogLog<P> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const P: u8> Extend<T> for HyperLogLog<P> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const P: u8> FromIterator<T> for HyperLogLog<P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Register-wise max, promoting to the dense form as needed.
+impl<const P: u8> Semigroup for HyperLogLog<P> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty sketch.
+impl<const P: u8> Monoid for HyperLogLog<P> {
+    fn zero() -> Self {
+        Self::new()
+    }
+}
+
+/// Merge in place.
+impl<const P: u8> SemigroupAssign for HyperLogLog<P> {
+    fn op_assign(&mut self, other: &Self) {
+        match (&mut self.0, &other.0) {
+            (Registers::Sparse(xs), Registers::Sparse(ys)) => {
+                let mut merged = Vec::with_capacity(xs.len() + ys.len());
+                let (mut i, mut j) = (0, 0);
+                while i < xs.len() && j < ys.len() {
+                    let (x, y) = (xs[i], ys[j]);
+                    match (x >> 6).cmp(&(y >> 6)) {
+                        Ordering::Less => {
+                            merged.push(x);
+                            i += 1;
+                        }
+                        Ordering::Greater => {
+                            merged.push(y);
+                            j += 1;
+                        }
+                        Ordering::Equal => {
+                            merged.push(x.max(y));
+                            i += 1;
+                            j += 1;
+                        }
+                    }
+                }
+                merged.extend_from_slice(&xs[i..]);
+                merged.extend_from_slice(&ys[j..]);
+                *xs = merged;
+                self.promote_if_full();
+            }
+            (Registers::Sparse(xs), Registers::Dense(ys)) => {
+                let mut ranks = densify::<P>(xs);
+                for (r, &s) in ranks.iter_mut().zip(ys) {
+                    *r = (*r).max(s);
+                }
+                self.0 = Registers::Dense(ranks);
+            }
+            (Registers::Dense(xs), Registers::Sparse(ys)) => {
+                for (r, &s) in xs.iter_mut().zip(&densify::<P>(ys)) {
+                    *r = (*r).max(s);
+                }
+            }
+            (Registers::Dense(xs), Registers::Dense(ys)) => {
+                for (r, &s) in xs.iter_mut().zip(ys) {
+                    *r = (*r).max(s);
+                }
+            }
+        }
+    }
+}
+
+/// Max is commutative.
+impl<const P: u8> CommutativeMonoid for HyperLogLog<P> {}
+
+/// Seeing the same items twice changes nothing.
+impl<const P: u8> Band for HyperLogLog<P> {}
+
+/// Register-wise max is a join.
+impl<const P: u8> Semilattice for HyperLogLog<P> {}
+
+/// A distinct-count sketch keeping the `K` smallest hashes of the items seen
+///
+/// The relative standard error is about `1 / √(K − 2)`, and counts below `K` are exact (barring hash
+/// collisions).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Kmv<const K: usize = 1024>(Vec<u64>);
+
+impl<const K: usize> Default for Kmv<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize> Kmv<K> {
+    /// Compile-time check on the size
+    const VALID: () = assert!(K >= 2, "Kmv needs to keep at least 2 hashes.");
+    /// An empty sketch.
+    pub fn new() -> Self {
+        let () = Self::VALID;
+        Kmv(Vec::new())
+    }
+    /// Record an item.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        let h = hashing::hash(t, 0);
+        if self.0.len() == K && h >= self.0[K - 1] {
+            return;
+        }
+        if let Err(i) = self.0.binary_search(&h) {
+            self.0.insert(i, h);
+            self.0.truncate(K);
+        }
+    }
+    /// Whether no items have been seen.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The number of hashes kept, at most `K`.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// An estimate of the number of distinct items seen: `(K − 1) / U`, where `U` is the `K`th smallest hash
+    /// as a fraction of the range.
+    pub fn estimate(&self) -> f64 {
+        if self.0.len() < K {
+            self.0.len() as f64
+        } else {
+            (K - 1) as f64 / unit(self.0[K - 1])
+        }
+    }
+    /// An estimate of the Jaccard similarity `|A ∩ B| / |A ∪ B|` of the two sets of items, or `None` if both
+    /// are empty.
+    pub fn jaccard(&self, other: &Self) -> Option<f64> {
+        self.jaccard_within(other, &Semigroup::op(self, other))
+    }
+    /// An estimate of the number of distinct items seen by both sketches.
+    pub fn intersection(&self, other: &Self) -> f64 {
+        let union = Semigroup::op(self, other);
+        self.jaccard_within(other, &union)
+            .map_or(0.0, |j| j * union.estimate())
+    }
+    /// The fraction of the minima of `union` seen by both sketches; any of them seen by either is among that
+    /// sketch's own minima too.
+    fn jaccard_within(&self, other: &Self, union: &Self) -> Option<f64> {
+        if union.is_empty() {
+            return None;
+        }
+        let shared = union
+            .0
+            .iter()
+            .filter(|h| self.0.binary_search(h).is_ok() && other.0.binary_search(h).is_ok())
+            .count();
+        Some(shared as f64 / union.len() as f64)
+    }
+}
+
+/// A hash as a fraction of the range, in `(0, 1]`.
+fn unit(h: u64) -> f64 {
+    (h as f64 + 1.0) / 2f64.powi(64)
+}
+
+/// The distinct hashes of two sorted lists, in order.
+fn merge_sorted<'a>(xs: &'a [u64], ys: &'a [u64]) -> impl Iterator<Item = u64> + 'a {
+    let (mut xs, mut ys) = (xs.iter().peekable(), ys.iter().peekable());
+    std::iter::from_fn(move || match (xs.peek(), ys.peek()) {
+        (Some(a), Some(b)) => match a.cmp(b) {
+            Ordering::Less => xs.next().copied(),
+            Ordering::Greater => ys.next().copied(),
+            Ordering::Equal => {
+                ys.next();
+                xs.next().copied()
+            }
+        },
+        _ => xs.next().or_else(|| ys.next()).copied(),
+    })
+}
+
+/// We can add a new item.
+impl<T: Hash, const K: usize> Add<T> for Kmv<K> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const K: usize> AddAssign<T> for Kmv<K> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const K: usize> Extend<T> for Kmv<K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const K: usize> FromIterator<T> for Kmv<K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+/// The `K` smallest of both sides' hashes.
+impl<const K: usize> Semigroup for Kmv<K> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Kmv(merge_sorted(&x.0, &y.0).take(K).collect())
+    }
+}
+
+/// The empty sketch.
+impl<const K: usize> Monoid for Kmv<K> {
+    fn zero() -> Self {
+        Self::new()
+    }
+}
+
+/// Merge in place.
+impl<const K: usize> SemigroupAssign for Kmv<K> {}
+
+/// Minima can be taken in any order.
+impl<const K: usize> CommutativeMonoid for Kmv<K> {}
+
+/// Seeing the same items twice changes nothing.
+impl<const K: usize> Band for Kmv<K> {}
+
+/// The `K` smallest of a union is a join.
+impl<const K: usize> Semilattice for Kmv<K> {}
+
+/// A distinct-count sketch keeping the hashes below a threshold `θ`, at most `K` of them
+///
+/// The relative standard error is about `1 / √K`, and counts up to `K` are exact (barring hash collisions).
+/// Besides union, the monoid operation, there are [`intersection`](ThetaSketch::intersection) and
+/// [`difference`](ThetaSketch::difference), whose results are sketches in turn, so whole set expressions
+/// can be estimated; the error grows as the result gets small relative to the sets it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThetaSketch<const K: usize = 4096> {
+    /// Only hashes below this are kept
+    theta: u64,
+    /// The hashes kept, sorted
+    hashes: Vec<u64>,
+}
+
+impl<const K: usize> Default for ThetaSketch<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize> ThetaSketch<K> {
+    /// Compile-time check on the size
+    const VALID: () = assert!(K >= 1, "Theta sketches need to keep at least 1 hash.");
+    /// An empty sketch.
+    pub fn new() -> Self {
+        let () = Self::VALID;
+        ThetaSketch {
+            theta: u64::MAX,
+            hashes: Vec::new(),
+        }
+    }
+    /// Record an item.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        let h = hashing::hash(t, 0);
+        if h >= self.theta {
+            return;
+        }
+        if let Err(i) = self.hashes.binary_search(&h) {
+            self.hashes.insert(i, h);
+            self.shrink();
+        }
+    }
+    /// Whether the sketch has no hashes, as when nothing has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+    /// The number of hashes kept, at most `K`.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+    /// The sampling rate `θ`, as a fraction of the range of hashes.
+    pub fn theta(&self) -> f64 {
+        if self.theta == u64::MAX {
+            1.0
+        } else {
+            unit(self.theta)
+        }
+    }
+    /// An estimate of the number of distinct items: the number of hashes kept, divided by `θ`.
+    pub fn estimate(&self) -> f64 {
+        self.hashes.len() as f64 / self.theta()
+    }
+    /// A sketch of the items seen by both.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let theta = self.theta.min(other.theta);
+        let hashes = self
+            .hashes
+            .iter()
+            .take_while(|&&h| h < theta)
+            .filter(|h| other.hashes.binary_search(h).is_ok())
+            .copied()
+            .collect();
+        ThetaSketch { theta, hashes }
+    }
+    /// A sketch of the items seen by `self` but not by `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let theta = self.theta.min(other.theta);
+        let hashes = self
+            .hashes
+            .iter()
+            .take_while(|&&h| h < theta)
+            .filter(|h| other.hashes.binary_search(h).is_err())
+            .copied()
+            .collect();
+        ThetaSketch { theta, hashes }
+    }
+    /// If there are more than `K` hashes, lower `θ` to the `(K + 1)`th smallest and keep only those below.
+    fn shrink(&mut self) {
+        if self.hashes.len() > K {
+            self.theta = self.hashes[K];
+            self.hashes.truncate(K);
+        }
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const K: usize> Add<T> for ThetaSketch<K> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const K: usize> AddAssign<T> for ThetaSketch<K> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const K: usize> Extend<T> for ThetaSketch<K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const K: usize> FromIterator<T> for ThetaSketch<K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Union: the hashes of either below the smaller threshold, cut back to `K`.
+impl<const K: usize> Semigroup for ThetaSketch<K> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        let theta = x.theta.min(y.theta);
+        let hashes = merge_sorted(&x.hashes, &y.hashes)
+            .take_while(|&h| h < theta)
+            .collect();
+        let mut z = ThetaSketch { theta, hashes };
+        z.shrink();
+        z
+    }
+}
+
+/// The empty sketch.
+impl<const K: usize> Monoid for ThetaSketch<K> {
+    fn zero() -> Self {
+        Self::new()
+    }
+}
+
+/// Merge in place.
+impl<const K: usize> SemigroupAssign for ThetaSketch<K> {}
+
+/// Union is commutative.
+impl<const K: usize> CommutativeMonoid for ThetaSketch<K> {}
+
+/// Seeing the same items twice changes nothing.
+impl<const K: usize> Band for ThetaSketch<K> {}
+
+/// Union is a join.
+impl<const K: usize> Semilattice for ThetaSketch<K> {}
@@ -0,0 +1,132 @@
+//! The categorical distribution, fitted by counting
+//!
+//! This is the discrete counterpart of [`Gaussian`](crate::gaussian::Gaussian): the maximum-likelihood
+//! probability of each label is its observed frequency, and counts from separate shards merge by addition.
+
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::impurity::EntropyCounter;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Parameterized categorical distribution over labels of type `T`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Categorical<T: Eq + Hash>(EntropyCounter<T>);
+
+impl<T: Eq + Hash> Default for Categorical<T> {
+    fn default() -> Self {
+        Categorical(EntropyCounter::default())
+    }
+}
+
+impl<T: Eq + Hash> Categorical<T> {
+    /// Construct from a single observation.
+    pub fn new(t: T) -> Self {
+        Categorical(EntropyCounter::new(t))
+    }
+    /// The underlying counts.
+    pub fn counts(&self) -> &EntropyCounter<T> {
+        &self.0
+    }
+    /// The number of observations.
+    pub fn count(&self) -> u64 {
+        self.0.total()
+    }
+    /// The probability of `t`: the fraction of observations that were `t`.
+    pub fn prob(&self, t: &T) -> f64 {
+        assert!(self.count() > 0, "Probabilities require at least 1 sample.");
+        self.0.count(t) as f64 / self.count() as f64
+    }
+    /// Natural logarithm of the [`prob`](Categorical::prob).
+    pub fn log_prob(&self, t: &T) -> f64 {
+        self.prob(t).ln()
+    }
+    /// The most frequent label, or `None` if nothing has been observed; ties are broken arbitrarily.
+    pub fn mode(&self) -> Option<&T> {
+        self.0.iter().max_by_key(|&(_, c)| c).map(|(t, _)| t)
+    }
+    /// The Shannon entropy of the distribution, in nats.
+    pub fn entropy(&self) -> f64 {
+        self.0.shannon_entropy()
+    }
+}
+
+/// Use existing counts as a distribution.
+impl<T: Eq + Hash> From<EntropyCounter<T>> for Categorical<T> {
+    fn from(c: EntropyCounter<T>) -> Self {
+        Categorical(c)
+    }
+}
+
+/// We can add a new observation.
+impl<T: Eq + Hash> Add<T> for Categorical<T> {
+    type Output = Self;
+    fn add(self, t: T) -> Self::Output {
+        Categorical(self.0 + t)
+    }
+}
+
+/// We can add a new observation.
+impl<T: Eq + Hash> AddAssign<T> for Categorical<T> {
+    fn add_assign(&mut self, t: T) {
+        self.0 += t;
+    }
+}
+
+/// Count the observations one at a time.
+impl<T: Eq + Hash> FromIterator<T> for Categorical<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Categorical(iter.into_iter().collect())
+    }
+}
+
+/// Count the observations one at a time.
+impl<T: Eq + Hash> Extend<T> for Categorical<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+/// Draw labels in proportion to how often they were observed; this needs at least one observation.
+#[cfg(feature = "rand")]
+impl<T: Eq + Hash + Clone> rand::distributions::Distribution<T> for Categorical<T> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> T {
+        assert!(self.count() > 0, "Sampling requires at least 1 sample.");
+        let mut r = rng.gen_range(0..self.count());
+        for (t, c) in self.0.iter() {
+            if r < c {
+                return t.clone();
+            }
+            r -= c;
+        }
+        unreachable!("the counts add up to the total")
+    }
+}
+
+/// Add the counts label by label.
+impl<T: Eq + Hash + Clone> Semigroup for Categorical<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        Categorical(Semigroup::op(&x.0, &y.0))
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        Categorical(Semigroup::op_owned(x.0, y.0))
+    }
+}
+
+/// Merge in place.
+impl<T: Eq + Hash + Clone> SemigroupAssign for Categorical<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.0.op_assign(&other.0);
+    }
+}
+
+/// No observations.
+impl<T: Eq + Hash + Clone> Monoid for Categorical<T> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Counts can be added in any order.
+impl<T: Eq + Hash + Clone> CommutativeMonoid for Categorical<T> {}
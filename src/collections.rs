@@ -0,0 +1,496 @@
+//! Collections whose natural way to combine is a merge rather than a concatenation
+//!
+//! Each keeps an invariant (sortedness, say) that makes merging two partial results from independent chunks
+//! cheaper than rebuilding from scratch, which is exactly what a parallel reduction needs.
+use std::{
+    cmp::Ordering,
+    iter::{FromIterator, Sum},
+    ops::{Range, Sub},
+};
+
+use crate::newtypes::{Intersection, SymmetricDifference};
+use crate::traits::{
+    Band, BoundedLattice, CancellativeMonoid, CommutativeMonoid, Group, Lattice, Monoid, Monus,
+    Semigroup, SemigroupAssign, Semilattice,
+};
+
+/// A vector kept in ascending order, combined by a linear-time merge
+///
+/// With `DEDUP = true` (see [`SortedSet`]) equal elements are kept only once, making this a semilattice.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortedVec<T, const DEDUP: bool = false>(Vec<T>);
+
+/// Sorted vectors without repeated elements.
+pub type SortedSet<T> = SortedVec<T, true>;
+
+impl<T: Ord, const DEDUP: bool> SortedVec<T, DEDUP> {
+    /// Sort (and, if `DEDUP`, deduplicate) `v`.
+    pub fn new(mut v: Vec<T>) -> Self {
+        v.sort();
+        if DEDUP {
+            v.dedup();
+        }
+        SortedVec(v)
+    }
+    /// The vector containing only `t`.
+    pub fn of(t: T) -> Self {
+        SortedVec(vec![t])
+    }
+    /// Add `t` in its place, in O(n).
+    pub fn insert(&mut self, t: T) {
+        match self.0.binary_search(&t) {
+            Ok(_) if DEDUP => {}
+            Ok(i) | Err(i) => self.0.insert(i, t),
+        }
+    }
+    /// Whether `t` is present, in O(log n).
+    pub fn contains(&self, t: &T) -> bool {
+        self.0.binary_search(t).is_ok()
+    }
+    /// The elements in ascending order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The underlying vector, in ascending order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+    /// Merge two ascending sequences, taking from the left first on ties.
+    fn merge(xs: impl IntoIterator<Item = T>, ys: impl IntoIterator<Item = T>, n: usize) -> Self {
+        let mut zs: Vec<T> = Vec::with_capacity(n);
+        let mut push = |t: T| {
+            if !(DEDUP && zs.last() == Some(&t)) {
+                zs.push(t);
+            }
+        };
+        let (mut xs, mut ys) = (xs.into_iter().peekable(), ys.into_iter().peekable());
+        loop {
+            let next = match (xs.peek(), ys.peek()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Ordering::Greater => ys.next(),
+                    _ => xs.next(),
+                },
+                (Some(_), None) => xs.next(),
+                (None, Some(_)) => ys.next(),
+                (None, None) => break,
+            };
+            push(next.expect("peeked"));
+        }
+        SortedVec(zs)
+    }
+}
+
+impl<T: Ord, const DEDUP: bool> FromIterator<T> for SortedVec<T, DEDUP> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T, const DEDUP: bool> From<SortedVec<T, DEDUP>> for Vec<T> {
+    fn from(SortedVec(v): SortedVec<T, DEDUP>) -> Self {
+        v
+    }
+}
+
+/// Linear-time merge.
+impl<T: Ord + Clone, const DEDUP: bool> Semigroup for SortedVec<T, DEDUP> {
+    const IDEMPOTENT: bool = DEDUP;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::merge(x.0.iter().cloned(), y.0.iter().cloned(), x.len() + y.len())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        let n = x.len() + y.len();
+        Self::merge(x.0, y.0, n)
+    }
+}
+
+/// The empty vector.
+impl<T: Ord + Clone, const DEDUP: bool> Monoid for SortedVec<T, DEDUP> {
+    fn zero() -> Self {
+        SortedVec(Vec::new())
+    }
+}
+
+/// Merge in place, cloning only the right side.
+impl<T: Ord + Clone, const DEDUP: bool> SemigroupAssign for SortedVec<T, DEDUP> {
+    fn op_assign(&mut self, other: &Self) {
+        let n = self.len() + other.len();
+        let xs = std::mem::take(&mut self.0);
+        *self = Self::merge(xs, other.0.iter().cloned(), n);
+    }
+}
+
+/// The result is sorted no matter which side came first.
+impl<T: Ord + Clone, const DEDUP: bool> CommutativeMonoid for SortedVec<T, DEDUP> {}
+
+/// Merging a set with itself changes nothing.
+impl<T: Ord + Clone> Band for SortedSet<T> {}
+
+/// Sorted sets under union.
+impl<T: Ord + Clone> Semilattice for SortedSet<T> {}
+
+/// A union of disjoint half-open ranges `start..end`, combined by union
+///
+/// Ranges are stored sorted and coalesced (touching ranges like `0..2` and `2..5` become `0..5`), so
+/// equality is structural and queries take O(log n).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T>(Vec<(T, T)>);
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet(Vec::new())
+    }
+}
+
+impl<T: Ord + Clone> IntervalSet<T> {
+    /// The set covering just `r` (which is empty if `r` is).
+    pub fn of(r: Range<T>) -> Self {
+        if r.start < r.end {
+            IntervalSet(vec![(r.start, r.end)])
+        } else {
+            IntervalSet(Vec::new())
+        }
+    }
+    /// Add the points of `r`.
+    pub fn insert(&mut self, r: Range<T>) {
+        self.op_assign(&Self::of(r));
+    }
+    /// Index of the last range starting at or before `t`, if any.
+    fn floor(&self, t: &T) -> Option<usize> {
+        self.0.partition_point(|(s, _)| s <= t).checked_sub(1)
+    }
+    /// Whether `t` lies in one of the ranges.
+    pub fn contains(&self, t: &T) -> bool {
+        self.floor(t).is_some_and(|i| *t < self.0[i].1)
+    }
+    /// Whether every point of `r` is covered; trivially true if `r` is empty.
+    pub fn covers(&self, r: &Range<T>) -> bool {
+        r.start >= r.end || self.floor(&r.start).is_some_and(|i| r.end <= self.0[i].1)
+    }
+    /// Whether some point of `r` is covered.
+    pub fn overlaps(&self, r: &Range<T>) -> bool {
+        let i = self.0.partition_point(|(_, e)| *e <= r.start);
+        r.start < r.end && self.0.get(i).is_some_and(|(s, _)| *s < r.end)
+    }
+    /// The disjoint ranges in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.0.iter().map(|(s, e)| s.clone()..e.clone())
+    }
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether no points are covered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The total length covered, e.g. a `Duration` for ranges of `Instant`s.
+    pub fn measure<D: Sum>(&self) -> D
+    where
+        T: Sub<Output = D>,
+    {
+        self.0.iter().map(|(s, e)| e.clone() - s.clone()).sum()
+    }
+    /// Coalesce ranges sorted by start into disjoint, non-touching ones.
+    fn coalesce(sorted: impl IntoIterator<Item = (T, T)>, n: usize) -> Self {
+        let mut rs: Vec<(T, T)> = Vec::with_capacity(n);
+        for (s, e) in sorted {
+            match rs.last_mut() {
+                Some(last) if s <= last.1 => {
+                    if e > last.1 {
+                        last.1 = e;
+                    }
+                }
+                _ => rs.push((s, e)),
+            }
+        }
+        IntervalSet(rs)
+    }
+    /// Union of two sets' ranges, merged by start.
+    fn union(
+        xs: impl IntoIterator<Item = (T, T)>,
+        ys: impl IntoIterator<Item = (T, T)>,
+        n: usize,
+    ) -> Self {
+        let (mut xs, mut ys) = (xs.into_iter().peekable(), ys.into_iter().peekable());
+        let merged = std::iter::from_fn(|| match (xs.peek(), ys.peek()) {
+            (Some(x), Some(y)) if y.0 < x.0 => ys.next(),
+            (Some(_), _) => xs.next(),
+            (None, _) => ys.next(),
+        });
+        Self::coalesce(merged, n)
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut rs = iter
+            .into_iter()
+            .filter(|r| r.start < r.end)
+            .map(|r| (r.start, r.end))
+            .collect::<Vec<_>>();
+        rs.sort();
+        let n = rs.len();
+        Self::coalesce(rs, n)
+    }
+}
+
+/// Union, in time linear in the number of ranges.
+impl<T: Ord + Clone> Semigroup for IntervalSet<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::union(x.0.iter().cloned(), y.0.iter().cloned(), x.len() + y.len())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        let n = x.len() + y.len();
+        Self::union(x.0, y.0, n)
+    }
+}
+
+/// Nothing covered.
+impl<T: Ord + Clone> Monoid for IntervalSet<T> {
+    fn zero() -> Self {
+        IntervalSet(Vec::new())
+    }
+}
+
+/// Union in place, cloning only the right side.
+impl<T: Ord + Clone> SemigroupAssign for IntervalSet<T> {
+    fn op_assign(&mut self, other: &Self) {
+        let n = self.len() + other.len();
+        let xs = std::mem::take(&mut self.0);
+        *self = Self::union(xs, other.0.iter().cloned(), n);
+    }
+}
+
+/// Union is commutative.
+impl<T: Ord + Clone> CommutativeMonoid for IntervalSet<T> {}
+
+/// Union is idempotent.
+impl<T: Ord + Clone> Band for IntervalSet<T> {}
+
+/// Sets of points under union.
+impl<T: Ord + Clone> Semilattice for IntervalSet<T> {}
+
+/// A set of integers in `0..64 * WORDS`, stored densely as bits
+///
+/// As with the standard library's sets, the plain type combines by union; wrap it in [`Intersection`] or
+/// [`SymmetricDifference`] for the other operations.  Having a finite universe makes intersection a monoid
+/// (with the full set as its identity) and the subsets a bounded lattice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize>([u64; WORDS]);
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    /// One more than the largest element that fits.
+    pub const CAPACITY: usize = 64 * WORDS;
+
+    /// The empty set.
+    pub fn new() -> Self {
+        BitSet([0; WORDS])
+    }
+    /// The set of everything in `0..CAPACITY`.
+    pub fn full() -> Self {
+        BitSet([!0; WORDS])
+    }
+    /// Add `i`, returning whether it was newly added; panics if `i` is out of range.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (w, b) = (i / 64, 1 << (i % 64));
+        let fresh = self.0[w] & b == 0;
+        self.0[w] |= b;
+        fresh
+    }
+    /// Remove `i`, returning whether it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let present = self.contains(i);
+        if present {
+            self.0[i / 64] &= !(1 << (i % 64));
+        }
+        present
+    }
+    /// Whether `i` is present.
+    pub fn contains(&self, i: usize) -> bool {
+        i < Self::CAPACITY && self.0[i / 64] >> (i % 64) & 1 == 1
+    }
+    /// Number of elements, i.e. the population count.
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    /// Whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+    /// Whether every element of `self` is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & !b == 0)
+    }
+    /// The elements in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(w, &bits)| {
+            let mut bits = bits;
+            std::iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let b = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    64 * w + b
+                })
+            })
+        })
+    }
+    /// Combine word by word.
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        BitSet(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut s = Self::new();
+        for i in iter {
+            s.insert(i);
+        }
+        s
+    }
+}
+
+/// Union.
+impl<const WORDS: usize> Semigroup for BitSet<WORDS> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        x.zip_with(y, |a, b| a | b)
+    }
+}
+
+/// The empty set is the identity for union.
+impl<const WORDS: usize> Monoid for BitSet<WORDS> {
+    fn zero() -> Self {
+        Self::new()
+    }
+}
+
+/// Union in place.
+impl<const WORDS: usize> SemigroupAssign for BitSet<WORDS> {
+    fn op_assign(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a |= b;
+        }
+    }
+}
+
+/// Union is commutative.
+impl<const WORDS: usize> CommutativeMonoid for BitSet<WORDS> {}
+
+/// Union is idempotent.
+impl<const WORDS: usize> Band for BitSet<WORDS> {}
+
+/// Union is the join of the subset lattice.
+impl<const WORDS: usize> Semilattice for BitSet<WORDS> {}
+
+/// Set difference truncates at the empty set.
+impl<const WORDS: usize> Monus for BitSet<WORDS> {
+    fn monus(x: &Self, y: &Self) -> Self {
+        x.zip_with(y, |a, b| a & !b)
+    }
+}
+
+/// Subsets form a lattice under union and intersection.
+impl<const WORDS: usize> Lattice for BitSet<WORDS> {
+    fn join(x: &Self, y: &Self) -> Self {
+        x.zip_with(y, |a, b| a | b)
+    }
+    fn meet(x: &Self, y: &Self) -> Self {
+        x.zip_with(y, |a, b| a & b)
+    }
+}
+
+/// The universe is finite, so the subset lattice is bounded.
+impl<const WORDS: usize> BoundedLattice for BitSet<WORDS> {
+    fn top() -> Self {
+        Self::full()
+    }
+    fn bottom() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the elements common to both sides.
+impl<const WORDS: usize> Semigroup for Intersection<BitSet<WORDS>> {
+    const IDEMPOTENT: bool = true;
+    fn op(Intersection(x): &Self, Intersection(y): &Self) -> Self {
+        Intersection(x.zip_with(y, |a, b| a & b))
+    }
+}
+
+/// The full set is the identity for intersection.
+impl<const WORDS: usize> Monoid for Intersection<BitSet<WORDS>> {
+    fn zero() -> Self {
+        Intersection(BitSet::full())
+    }
+}
+
+/// Intersect in place.
+impl<const WORDS: usize> SemigroupAssign for Intersection<BitSet<WORDS>> {
+    fn op_assign(&mut self, Intersection(other): &Self) {
+        for (a, b) in self.0 .0.iter_mut().zip(&other.0) {
+            *a &= b;
+        }
+    }
+}
+
+/// Intersection is commutative.
+impl<const WORDS: usize> CommutativeMonoid for Intersection<BitSet<WORDS>> {}
+
+/// Intersection is idempotent.
+impl<const WORDS: usize> Band for Intersection<BitSet<WORDS>> {}
+
+/// Intersection is the meet of the subset lattice.
+impl<const WORDS: usize> Semilattice for Intersection<BitSet<WORDS>> {}
+
+/// Keep the elements in exactly one side.
+impl<const WORDS: usize> Semigroup for SymmetricDifference<BitSet<WORDS>> {
+    fn op(SymmetricDifference(x): &Self, SymmetricDifference(y): &Self) -> Self {
+        SymmetricDifference(x.zip_with(y, |a, b| a ^ b))
+    }
+}
+
+/// The empty set.
+impl<const WORDS: usize> Monoid for SymmetricDifference<BitSet<WORDS>> {
+    fn zero() -> Self {
+        SymmetricDifference(BitSet::new())
+    }
+}
+
+/// Toggle membership in place.
+impl<const WORDS: usize> SemigroupAssign for SymmetricDifference<BitSet<WORDS>> {
+    fn op_assign(&mut self, SymmetricDifference(other): &Self) {
+        for (a, b) in self.0 .0.iter_mut().zip(&other.0) {
+            *a ^= b;
+        }
+    }
+}
+
+/// Symmetric difference is commutative.
+impl<const WORDS: usize> CommutativeMonoid for SymmetricDifference<BitSet<WORDS>> {}
+
+/// Every set cancels itself.
+impl<const WORDS: usize> Group for SymmetricDifference<BitSet<WORDS>> {
+    fn invert(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Toggling can always be undone.
+impl<const WORDS: usize> CancellativeMonoid for SymmetricDifference<BitSet<WORDS>> {}
@@ -0,0 +1,423 @@
+//! Wrappers that build new semigroups & monoids out of existing ones
+use std::{fmt, rc::Rc, sync::Arc};
+
+use crate::traits::{
+    Band, CommutativeMonoid, Group, Monoid, MonoidAction, Semigroup, SemigroupAssign, Semilattice,
+};
+
+/// The same semigroup with its arguments flipped, i.e. `op(Dual(x), Dual(y)) == Dual(op(y, x))`
+///
+/// Folding with `Dual` accumulates from the right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dual<M>(pub M);
+
+/// Flip the order of the arguments.
+impl<M: Semigroup> Semigroup for Dual<M> {
+    const IDEMPOTENT: bool = M::IDEMPOTENT;
+    fn op(Dual(x): &Self, Dual(y): &Self) -> Self {
+        Dual(M::op(y, x))
+    }
+    fn op_owned(Dual(x): Self, Dual(y): Self) -> Self {
+        Dual(M::op_owned(y, x))
+    }
+}
+
+/// The identity is the same on either side.
+impl<M: Monoid> Monoid for Dual<M> {
+    fn zero() -> Self {
+        Dual(M::zero())
+    }
+}
+
+/// Flipping a commutative operation does nothing.
+impl<M: CommutativeMonoid> CommutativeMonoid for Dual<M> {}
+
+/// Flipping preserves idempotency.
+impl<M: Band> Band for Dual<M> {}
+
+/// Flipping preserves idempotency.
+impl<M: Semilattice> Semilattice for Dual<M> {}
+
+/// Inverses are the same on either side.
+impl<M: Group> Group for Dual<M> {
+    fn invert(&self) -> Self {
+        Dual(self.0.invert())
+    }
+}
+
+/// Combine in place via the flipped operation.
+impl<M: Semigroup> SemigroupAssign for Dual<M> {}
+
+/// `Result` with errors accumulated rather than short-circuited
+///
+/// Successes combine via `T`'s operation and failures via `E`'s; combining a success with a failure keeps
+/// the failure.  So folding a batch of checks reports *every* error, not just the first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Validation<T, E>(pub Result<T, E>);
+
+impl<T, E> Validation<T, E> {
+    /// A success.
+    pub fn ok(t: T) -> Self {
+        Validation(Ok(t))
+    }
+    /// A failure.
+    pub fn err(e: E) -> Self {
+        Validation(Err(e))
+    }
+    /// Unwrap the underlying `Result`.
+    pub fn into_result(self) -> Result<T, E> {
+        self.0
+    }
+}
+
+impl<T: Monoid, E: Semigroup + Clone> Validation<T, E> {
+    /// Fold an iterator of `Result`s, combining all successes if there are no failures and all failures
+    /// otherwise.
+    pub fn fold(results: impl IntoIterator<Item = Result<T, E>>) -> Result<T, E> {
+        results
+            .into_iter()
+            .fold(Validation::ok(T::zero()), |v, r| {
+                Validation::op_owned(v, Validation(r))
+            })
+            .0
+    }
+}
+
+impl<T, E> Validation<Vec<T>, Vec<E>> {
+    /// Collect an iterator of `Result`s into every success if there are no failures, or every failure
+    /// otherwise.
+    pub fn collect_all(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, Vec<E>> {
+        let mut oks = Vec::new();
+        let mut errs = Vec::new();
+        for r in results {
+            match r {
+                Ok(t) if errs.is_empty() => oks.push(t),
+                Ok(_) => {}
+                Err(e) => errs.push(e),
+            }
+        }
+        if errs.is_empty() {
+            Ok(oks)
+        } else {
+            Err(errs)
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validation<T, E> {
+    fn from(r: Result<T, E>) -> Self {
+        Validation(r)
+    }
+}
+
+impl<T: Monoid, E: Semigroup + Clone> FromIterator<Result<T, E>> for Validation<T, E> {
+    fn from_iter<I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Self {
+        Validation(Validation::fold(iter))
+    }
+}
+
+/// Combine successes if both succeeded, otherwise combine the failures.
+impl<T: Semigroup, E: Semigroup + Clone> Semigroup for Validation<T, E> {
+    const IDEMPOTENT: bool = T::IDEMPOTENT && E::IDEMPOTENT;
+    fn op(x: &Self, y: &Self) -> Self {
+        Validation(match (&x.0, &y.0) {
+            (Ok(a), Ok(b)) => Ok(T::op(a, b)),
+            (Err(a), Err(b)) => Err(E::op(a, b)),
+            (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e.clone()),
+        })
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        Validation(match (x.0, y.0) {
+            (Ok(a), Ok(b)) => Ok(T::op_owned(a, b)),
+            (Err(a), Err(b)) => Err(E::op_owned(a, b)),
+            (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+        })
+    }
+}
+
+/// The empty success.
+impl<T: Monoid, E: Semigroup + Clone> Monoid for Validation<T, E> {
+    fn zero() -> Self {
+        Validation::ok(T::zero())
+    }
+}
+
+/// Commutative if both the successes and the failures are.
+impl<T: CommutativeMonoid, E: CommutativeMonoid + Clone> CommutativeMonoid for Validation<T, E> {}
+
+/// Idempotent if both the successes and the failures are.
+impl<T: Band, E: Band + Clone> Band for Validation<T, E> {}
+
+/// Only a failure on the right needs cloning when combining in place.
+impl<T: SemigroupAssign, E: SemigroupAssign + Clone> SemigroupAssign for Validation<T, E> {
+    fn op_assign(&mut self, other: &Self) {
+        match (&mut self.0, &other.0) {
+            (Ok(a), Ok(b)) => a.op_assign(b),
+            (Err(a), Err(b)) => a.op_assign(b),
+            (Ok(_), Err(e)) => self.0 = Err(e.clone()),
+            (Err(_), Ok(_)) => {}
+        }
+    }
+}
+
+/// Vectors combined element-wise, e.g. per-bucket histograms or feature vectors
+///
+/// The shorter side is implicitly padded with [`Monoid::zero`], so the empty vector is the identity.  Padding
+/// isn't normalized away: `ZipVec(vec![])` and `ZipVec(vec![M::zero()])` act alike but compare unequal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZipVec<M>(pub Vec<M>);
+
+impl<M> FromIterator<M> for ZipVec<M> {
+    fn from_iter<I: IntoIterator<Item = M>>(iter: I) -> Self {
+        ZipVec(iter.into_iter().collect())
+    }
+}
+
+/// Combine matching positions; the tail of the longer side passes through.
+impl<M: Monoid + Clone> Semigroup for ZipVec<M> {
+    const IDEMPOTENT: bool = M::IDEMPOTENT;
+    fn op(x: &Self, y: &Self) -> Self {
+        let (xs, ys) = (&x.0, &y.0);
+        let n = xs.len().min(ys.len());
+        let mut zs = Vec::with_capacity(xs.len().max(ys.len()));
+        zs.extend(xs.iter().zip(ys).map(|(a, b)| M::op(a, b)));
+        zs.extend_from_slice(&xs[n..]);
+        zs.extend_from_slice(&ys[n..]);
+        ZipVec(zs)
+    }
+    fn op_owned(ZipVec(xs): Self, ZipVec(ys): Self) -> Self {
+        let (n, m) = (xs.len(), ys.len());
+        let (mut xs, mut ys) = (xs.into_iter(), ys.into_iter());
+        let mut zs = Vec::with_capacity(n.max(m));
+        zs.extend(xs.by_ref().zip(ys.by_ref()).map(|(a, b)| M::op_owned(a, b)));
+        zs.extend(xs);
+        zs.extend(ys);
+        ZipVec(zs)
+    }
+}
+
+/// The empty vector, padded to whatever length is needed.
+impl<M: Monoid + Clone> Monoid for ZipVec<M> {
+    fn zero() -> Self {
+        ZipVec(Vec::new())
+    }
+}
+
+/// Combine matching positions in place, cloning only the right side's excess tail.
+impl<M: Monoid + SemigroupAssign + Clone> SemigroupAssign for ZipVec<M> {
+    fn op_assign(&mut self, ZipVec(ys): &Self) {
+        let n = self.0.len().min(ys.len());
+        for (a, b) in self.0.iter_mut().zip(ys) {
+            a.op_assign(b);
+        }
+        self.0.extend_from_slice(&ys[n..]);
+    }
+}
+
+/// Commutative if each position is.
+impl<M: CommutativeMonoid + Clone> CommutativeMonoid for ZipVec<M> {}
+
+/// Idempotent if each position is.
+impl<M: Monoid + Band + Clone> Band for ZipVec<M> {}
+
+/// A semilattice if each position is.
+impl<M: Semilattice + Clone> Semilattice for ZipVec<M> {}
+
+/// Functions from a type to itself form a monoid under composition.
+///
+/// The function is reference counted (rather than boxed) so that [`Semigroup::op`] can share its borrowed
+/// operands; see [`ArcEndo`] for a thread-safe version.
+pub struct Endo<T>(pub Rc<dyn Fn(T) -> T>);
+
+impl<T> Endo<T> {
+    /// Wrap a function.
+    pub fn new(f: impl Fn(T) -> T + 'static) -> Self {
+        Endo(Rc::new(f))
+    }
+    /// Apply the wrapped function.
+    pub fn apply(&self, x: T) -> T {
+        (self.0)(x)
+    }
+}
+
+impl<T> Clone for Endo<T> {
+    fn clone(&self) -> Self {
+        Endo(Rc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for Endo<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Endo(<function>)")
+    }
+}
+
+/// Composition, so that `op(f, g)` applies `g` first and then `f`.
+impl<T: 'static> Semigroup for Endo<T> {
+    fn op(Endo(f): &Self, Endo(g): &Self) -> Self {
+        let (f, g) = (Rc::clone(f), Rc::clone(g));
+        Endo(Rc::new(move |x| f(g(x))))
+    }
+}
+
+/// The identity function.
+impl<T: 'static> Monoid for Endo<T> {
+    fn zero() -> Self {
+        Endo::new(|x| x)
+    }
+}
+
+/// Compose in place.
+impl<T: 'static> SemigroupAssign for Endo<T> {}
+
+/// Functions act on their domain by application.
+impl<T: Clone + 'static> MonoidAction<T> for Endo<T> {
+    fn act(&self, x: &T) -> T {
+        self.apply(x.clone())
+    }
+}
+
+/// Thread-safe version of [`Endo`], e.g. for building pipelines in parallel with `rayon`.
+pub struct ArcEndo<T>(pub Arc<dyn Fn(T) -> T + Send + Sync>);
+
+impl<T> ArcEndo<T> {
+    /// Wrap a function.
+    pub fn new(f: impl Fn(T) -> T + Send + Sync + 'static) -> Self {
+        ArcEndo(Arc::new(f))
+    }
+    /// Apply the wrapped function.
+    pub fn apply(&self, x: T) -> T {
+        (self.0)(x)
+    }
+}
+
+impl<T> Clone for ArcEndo<T> {
+    fn clone(&self) -> Self {
+        ArcEndo(Arc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for ArcEndo<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ArcEndo(<function>)")
+    }
+}
+
+/// Composition, so that `op(f, g)` applies `g` first and then `f`.
+impl<T: 'static> Semigroup for ArcEndo<T> {
+    fn op(ArcEndo(f): &Self, ArcEndo(g): &Self) -> Self {
+        let (f, g) = (Arc::clone(f), Arc::clone(g));
+        ArcEndo(Arc::new(move |x| f(g(x))))
+    }
+}
+
+/// The identity function.
+impl<T: 'static> Monoid for ArcEndo<T> {
+    fn zero() -> Self {
+        ArcEndo::new(|x| x)
+    }
+}
+
+/// Compose in place.
+impl<T: 'static> SemigroupAssign for ArcEndo<T> {}
+
+/// Functions act on their domain by application.
+impl<T: Clone + 'static> MonoidAction<T> for ArcEndo<T> {
+    fn act(&self, x: &T) -> T {
+        self.apply(x.clone())
+    }
+}
+
+/// Difference-list ("Cayley") representation of a monoid, for when [`Semigroup::op`] is expensive
+///
+/// Each element is a pending in-place append; combining two of them just records that one runs before the
+/// other, in O(1).  Nothing is materialized until [`Cayley::run`], which performs all the appends in order
+/// into a single accumulator, so repeatedly appending `String`s or `Vec`s costs linear rather than
+/// quadratic time.
+pub struct Cayley<M>(Option<Rc<Pending<M>>>);
+
+struct Pending<M>(Option<Step<M>>);
+
+enum Step<M> {
+    Append(Box<dyn Fn(&mut M)>),
+    Then(Rc<Pending<M>>, Rc<Pending<M>>),
+}
+
+/// Unlink iteratively so that dropping a long chain of appends doesn't overflow the stack.
+impl<M> Drop for Pending<M> {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        let mut next = self.0.take();
+        loop {
+            if let Some(Step::Then(a, b)) = next {
+                stack.push(a);
+                stack.push(b);
+            }
+            match stack.pop() {
+                Some(p) => next = Rc::try_unwrap(p).ok().and_then(|mut p| p.0.take()),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<M: SemigroupAssign + 'static> Cayley<M> {
+    /// A single pending append of `m`.
+    pub fn new(m: M) -> Self {
+        Cayley(Some(Rc::new(Pending(Some(Step::Append(Box::new(
+            move |acc: &mut M| acc.op_assign(&m),
+        )))))))
+    }
+}
+
+impl<M: Monoid> Cayley<M> {
+    /// Perform all the pending appends, from left to right.
+    pub fn run(&self) -> M {
+        let mut m = M::zero();
+        let mut stack = self.0.iter().map(Rc::as_ref).collect::<Vec<_>>();
+        while let Some(Pending(p)) = stack.pop() {
+            match p {
+                Some(Step::Append(f)) => f(&mut m),
+                Some(Step::Then(a, b)) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                None => {}
+            }
+        }
+        m
+    }
+}
+
+impl<M> Clone for Cayley<M> {
+    fn clone(&self) -> Self {
+        Cayley(self.0.clone())
+    }
+}
+
+impl<M> fmt::Debug for Cayley<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cayley(<pending>)")
+    }
+}
+
+/// Record that `x`'s appends happen before `y`'s.
+impl<M> Semigroup for Cayley<M> {
+    fn op(Cayley(x): &Self, Cayley(y): &Self) -> Self {
+        match (x, y) {
+            (Some(a), Some(b)) => Cayley(Some(Rc::new(Pending(Some(Step::Then(
+                Rc::clone(a),
+                Rc::clone(b),
+            )))))),
+            (None, _) => Cayley(y.clone()),
+            (_, None) => Cayley(x.clone()),
+        }
+    }
+}
+
+/// Nothing pending.
+impl<M> Monoid for Cayley<M> {
+    fn zero() -> Self {
+        Cayley(None)
+    }
+}
@@ -0,0 +1,121 @@
+//! Compensated floating-point summation, following [Neumaier's improvement](https://en.wikipedia.org/wiki/Kahan_summation_algorithm#Further_enhancements)
+//! to Kahan's algorithm.
+//!
+//! Plain `f64` addition isn't associative, so a parallel reduction can give a different (and worse) answer
+//! than a sequential one.  Carrying the rounding error alongside the running sum, and merging errors as well
+//! as sums, recovers nearly all of the lost precision regardless of how the work is split up.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Group, Monoid, Semigroup, SemigroupAssign};
+
+/// Running sum of floats with a compensation term for lost low-order bits
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompensatedSum {
+    /// Naïve running sum
+    sum: f64,
+    /// Accumulated rounding error
+    compensation: f64,
+}
+
+impl PartialEq for CompensatedSum {
+    fn eq(&self, other: &CompensatedSum) -> bool {
+        close(self.value(), other.value())
+    }
+}
+
+impl CompensatedSum {
+    /// Construct from a single value.
+    pub fn new(x: f64) -> CompensatedSum {
+        CompensatedSum {
+            sum: x,
+            compensation: 0.0,
+        }
+    }
+    /// The compensated total.
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+    /// Neumaier's step: add `x` to the sum, recording what rounding throws away.
+    fn add_value(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+}
+
+/// We can add a new value to a sum.
+impl Add<f64> for CompensatedSum {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self.add_value(x);
+        self
+    }
+}
+
+/// We can add a new value to a sum.
+impl AddAssign<f64> for CompensatedSum {
+    fn add_assign(&mut self, x: f64) {
+        self.add_value(x);
+    }
+}
+
+/// Accumulate the values one at a time into a new sum.
+impl FromIterator<f64> for CompensatedSum {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut s = CompensatedSum::default();
+        for x in iter {
+            s += x
+        }
+        s
+    }
+}
+
+/// Accumulate the values one at a time into a new sum.
+impl<'a> FromIterator<&'a f64> for CompensatedSum {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the sums (compensating as we go) and the compensations.
+impl Semigroup for CompensatedSum {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty sum.
+impl Monoid for CompensatedSum {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merge another sum into this one.
+impl SemigroupAssign for CompensatedSum {
+    fn op_assign(&mut self, other: &Self) {
+        self.add_value(other.sum);
+        self.compensation += other.compensation;
+    }
+}
+
+/// Addition is commutative.
+impl CommutativeMonoid for CompensatedSum {}
+
+/// Negating both parts cancels a sum exactly.
+impl Group for CompensatedSum {
+    fn invert(&self) -> Self {
+        CompensatedSum {
+            sum: -self.sum,
+            compensation: -self.compensation,
+        }
+    }
+}
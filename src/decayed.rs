@@ -0,0 +1,374 @@
+//! Exponentially time-decayed mean and variance
+//!
+//! Each point's weight halves every `half_life` units of time, so a [`DecayedGaussian`] tracks recent
+//! behavior instead of averaging over all history.  Summaries are kept relative to their latest timestamp;
+//! merging first decays the older one forward to match, so shards with different clocks combine correctly
+//! and in any order.
+//!
+//! [`Ewma`] and [`Ewvar`] are the same idea parameterized the way monitoring systems usually are, by the
+//! fraction of weight kept per unit of time; on evenly spaced data this is the (bias-adjusted) exponentially
+//! weighted moving average with smoothing factor `α = 1 − decay`.
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Time-weighted mean and variance, forgetting old data at a fixed half-life
+#[derive(Clone, Copy, Debug)]
+pub struct DecayedGaussian {
+    /// Time for a point's weight to halve
+    half_life: f64,
+    /// Latest timestamp seen, to which the weights are relative
+    time: f64,
+    /// Total weight as of `time`
+    weight: f64,
+    /// Weighted mean
+    m1: f64,
+    /// Weighted sum of squared deviations from the mean
+    m2: f64,
+}
+
+/// Any two empty summaries are equal; otherwise, half-lives and times must match exactly.
+impl PartialEq for DecayedGaussian {
+    fn eq(&self, other: &DecayedGaussian) -> bool {
+        (self.is_empty() && other.is_empty())
+            || (self.half_life == other.half_life
+                && self.time == other.time
+                && close(self.weight, other.weight)
+                && close(self.m1, other.m1)
+                && close(self.m2, other.m2))
+    }
+}
+
+impl DecayedGaussian {
+    /// An empty summary with the given (positive) half-life.
+    pub fn new(half_life: f64) -> DecayedGaussian {
+        assert!(half_life > 0.0, "Half-life must be positive.");
+        DecayedGaussian {
+            half_life,
+            time: f64::NEG_INFINITY,
+            weight: 0.0,
+            m1: 0.0,
+            m2: 0.0,
+        }
+    }
+    /// Construct from a single data point `x` observed at time `t`.
+    pub fn of(x: f64, t: f64, half_life: f64) -> DecayedGaussian {
+        DecayedGaussian {
+            time: t,
+            weight: 1.0,
+            m1: x,
+            ..Self::new(half_life)
+        }
+    }
+    /// Add the data point `x`, observed at time `t`; points may arrive out of order.
+    ///
+    /// Panics on the identity [`zero`](Monoid::zero), which has no half-life to weight the point with; start
+    /// from [`new`](DecayedGaussian::new) instead.
+    pub fn observe(&mut self, x: f64, t: f64) {
+        assert!(
+            !self.half_life.is_nan(),
+            "Can't observe without a half-life; start from DecayedGaussian::new."
+        );
+        self.op_assign(&Self::of(x, t, self.half_life));
+    }
+    /// Whether no data has been observed.
+    pub fn is_empty(&self) -> bool {
+        self.weight == 0.0
+    }
+    /// The half-life.
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+    /// The latest timestamp observed.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+    /// The total weight of the data as of [`time`](DecayedGaussian::time), an "effective count."
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+    /// The total weight of the data as of time `t`, which should be no earlier than
+    /// [`time`](DecayedGaussian::time).
+    pub fn weight_at(&self, t: f64) -> f64 {
+        self.weight * self.decay(t)
+    }
+    /// The weighted mean; decay doesn't change it until new data arrives.
+    pub fn mean(&self) -> f64 {
+        self.m1
+    }
+    /// The weighted (population) variance.
+    pub fn variance(&self) -> f64 {
+        assert!(!self.is_empty(), "Variance requires at least 1 sample.");
+        self.m2 / self.weight
+    }
+    /// The factor by which weights shrink between [`time`](DecayedGaussian::time) and `t`.
+    fn decay(&self, t: f64) -> f64 {
+        (-(t - self.time) / self.half_life).exp2()
+    }
+}
+
+/// Decay both summaries to the later time, then merge their weighted moments.
+impl Semigroup for DecayedGaussian {
+    fn op(x: &Self, y: &Self) -> Self {
+        if x.is_empty() {
+            // Keep whichever half-life is known, so folds starting from `zero` can still observe.
+            let mut z = *y;
+            if z.half_life.is_nan() {
+                z.half_life = x.half_life;
+            }
+            return z;
+        } else if y.is_empty() {
+            return *x;
+        }
+        assert_eq!(
+            x.half_life, y.half_life,
+            "Can't merge summaries with different half-lives."
+        );
+        let time = x.time.max(y.time);
+        let (wx, wy) = (x.weight * x.decay(time), y.weight * y.decay(time));
+        let weight = wx + wy;
+        let d = y.m1 - x.m1;
+        DecayedGaussian {
+            half_life: x.half_life,
+            time,
+            weight,
+            m1: x.m1 + d * (wy / weight),
+            m2: x.m2 * x.decay(time) + y.m2 * y.decay(time) + d * d * (wx * wy / weight),
+        }
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for DecayedGaussian {}
+
+/// The empty summary, which merges with one of any half-life and takes on that half-life.
+impl Monoid for DecayedGaussian {
+    fn zero() -> Self {
+        DecayedGaussian {
+            half_life: f64::NAN,
+            time: f64::NEG_INFINITY,
+            weight: 0.0,
+            m1: 0.0,
+            m2: 0.0,
+        }
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for DecayedGaussian {}
+
+/// Check that a per-unit-time decay factor is in range.
+fn check_decay(decay: f64) {
+    assert!(
+        decay > 0.0 && decay < 1.0,
+        "Decay must be strictly between 0 and 1."
+    );
+}
+
+/// Exponentially weighted moving average, keeping a fraction `decay` of each point's weight per unit of time
+#[derive(Clone, Copy, Debug)]
+pub struct Ewma {
+    /// Fraction of weight kept per unit of time
+    decay: f64,
+    /// Latest timestamp seen, to which the weights are relative
+    time: f64,
+    /// Total weight as of `time`
+    weight: f64,
+    /// Weighted mean
+    mean: f64,
+}
+
+/// Any two empty averages are equal; otherwise, decays and times must match exactly.
+impl PartialEq for Ewma {
+    fn eq(&self, other: &Ewma) -> bool {
+        (self.is_empty() && other.is_empty())
+            || (self.decay == other.decay
+                && self.time == other.time
+                && close(self.weight, other.weight)
+                && close(self.mean, other.mean))
+    }
+}
+
+impl Ewma {
+    /// An empty average with the given decay, strictly between 0 and 1.
+    pub fn new(decay: f64) -> Ewma {
+        check_decay(decay);
+        Ewma {
+            decay,
+            time: f64::NEG_INFINITY,
+            weight: 0.0,
+            mean: 0.0,
+        }
+    }
+    /// Construct from a single data point `x` observed at time `t`.
+    pub fn of(x: f64, t: f64, decay: f64) -> Ewma {
+        Ewma {
+            time: t,
+            weight: 1.0,
+            mean: x,
+            ..Self::new(decay)
+        }
+    }
+    /// Add the data point `x`, observed at time `t`; points may arrive out of order.
+    ///
+    /// Panics on the identity [`zero`](Monoid::zero), which has no decay to weight the point with; start from
+    /// [`new`](Ewma::new) instead.
+    pub fn observe(&mut self, x: f64, t: f64) {
+        assert!(
+            !self.decay.is_nan(),
+            "Can't observe without a decay; start from Ewma::new."
+        );
+        self.op_assign(&Self::of(x, t, self.decay));
+    }
+    /// Whether no data has been observed.
+    pub fn is_empty(&self) -> bool {
+        self.weight == 0.0
+    }
+    /// The fraction of weight kept per unit of time.
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+    /// The latest timestamp observed.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+    /// The total weight of the data as of [`time`](Ewma::time), an "effective count."
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+    /// The weighted mean, or `None` if no data has been observed.
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.mean)
+    }
+}
+
+/// Decay both averages to the later time, then take their weighted mean.
+impl Semigroup for Ewma {
+    fn op(x: &Self, y: &Self) -> Self {
+        if x.is_empty() {
+            // Keep whichever decay is known, so folds starting from `zero` can still observe.
+            let mut z = *y;
+            if z.decay.is_nan() {
+                z.decay = x.decay;
+            }
+            return z;
+        } else if y.is_empty() {
+            return *x;
+        }
+        assert_eq!(
+            x.decay, y.decay,
+            "Can't merge averages with different decays."
+        );
+        let time = x.time.max(y.time);
+        let wx = x.weight * x.decay.powf(time - x.time);
+        let wy = y.weight * y.decay.powf(time - y.time);
+        let weight = wx + wy;
+        Ewma {
+            decay: x.decay,
+            time,
+            weight,
+            mean: x.mean + (y.mean - x.mean) * (wy / weight),
+        }
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for Ewma {}
+
+/// The empty average, which merges with one of any decay and takes on that decay.
+impl Monoid for Ewma {
+    fn zero() -> Self {
+        Ewma {
+            decay: f64::NAN,
+            time: f64::NEG_INFINITY,
+            weight: 0.0,
+            mean: 0.0,
+        }
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for Ewma {}
+
+/// Exponentially weighted mean and variance, keeping a fraction `decay` of each point's weight per unit of
+/// time
+///
+/// This is a [`DecayedGaussian`] with half-life `−1 / log₂(decay)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ewvar(DecayedGaussian);
+
+impl Ewvar {
+    /// An empty summary with the given decay, strictly between 0 and 1.
+    pub fn new(decay: f64) -> Ewvar {
+        check_decay(decay);
+        Ewvar(DecayedGaussian::new(-1.0 / decay.log2()))
+    }
+    /// Construct from a single data point `x` observed at time `t`.
+    pub fn of(x: f64, t: f64, decay: f64) -> Ewvar {
+        let mut e = Self::new(decay);
+        e.observe(x, t);
+        e
+    }
+    /// Add the data point `x`, observed at time `t`; points may arrive out of order.
+    ///
+    /// Panics on the identity [`zero`](Monoid::zero), which has no decay to weight the point with; start from
+    /// [`new`](Ewvar::new) instead.
+    pub fn observe(&mut self, x: f64, t: f64) {
+        assert!(
+            !self.0.half_life().is_nan(),
+            "Can't observe without a decay; start from Ewvar::new."
+        );
+        self.0.observe(x, t);
+    }
+    /// The underlying [`DecayedGaussian`].
+    pub fn decayed(&self) -> &DecayedGaussian {
+        &self.0
+    }
+    /// Whether no data has been observed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The fraction of weight kept per unit of time.
+    pub fn decay(&self) -> f64 {
+        (-1.0 / self.0.half_life()).exp2()
+    }
+    /// The latest timestamp observed.
+    pub fn time(&self) -> f64 {
+        self.0.time()
+    }
+    /// The total weight of the data as of [`time`](Ewvar::time), an "effective count."
+    pub fn weight(&self) -> f64 {
+        self.0.weight()
+    }
+    /// The weighted mean, or `None` if no data has been observed.
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.0.mean())
+    }
+    /// The weighted (population) variance.
+    pub fn variance(&self) -> f64 {
+        self.0.variance()
+    }
+    /// The square root of the [`variance`](Ewvar::variance).
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Merge the underlying decayed summaries.
+impl Semigroup for Ewvar {
+    fn op(x: &Self, y: &Self) -> Self {
+        Ewvar(Semigroup::op(&x.0, &y.0))
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for Ewvar {}
+
+/// The empty summary, which merges with one of any decay and takes on that decay.
+impl Monoid for Ewvar {
+    fn zero() -> Self {
+        Ewvar(DecayedGaussian::zero())
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for Ewvar {}
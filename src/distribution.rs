@@ -0,0 +1,44 @@
+//! A common interface to the fitted distributions
+//!
+//! [`Gaussian`](crate::gaussian::Gaussian), [`Exponential`](crate::exponential::Exponential),
+//! [`Poisson`](crate::poisson::Poisson), [`GammaSummary`](crate::gamma::GammaSummary) and
+//! [`Proportion`](crate::proportion::Proportion) each summarize data as a monoid and fit a distribution to
+//! it; [`Distribution`] lets downstream code evaluate the fit without caring which family was chosen.  With
+//! the `rand` feature, `Sample` additionally draws from it.
+use num_traits::Float;
+
+/// A probability distribution fitted to the data summarized so far
+pub trait Distribution {
+    /// The type of the outcomes, e.g. `f64` for continuous distributions or `u64` for counts
+    type Value;
+    /// The type of moments and probabilities
+    type Real: Float;
+    /// Mean of the fitted distribution
+    fn mean(&self) -> Self::Real;
+    /// Variance of the fitted distribution
+    fn variance(&self) -> Self::Real;
+    /// Probability density (or, for discrete distributions, mass) at `x`
+    fn pdf(&self, x: Self::Value) -> Self::Real;
+    /// Natural logarithm of the [`pdf`](Distribution::pdf)
+    ///
+    /// The default takes the logarithm of the density, which underflows in the tails; implementations should
+    /// override it where they can do better.
+    fn log_pdf(&self, x: Self::Value) -> Self::Real {
+        self.pdf(x).ln()
+    }
+    /// Probability of an outcome no greater than `x`
+    fn cdf(&self, x: Self::Value) -> Self::Real;
+}
+
+/// A [`Distribution`] that [`rand`] can also draw outcomes from
+///
+/// This is implemented for everything that is both, so enabling the feature never asks more of downstream
+/// implementations of [`Distribution`].
+#[cfg(feature = "rand")]
+pub trait Sample:
+    Distribution + rand::distributions::Distribution<<Self as Distribution>::Value>
+{
+}
+
+#[cfg(feature = "rand")]
+impl<D: Distribution + rand::distributions::Distribution<D::Value>> Sample for D {}
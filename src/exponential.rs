@@ -0,0 +1,228 @@
+//! The exponential distribution, fitted by maximum likelihood
+//!
+//! Its sufficient statistics are just the count and the sum, so summaries of separate shards (inter-arrival
+//! times seen by different servers, say) merge by adding both.
+
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use num_traits::Float;
+
+use crate::distribution::Distribution;
+use crate::special::close;
+#[cfg(feature = "rand")]
+use crate::special::lit;
+use crate::traits::{CommutativeMonoid, Group, Monoid, Semigroup, SemigroupAssign};
+
+/// Parameterized exponential distribution
+///
+/// Generic over the float type `T`, which defaults to `f64`.
+#[derive(Clone, Copy, Debug)]
+pub struct Exponential<T: Float = f64> {
+    /// Count of datapoints (stored as a float for convenience)
+    n: T,
+    /// Sum of the datapoints
+    sum: T,
+}
+
+/// Equal counts, and sums within `numpy.isclose`'s default tolerances.
+impl<T: Float> PartialEq for Exponential<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.n == other.n) && close(self.sum, other.sum)
+    }
+}
+impl<T: Float> Eq for Exponential<T> {}
+
+impl<T: Float> Default for Exponential<T> {
+    fn default() -> Self {
+        Exponential {
+            n: T::zero(),
+            sum: T::zero(),
+        }
+    }
+}
+
+impl<T: Float> Exponential<T> {
+    /// Construct from a single data point.
+    pub fn new(x: T) -> Self {
+        Exponential {
+            n: T::one(),
+            sum: x,
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> T {
+        self.n
+    }
+    /// The sum of the data.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+    /// The mean of this distribution, `1 / rate`.
+    pub fn mean(&self) -> T {
+        assert!(self.n > T::zero(), "Mean requires at least 1 sample.");
+        self.sum / self.n
+    }
+    /// The maximum-likelihood rate, `count / sum`.
+    pub fn rate(&self) -> T {
+        assert!(self.n > T::zero(), "Rate requires at least 1 sample.");
+        self.n / self.sum
+    }
+    /// The variance of this distribution, `1 / rate²`.
+    pub fn variance(&self) -> T {
+        self.mean().powi(2)
+    }
+    /// Probability Density Function.
+    pub fn pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
+        } else {
+            let l = self.rate();
+            l * (-l * x).exp()
+        }
+    }
+    /// Natural logarithm of the [`pdf`](Exponential::pdf), which doesn't underflow in the tail.
+    pub fn log_pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::neg_infinity()
+        } else {
+            let l = self.rate();
+            l.ln() - l * x
+        }
+    }
+    /// Cumulative Distribution Function.
+    pub fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
+        } else {
+            -(-self.rate() * x).exp_m1()
+        }
+    }
+    /// Survival function, `1 - cdf(x)`, without the cancellation in the tail.
+    pub fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
+        } else {
+            (-self.rate() * x).exp()
+        }
+    }
+    /// Quantile function (inverse CDF): the `x` with `cdf(x) == p`.
+    ///
+    /// Returns +∞ for `p` of 1, and NaN outside `[0, 1]`.
+    pub fn quantile(&self, p: T) -> T {
+        -(-p).ln_1p() / self.rate()
+    }
+}
+
+/// We can add a new data point to an exponential distribution.
+impl<T: Float> Add<T> for Exponential<T> {
+    type Output = Self;
+    fn add(mut self, x: T) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point to an exponential distribution.
+impl<T: Float> AddAssign<T> for Exponential<T> {
+    fn add_assign(&mut self, x: T) {
+        self.n = self.n + T::one();
+        self.sum = self.sum + x;
+    }
+}
+
+/// Accumulate the points one at a time into a new exponential distribution.
+impl<T: Float> FromIterator<T> for Exponential<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut e = Self::default();
+        e.extend(iter);
+        e
+    }
+}
+
+/// Accumulate the points one at a time into a new exponential distribution.
+impl<'a, T: Float> FromIterator<&'a T> for Exponential<T> {
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the points one at a time.
+impl<T: Float> Extend<T> for Exponential<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            *self += x
+        }
+    }
+}
+
+/// Add the points one at a time.
+impl<'a, T: Float> Extend<&'a T> for Exponential<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+/// Draw samples from the fitted distribution, by inverting the CDF.
+#[cfg(feature = "rand")]
+impl<T: Float> rand::distributions::Distribution<T> for Exponential<T> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> T {
+        // `gen` is in `[0, 1)`, so flip it to keep the logarithm finite.
+        let u = 1.0 - rng.gen::<f64>();
+        -lit::<T>(u.ln()) / self.rate()
+    }
+}
+
+/// The maximum-likelihood fit.
+impl<T: Float> Distribution for Exponential<T> {
+    type Value = T;
+    type Real = T;
+    fn mean(&self) -> T {
+        self.mean()
+    }
+    fn variance(&self) -> T {
+        self.variance()
+    }
+    fn pdf(&self, x: T) -> T {
+        self.pdf(x)
+    }
+    fn log_pdf(&self, x: T) -> T {
+        self.log_pdf(x)
+    }
+    fn cdf(&self, x: T) -> T {
+        self.cdf(x)
+    }
+}
+
+/// Add the counts and the sums.
+impl<T: Float> Semigroup for Exponential<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        Exponential {
+            n: x.n + y.n,
+            sum: x.sum + y.sum,
+        }
+    }
+}
+
+/// Merge in place.
+impl<T: Float> SemigroupAssign for Exponential<T> {}
+
+/// The "empty distribution."
+impl<T: Float> Monoid for Exponential<T> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Addition is commutative.
+impl<T: Float> CommutativeMonoid for Exponential<T> {}
+
+/// Negating the count and the sum gives a "distribution" that merges with this one to nothing.
+impl<T: Float> Group for Exponential<T> {
+    fn invert(&self) -> Self {
+        Exponential {
+            n: -self.n,
+            sum: -self.sum,
+        }
+    }
+}
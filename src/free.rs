@@ -0,0 +1,313 @@
+//! Free structures: the most general monoids generated by a set of values
+//!
+//! Every other monoid is a quotient of these, which shows up as `fold_hom`: a map on generators extends
+//! uniquely to a homomorphism out of the free structure.
+use std::{collections::HashMap, hash::Hash, iter::FromIterator, num::NonZeroU64};
+
+use crate::traits::{
+    power_semigroup, CancellativeMonoid, CommutativeMonoid, Monoid, Monus, Semigroup,
+    SemigroupAssign,
+};
+
+/// The free monoid on `T`: finite sequences under concatenation
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FreeMonoid<T>(pub Vec<T>);
+
+impl<T> FreeMonoid<T> {
+    /// The sequence containing only `t`.
+    pub fn of(t: T) -> Self {
+        FreeMonoid(vec![t])
+    }
+    /// The unique homomorphism into `M` that sends each generator `t` to `f(t)`.
+    pub fn fold_hom<M: Monoid>(&self, f: impl Fn(&T) -> M) -> M {
+        self.0.iter().fold(M::zero(), |m, t| M::op_owned(m, f(t)))
+    }
+}
+
+impl<T> FromIterator<T> for FreeMonoid<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        FreeMonoid(iter.into_iter().collect())
+    }
+}
+
+/// Concatenation.
+impl<T: Clone> Semigroup for FreeMonoid<T> {
+    fn op(FreeMonoid(xs): &Self, FreeMonoid(ys): &Self) -> Self {
+        FreeMonoid(xs.iter().chain(ys.iter()).cloned().collect())
+    }
+    fn op_owned(FreeMonoid(mut xs): Self, FreeMonoid(mut ys): Self) -> Self {
+        xs.append(&mut ys);
+        FreeMonoid(xs)
+    }
+}
+
+/// The empty sequence.
+impl<T: Clone> Monoid for FreeMonoid<T> {
+    fn zero() -> Self {
+        FreeMonoid(Vec::new())
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl<T: Clone> SemigroupAssign for FreeMonoid<T> {
+    fn op_assign(&mut self, FreeMonoid(ys): &Self) {
+        self.0.extend_from_slice(ys);
+    }
+}
+
+/// Equal prefixes can be stripped from either side.
+impl<T: Clone> CancellativeMonoid for FreeMonoid<T> {}
+
+/// The free semigroup on `T`: nonempty sequences under concatenation
+///
+/// Having at least one element means any semigroup can be folded over it totally, without needing a
+/// [`Monoid::zero`] to start from; see [`NonEmpty::reduce`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NonEmpty<T> {
+    head: T,
+    tail: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    /// The sequence containing only `t`.
+    pub fn of(t: T) -> Self {
+        NonEmpty {
+            head: t,
+            tail: Vec::new(),
+        }
+    }
+    /// The sequence `head` followed by `tail`.
+    pub fn new(head: T, tail: Vec<T>) -> Self {
+        NonEmpty { head, tail }
+    }
+    /// The elements of `v`, or `None` if it's empty.
+    pub fn from_vec(mut v: Vec<T>) -> Option<Self> {
+        if v.is_empty() {
+            None
+        } else {
+            let head = v.remove(0);
+            Some(NonEmpty { head, tail: v })
+        }
+    }
+    /// Add `t` to the end.
+    pub fn push(&mut self, t: T) {
+        self.tail.push(t);
+    }
+    /// The first element.
+    pub fn first(&self) -> &T {
+        &self.head
+    }
+    /// The last element.
+    pub fn last(&self) -> &T {
+        self.tail.last().unwrap_or(&self.head)
+    }
+    /// Number of elements, which is always at least one.
+    pub fn len(&self) -> usize {
+        1 + self.tail.len()
+    }
+    /// Always `false`; provided for symmetry with [`NonEmpty::len`].
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// The elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::once(&self.head).chain(self.tail.iter())
+    }
+    /// Forget that there's at least one element.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        v.push(self.head);
+        v.extend(self.tail);
+        v
+    }
+    /// The unique semigroup homomorphism into `S` that sends each generator `t` to `f(t)`.
+    pub fn fold_hom<S: Semigroup>(&self, f: impl Fn(&T) -> S) -> S {
+        self.tail
+            .iter()
+            .fold(f(&self.head), |s, t| S::op_owned(s, f(t)))
+    }
+}
+
+impl<S: Semigroup> NonEmpty<S> {
+    /// Combine all the elements in order.
+    pub fn reduce(self) -> S {
+        self.tail.into_iter().fold(self.head, S::op_owned)
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(xs: NonEmpty<T>) -> Self {
+        xs.into_vec()
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+/// Concatenation.
+impl<T: Clone> Semigroup for NonEmpty<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.tail.reserve(y.len());
+        x.tail.push(y.head);
+        x.tail.extend(y.tail);
+        x
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl<T: Clone> SemigroupAssign for NonEmpty<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.tail.reserve(other.len());
+        self.tail.extend(other.iter().cloned());
+    }
+}
+
+/// The free commutative monoid on `T`: finite multisets (a.k.a. bags or counters) under sum of counts
+///
+/// Zero counts are never stored, so equality is structural.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Multiset<T: Eq + Hash>(HashMap<T, NonZeroU64>);
+
+/// Counting occurrences is the canonical use of [`Multiset`].
+pub type Counter<T> = Multiset<T>;
+
+impl<T: Eq + Hash> Multiset<T> {
+    /// The multiset containing `t` once.
+    pub fn of(t: T) -> Self {
+        let mut m = Multiset(HashMap::new());
+        m.insert(t);
+        m
+    }
+    /// Add one occurrence of `t`.
+    pub fn insert(&mut self, t: T) {
+        self.insert_many(t, 1);
+    }
+    /// Add `n` occurrences of `t`.
+    pub fn insert_many(&mut self, t: T, n: u64) {
+        if let Some(n) = NonZeroU64::new(n) {
+            self.0
+                .entry(t)
+                .and_modify(|c| *c = c.saturating_add(n.get()))
+                .or_insert(n);
+        }
+    }
+    /// How many times `t` occurs.
+    pub fn count(&self, t: &T) -> u64 {
+        self.0.get(t).map_or(0, |c| c.get())
+    }
+    /// Total number of occurrences.
+    pub fn len(&self) -> u64 {
+        self.0.values().map(|c| c.get()).sum()
+    }
+    /// Whether there are no occurrences at all.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Number of distinct elements.
+    pub fn distinct(&self) -> usize {
+        self.0.len()
+    }
+    /// Distinct elements with their (nonzero) counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.0.iter().map(|(t, c)| (t, c.get()))
+    }
+    /// The unique homomorphism into `M` that sends each generator `t` to `f(t)`.
+    ///
+    /// Repeated elements are handled with [`power_semigroup`], so this is logarithmic in each count.
+    pub fn fold_hom<M: CommutativeMonoid + Clone>(&self, f: impl Fn(&T) -> M) -> M {
+        self.0.iter().fold(M::zero(), |m, (t, &c)| {
+            M::op_owned(m, power_semigroup(f(t), c))
+        })
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Multiset<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut m = Multiset(HashMap::new());
+        for t in iter {
+            m.insert(t);
+        }
+        m
+    }
+}
+
+/// Counts add.
+impl<T: Clone + Eq + Hash> Semigroup for Multiset<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        let (mut big, small) = if x.0.len() >= y.0.len() {
+            (x, y)
+        } else {
+            (y, x)
+        };
+        for (t, c) in small.0 {
+            big.insert_many(t, c.get());
+        }
+        big
+    }
+}
+
+/// The empty multiset.
+impl<T: Clone + Eq + Hash> Monoid for Multiset<T> {
+    fn zero() -> Self {
+        Multiset(HashMap::new())
+    }
+}
+
+/// Counts add in place.
+impl<T: Clone + Eq + Hash> SemigroupAssign for Multiset<T> {
+    fn op_assign(&mut self, other: &Self) {
+        for (t, c) in other.iter() {
+            self.insert_many(t.clone(), c);
+        }
+    }
+}
+
+/// Addition of counts is commutative.
+impl<T: Clone + Eq + Hash> CommutativeMonoid for Multiset<T> {}
+
+/// Addition of counts is cancellative (short of saturation).
+impl<T: Clone + Eq + Hash> CancellativeMonoid for Multiset<T> {}
+
+/// Counts subtract, bottoming out at zero.
+impl<T: Clone + Eq + Hash> Monus for Multiset<T> {
+    fn monus(x: &Self, y: &Self) -> Self {
+        Multiset(
+            x.0.iter()
+                .filter_map(|(t, c)| {
+                    NonZeroU64::new(c.get().saturating_sub(y.count(t))).map(|c| (t.clone(), c))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T: Clone + Eq + Hash> Multiset<T> {
+    /// `n` copies of this multiset, i.e. [`power_monoid`](crate::traits::power_monoid) without the
+    /// repeated squaring.
+    pub fn scale(&self, n: u64) -> Self {
+        match NonZeroU64::new(n) {
+            None => Monoid::zero(),
+            Some(n) => Multiset(
+                self.0
+                    .iter()
+                    .map(|(t, c)| (t.clone(), c.saturating_mul(n)))
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Mergeable sketches for estimating how often items occur
+//!
+//! [`CountSketch`] follows Charikar, Chen & Farach-Colton, [Finding Frequent Items in Data
+//! Streams](https://www.cs.princeton.edu/courses/archive/spring04/cos598B/bib/CharikarCF.pdf).  Each of `D`
+//! rows hashes an item to one of `W` counters and adds its count there with a hashed sign, so collisions
+//! cancel out on average and each row's reading is unbiased; the median across rows tames the variance.
+//! Counters add cell by cell, so sketches merge by addition and counts can be taken back out again.
+//!
+//! [`MisraGries`] instead keeps exact counters for at most `K` items, following Misra & Gries, [Finding
+//! Repeated Elements](https://doi.org/10.1016/0167-6423(82)90012-0), with the merge of Agarwal et al.,
+//! [Mergeable Summaries](https://www.cs.utah.edu/~jeffp/papers/mergeable-summaries.pdf): add the counters,
+//! then subtract the `(K + 1)`th largest from all of them and drop those left empty.  Every counter
+//! undercounts by at most a known amount however the summaries were merged, though which items survive can
+//! depend on the order, so merging is only approximately associative once counters start being dropped.
+//!
+//! [`AmsSketch`] follows Alon, Matias & Szegedy, [The space complexity of approximating the frequency
+//! moments](https://doi.org/10.1006/jcss.1997.1545): each counter adds up every occurrence with a hashed
+//! random sign, so its square is an unbiased estimate of the second frequency moment `F₂ = Σ fᵢ²`, the size
+//! of the data's self-join.  Averaging within groups of counters and taking the median across groups gives
+//! a reliable estimate; as with [`CountSketch`], the counters merge by addition.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign, RangeInclusive};
+
+use crate::hashing;
+use crate::traits::{
+    CancellativeMonoid, CommutativeMonoid, Group, Monoid, Semigroup, SemigroupAssign,
+};
+
+/// Unbiased frequency estimates from `D` rows of `W` signed counters
+///
+/// Each estimate is within about `‖f‖₂ / √W` of the truth, where `‖f‖₂` is the root of the sum of squared
+/// frequencies, with a failure probability falling exponentially in `D`.  Unlike Count-Min, errors go both
+/// ways rather than only overestimating.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CountSketch<const D: usize = 5, const W: usize = 1024>(Vec<i64>);
+
+impl<const D: usize, const W: usize> Default for CountSketch<D, W> {
+    fn default() -> Self {
+        const { assert!(D > 0 && W > 0, "a count sketch needs at least one counter") };
+        CountSketch(vec![0; D * W])
+    }
+}
+
+impl<const D: usize, const W: usize> CountSketch<D, W> {
+    /// Construct from a single occurrence.
+    pub fn new<T: Hash + ?Sized>(t: &T) -> Self {
+        let mut s = Self::default();
+        s.insert(t);
+        s
+    }
+    /// Record `n` more occurrences of `t`; negative `n` removes them.
+    pub fn add_count<T: Hash + ?Sized>(&mut self, t: &T, n: i64) {
+        for row in 0..D {
+            let (cell, sign) = Self::cell(t, row);
+            self.0[cell] += sign * n;
+        }
+    }
+    /// Record an occurrence of `t`.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        self.add_count(t, 1);
+    }
+    /// An estimate of the number of occurrences of `t`: the median of the rows' signed counters.
+    pub fn estimate<T: Hash + ?Sized>(&self, t: &T) -> f64 {
+        let mut readings: Vec<i64> = (0..D)
+            .map(|row| {
+                let (cell, sign) = Self::cell(t, row);
+                sign * self.0[cell]
+            })
+            .collect();
+        readings.sort_unstable();
+        if D % 2 == 1 {
+            readings[D / 2] as f64
+        } else {
+            (readings[D / 2 - 1] as f64 + readings[D / 2] as f64) / 2.0
+        }
+    }
+    /// The index of the counter `t` lands in on `row`, and the sign its counts take there.
+    fn cell<T: Hash + ?Sized>(t: &T, row: usize) -> (usize, i64) {
+        let h = hashing::hash(t, row as u64);
+        let sign = if h >> 63 == 0 { 1 } else { -1 };
+        (row * W + (h % W as u64) as usize, sign)
+    }
+}
+
+/// We can add a new occurrence.
+impl<T: Hash, const D: usize, const W: usize> Add<T> for CountSketch<D, W> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new occurrence.
+impl<T: Hash, const D: usize, const W: usize> AddAssign<T> for CountSketch<D, W> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the occurrences one at a time.
+impl<T: Hash, const D: usize, const W: usize> Extend<T> for CountSketch<D, W> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the occurrences one at a time.
+impl<T: Hash, const D: usize, const W: usize> FromIterator<T> for CountSketch<D, W> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::default();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Add the counters cell by cell.
+impl<const D: usize, const W: usize> Semigroup for CountSketch<D, W> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.op_assign(&y);
+        x
+    }
+}
+
+/// All counters zero.
+impl<const D: usize, const W: usize> Monoid for CountSketch<D, W> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Add in place.
+impl<const D: usize, const W: usize> SemigroupAssign for CountSketch<D, W> {
+    fn op_assign(&mut self, other: &Self) {
+        for (c, d) in self.0.iter_mut().zip(&other.0) {
+            *c += d;
+        }
+    }
+}
+
+/// Addition is commutative.
+impl<const D: usize, const W: usize> CommutativeMonoid for CountSketch<D, W> {}
+
+/// Negating every counter takes the counts back out.
+impl<const D: usize, const W: usize> Group for CountSketch<D, W> {
+    fn invert(&self) -> Self {
+        CountSketch(self.0.iter().map(|c| -c).collect())
+    }
+}
+
+/// Addition can be undone.
+impl<const D: usize, const W: usize> CancellativeMonoid for CountSketch<D, W> {}
+
+/// Counters for the (at most `K`) most frequent items, with bounds on their true counts
+///
+/// Any item occurring more than `n / (K + 1)` times among `n` is sure to be kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MisraGries<T: Eq + Hash, const K: usize = 100> {
+    /// Counters for the kept items, each an undercount by at most the slack
+    counters: HashMap<T, u64>,
+    /// Total number of items seen
+    count: u64,
+}
+
+impl<T: Eq + Hash, const K: usize> Default for MisraGries<T, K> {
+    fn default() -> Self {
+        const { assert!(K > 0, "a heavy-hitters summary needs at least one counter") };
+        MisraGries {
+            counters: HashMap::new(),
+            count: 0,
+        }
+    }
+}
+
+impl<T: Eq + Hash, const K: usize> MisraGries<T, K> {
+    /// Construct from a single item.
+    pub fn new(t: T) -> Self {
+        let mut s = Self::default();
+        s.insert(t);
+        s
+    }
+    /// Record an item.
+    pub fn insert(&mut self, t: T) {
+        *self.counters.entry(t).or_insert(0) += 1;
+        self.count += 1;
+        self.prune();
+    }
+    /// The number of items seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Whether no items have been seen.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The most any counter can fall short of its item's true count: `(n − Σ counters) / (K + 1)`.
+    pub fn slack(&self) -> u64 {
+        (self.count - self.counters.values().sum::<u64>()) / (K as u64 + 1)
+    }
+    /// The range in which the count of `t` must lie.
+    pub fn bounds(&self, t: &T) -> RangeInclusive<u64> {
+        let c = self.counters.get(t).copied().unwrap_or(0);
+        c..=c + self.slack()
+    }
+    /// The (at most) `k` kept items with the largest counters, most frequent first, with the ranges in which
+    /// their counts must lie; ties are broken arbitrarily.
+    pub fn top_k(&self, k: usize) -> Vec<(&T, RangeInclusive<u64>)> {
+        let slack = self.slack();
+        let mut top: Vec<_> = self.counters.iter().collect();
+        top.sort_unstable_by(|a, b| b.1.cmp(a.1));
+        top.into_iter()
+            .take(k)
+            .map(|(t, &c)| (t, c..=c + slack))
+            .collect()
+    }
+    /// If there are more than `K` counters, subtract the `(K + 1)`th largest from all and drop those left at
+    /// zero.
+    fn prune(&mut self) {
+        if self.counters.len() <= K {
+            return;
+        }
+        let mut counts: Vec<u64> = self.counters.values().copied().collect();
+        let (_, &mut cut, _) = counts.select_nth_unstable_by(K, |a, b| b.cmp(a));
+        self.counters.retain(|_, c| {
+            *c -= cut.min(*c);
+            *c > 0
+        });
+    }
+}
+
+/// We can add a new item.
+impl<T: Eq + Hash, const K: usize> Add<T> for MisraGries<T, K> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(t);
+        self
+    }
+}
+
+/// We can add a new item.
+impl<T: Eq + Hash, const K: usize> AddAssign<T> for MisraGries<T, K> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(t);
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Eq + Hash, const K: usize> Extend<T> for MisraGries<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(t);
+        }
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Eq + Hash, const K: usize> FromIterator<T> for MisraGries<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::default();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Add the counters, then prune back to `K`.
+impl<T: Eq + Hash + Clone, const K: usize> Semigroup for MisraGries<T, K> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        if x.counters.len() < y.counters.len() {
+            return Self::op_owned(y, x);
+        }
+        for (t, c) in y.counters {
+            *x.counters.entry(t).or_insert(0) += c;
+        }
+        x.count += y.count;
+        x.prune();
+        x
+    }
+}
+
+/// No items.
+impl<T: Eq + Hash + Clone, const K: usize> Monoid for MisraGries<T, K> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merge in place.
+impl<T: Eq + Hash + Clone, const K: usize> SemigroupAssign for MisraGries<T, K> {
+    fn op_assign(&mut self, other: &Self) {
+        for (t, &c) in &other.counters {
+            *self.counters.entry(t.clone()).or_insert(0) += c;
+        }
+        self.count += other.count;
+        self.prune();
+    }
+}
+
+/// Both sides are treated alike.
+impl<T: Eq + Hash + Clone, const K: usize> CommutativeMonoid for MisraGries<T, K> {}
+
+/// Estimates of the second frequency moment from `D` groups of `W` signed counters
+///
+/// The relative standard error of the estimate is about `√(2 / W)`, with a failure probability falling
+/// exponentially in `D`.  A large `F₂` relative to the square of the number of items signals skew.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmsSketch<const D: usize = 5, const W: usize = 64>(Vec<i64>);
+
+impl<const D: usize, const W: usize> Default for AmsSketch<D, W> {
+    fn default() -> Self {
+        const { assert!(D > 0 && W > 0, "an AMS sketch needs at least one counter") };
+        AmsSketch(vec![0; D * W])
+    }
+}
+
+impl<const D: usize, const W: usize> AmsSketch<D, W> {
+    /// Construct from a single occurrence.
+    pub fn new<T: Hash + ?Sized>(t: &T) -> Self {
+        let mut s = Self::default();
+        s.insert(t);
+        s
+    }
+    /// Record `n` more occurrences of `t`; negative `n` removes them.
+    pub fn add_count<T: Hash + ?Sized>(&mut self, t: &T, n: i64) {
+        let h = hashing::hash(t, 0);
+        for (j, c) in self.0.iter_mut().enumerate() {
+            let sign = if hashing::mix(h ^ hashing::mix(j as u64)) >> 63 == 0 {
+                1
+            } else {
+                -1
+            };
+            *c += sign * n;
+        }
+    }
+    /// Record an occurrence of `t`.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        self.add_count(t, 1);
+    }
+    /// An estimate of the second frequency moment `Σ fᵢ²`.
+    pub fn estimate(&self) -> f64 {
+        self.join_size(self)
+    }
+    /// An estimate of the inner product `Σ fᵢ gᵢ` of the two sketches' frequencies, i.e. the size of the
+    /// join between the two datasets on their items.
+    pub fn join_size(&self, other: &Self) -> f64 {
+        let mut means: Vec<f64> = self
+            .0
+            .chunks(W)
+            .zip(other.0.chunks(W))
+            .map(|(xs, ys)| {
+                let products: f64 = xs.iter().zip(ys).map(|(&x, &y)| x as f64 * y as f64).sum();
+                products / W as f64
+            })
+            .collect();
+        means.sort_unstable_by(f64::total_cmp);
+        if D % 2 == 1 {
+            means[D / 2]
+        } else {
+            (means[D / 2 - 1] + means[D / 2]) / 2.0
+        }
+    }
+}
+
+/// We can add a new occurrence.
+impl<T: Hash, const D: usize, const W: usize> Add<T> for AmsSketch<D, W> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new occurrence.
+impl<T: Hash, const D: usize, const W: usize> AddAssign<T> for AmsSketch<D, W> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the occurrences one at a time.
+impl<T: Hash, const D: usize, const W: usize> Extend<T> for AmsSketch<D, W> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the occurrences one at a time.
+impl<T: Hash, const D: usize, const W: usize> FromIterator<T> for AmsSketch<D, W> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::default();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Add the counters cell by cell.
+impl<const D: usize, const W: usize> Semigroup for AmsSketch<D, W> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.op_assign(&y);
+        x
+    }
+}
+
+/// All counters zero.
+impl<const D: usize, const W: usize> Monoid for AmsSketch<D, W> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Add in place.
+impl<const D: usize, const W: usize> SemigroupAssign for AmsSketch<D, W> {
+    fn op_assign(&mut self, other: &Self) {
+        for (c, d) in self.0.iter_mut().zip(&other.0) {
+            *c += d;
+        }
+    }
+}
+
+/// Addition is commutative.
+impl<const D: usize, const W: usize> CommutativeMonoid for AmsSketch<D, W> {}
+
+/// Negating every counter takes the counts back out.
+impl<const D: usize, const W: usize> Group for AmsSketch<D, W> {
+    fn invert(&self) -> Self {
+        AmsSketch(self.0.iter().map(|c| -c).collect())
+    }
+}
+
+/// Addition can be undone.
+impl<const D: usize, const W: usize> CancellativeMonoid for AmsSketch<D, W> {}
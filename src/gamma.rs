@@ -0,0 +1,235 @@
+//! The gamma distribution, fitted by the method of moments or by maximum likelihood
+//!
+//! The maximum-likelihood fit depends on the data only through the count, the sum and the sum of
+//! logarithms, which merge by addition; the method of moments needs the variance as well, which a
+//! [`Gaussian`] carries.
+
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::distribution::Distribution;
+use crate::gaussian::Gaussian;
+use crate::special::{self, close};
+use crate::traits::{Group, Monoid, Semigroup, SemigroupAssign};
+
+/// Sufficient statistics for fitting a gamma distribution to positive data
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GammaSummary {
+    /// Count, mean and variance
+    moments: Gaussian,
+    /// Sum of the logarithms of the data
+    log_sum: f64,
+}
+
+/// Equal moments, and sums of logarithms within `numpy.isclose`'s default tolerances.
+impl PartialEq for GammaSummary {
+    fn eq(&self, other: &Self) -> bool {
+        self.moments == other.moments && close(self.log_sum, other.log_sum)
+    }
+}
+
+impl GammaSummary {
+    /// Construct from a single data point, which must be positive.
+    pub fn new(x: f64) -> Self {
+        assert!(x > 0.0, "Gamma-distributed data must be positive.");
+        GammaSummary {
+            moments: Gaussian::new(x),
+            log_sum: x.ln(),
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> f64 {
+        self.moments.count()
+    }
+    /// The mean of the data.
+    pub fn mean(&self) -> f64 {
+        self.moments.mean()
+    }
+    /// The (sample) variance of the data.
+    pub fn variance(&self) -> f64 {
+        self.moments.variance()
+    }
+    /// The mean of the logarithms of the data.
+    pub fn mean_log(&self) -> f64 {
+        self.log_sum / self.count()
+    }
+    /// Shape `k` and scale `θ` matching the sample mean `kθ` and variance `kθ²`; needs at least two data
+    /// points.
+    pub fn method_of_moments(&self) -> (f64, f64) {
+        let (m, v) = (self.mean(), self.variance());
+        (m * m / v, v / m)
+    }
+    /// Maximum-likelihood shape `k` and scale `θ`, by Newton's method from Minka's closed-form approximation.
+    ///
+    /// Needs at least two distinct data points.
+    pub fn mle(&self) -> (f64, f64) {
+        let m = self.mean();
+        // ln k − ψ(k) = s, which is positive by Jensen's inequality unless the data are all equal.
+        let s = m.ln() - self.mean_log();
+        assert!(
+            s > 0.0,
+            "Fitting a gamma distribution requires distinct data."
+        );
+        let mut k = (3.0 - s + ((s - 3.0).powi(2) + 24.0 * s).sqrt()) / (12.0 * s);
+        for _ in 0..50 {
+            let step = (k.ln() - special::digamma(k) - s) / (1.0 / k - special::trigamma(k));
+            k -= step;
+            if step.abs() <= 1e-14 * k {
+                break;
+            }
+        }
+        (k, m / k)
+    }
+    /// Probability Density Function of the maximum-likelihood fit.
+    pub fn pdf(&self, x: f64) -> f64 {
+        self.log_pdf(x).exp()
+    }
+    /// Natural logarithm of the [`pdf`](GammaSummary::pdf), which doesn't underflow in the tails.
+    pub fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let (k, theta) = self.mle();
+        (k - 1.0) * x.ln() - x / theta - libm::lgamma(k) - k * theta.ln()
+    }
+    /// Cumulative Distribution Function of the maximum-likelihood fit.
+    pub fn cdf(&self, x: f64) -> f64 {
+        let (k, theta) = self.mle();
+        special::gamma_p(k, x / theta)
+    }
+    /// Survival function of the maximum-likelihood fit, `1 - cdf(x)`, without the cancellation in the tail.
+    pub fn sf(&self, x: f64) -> f64 {
+        let (k, theta) = self.mle();
+        special::gamma_q(k, x / theta)
+    }
+}
+
+/// We can add a new data point.
+impl Add<f64> for GammaSummary {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point.
+impl AddAssign<f64> for GammaSummary {
+    fn add_assign(&mut self, x: f64) {
+        assert!(x > 0.0, "Gamma-distributed data must be positive.");
+        self.moments += x;
+        self.log_sum += x.ln();
+    }
+}
+
+/// Accumulate the points one at a time.
+impl FromIterator<f64> for GammaSummary {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut g = Self::default();
+        g.extend(iter);
+        g
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a> FromIterator<&'a f64> for GammaSummary {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the points one at a time.
+impl Extend<f64> for GammaSummary {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            *self += x
+        }
+    }
+}
+
+/// Draw samples from the maximum-likelihood fit, by Marsaglia and Tsang's rejection method.
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<f64> for GammaSummary {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (k, theta) = self.mle();
+        // The method needs a shape of at least 1; smaller shapes are boosted by one and scaled back down.
+        let (k, boost) = if k < 1.0 {
+            (k + 1.0, (1.0 - rng.gen::<f64>()).powf(1.0 / k))
+        } else {
+            (k, 1.0)
+        };
+        let d = k - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            // A standard normal deviate, via the Box–Muller transform
+            let z = (-2.0 * (1.0 - rng.gen::<f64>()).ln()).sqrt()
+                * (2.0 * std::f64::consts::PI * rng.gen::<f64>()).cos();
+            let v = (1.0 + c * z).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            let u = 1.0 - rng.gen::<f64>();
+            if u.ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+                return d * v * theta * boost;
+            }
+        }
+    }
+}
+
+/// The maximum-likelihood fit, whose mean is the sample mean but whose variance `kθ²` generally differs
+/// from the sample [`variance`](GammaSummary::variance).
+impl Distribution for GammaSummary {
+    type Value = f64;
+    type Real = f64;
+    fn mean(&self) -> f64 {
+        self.mean()
+    }
+    fn variance(&self) -> f64 {
+        let (k, theta) = self.mle();
+        k * theta * theta
+    }
+    fn pdf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+    fn log_pdf(&self, x: f64) -> f64 {
+        self.log_pdf(x)
+    }
+    fn cdf(&self, x: f64) -> f64 {
+        self.cdf(x)
+    }
+}
+
+/// Merge the moments, and add the sums of logarithms.
+impl Semigroup for GammaSummary {
+    fn op(x: &Self, y: &Self) -> Self {
+        GammaSummary {
+            moments: Semigroup::op(&x.moments, &y.moments),
+            log_sum: x.log_sum + y.log_sum,
+        }
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for GammaSummary {
+    fn op_assign(&mut self, other: &Self) {
+        self.moments.op_assign(&other.moments);
+        self.log_sum += other.log_sum;
+    }
+}
+
+/// The "empty distribution."
+impl Monoid for GammaSummary {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Invert the moments and negate the sum of logarithms.
+impl Group for GammaSummary {
+    fn invert(&self) -> Self {
+        GammaSummary {
+            moments: self.moments.invert(),
+            log_sum: -self.log_sum,
+        }
+    }
+}
@@ -1,81 +1,227 @@
 //! See HLearn's original [Gaussian distribution work](https://github.com/mikeizbicki/HLearn/blob/bb258e88a0f42be4cead167b4da2694a1a2c4605/src/HLearn/Models/Distributions/Gaussian.hs).
 
-use std::f64::consts::PI;
+use std::f64::consts::{PI, SQRT_2};
 use std::iter::FromIterator;
 use std::ops::{Add, AddAssign};
 
-use crate::traits::{Monoid, Semigroup};
+use num_traits::Float;
+
+use crate::distribution::Distribution;
+use crate::special::{self, close, lit, wide};
+use crate::traits::{Group, Monoid, Semigroup, SemigroupAssign};
 
 /// Parameterized 1D Gaussian distribution
+///
+/// Generic over the float type `T`, which defaults to `f64`.
 #[derive(Clone, Copy, Debug)]
-pub struct Gaussian {
+pub struct Gaussian<T: Float = f64> {
     /// First moment of distribution (mean)
-    m1: f64,
+    m1: T,
     /// Second moment of distribution
-    m2: f64,
+    m2: T,
     /// Count of datapoints (stored as a float for convenience)
-    n: f64,
+    n: T,
 }
 
-/// numpy.isclose
-fn _close(x: f64, y: f64) -> bool {
-    (x - y).abs() <= 1e-8 + 1e-5 * y.abs()
+/// The error function, computed in `f64` precision.
+fn erf<T: Float>(x: T) -> T {
+    lit(libm::erf(wide(x)))
 }
 
-impl PartialEq for Gaussian {
-    fn eq(&self, other: &Gaussian) -> bool {
-        (self.n == other.n) && _close(self.m1, other.m1) && _close(self.m2, other.m2)
+/// Equal counts, and means and second moments within `numpy.isclose`'s default tolerances; see
+/// [`Gaussian::bitwise_eq`] for exact comparison, or enable the `approx` feature for custom tolerances.
+impl<T: Float> PartialEq for Gaussian<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.n == other.n) && close(self.m1, other.m1) && close(self.m2, other.m2)
     }
 }
-impl Eq for Gaussian {}
+impl<T: Float> Eq for Gaussian<T> {}
 
-impl Default for Gaussian {
+impl<T: Float> Default for Gaussian<T> {
     fn default() -> Self {
         Gaussian {
-            m1: 0.0,
-            m2: 0.0,
-            n: 0.0,
+            m1: T::zero(),
+            m2: T::zero(),
+            n: T::zero(),
         }
     }
 }
 
-impl Gaussian {
+impl<T: Float> Gaussian<T> {
     /// Construct from a single data point.
-    pub fn new(x: f64) -> Gaussian {
+    pub fn new(x: T) -> Self {
         Gaussian {
             m1: x,
-            m2: 0.0,
-            n: 1.0,
+            m2: T::zero(),
+            n: T::one(),
         }
     }
+    /// Whether every statistic matches exactly, bit for bit.
+    pub fn bitwise_eq(&self, other: &Self) -> bool {
+        let bits = |g: &Self| [g.m1, g.m2, g.n].map(|x| x.integer_decode());
+        bits(self) == bits(other)
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> T {
+        self.n
+    }
     /// The mean of this distribution.
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> T {
         self.m1
     }
     /// The (sample) variance of this distribution.
-    pub fn variance(&self) -> f64 {
-        assert!(self.n > 1.0, "Variance requires more than 1 sample.");
-        self.m2 / (self.n - 1.0)
+    pub fn variance(&self) -> T {
+        assert!(self.n > T::one(), "Variance requires more than 1 sample.");
+        self.m2 / (self.n - T::one())
+    }
+    /// The (sample) variance, or `None` with fewer than 2 samples.
+    pub fn try_variance(&self) -> Option<T> {
+        (self.n > T::one()).then(|| self.m2 / (self.n - T::one()))
+    }
+    /// The population variance, treating the data as the whole population rather than a sample.
+    pub fn population_variance(&self) -> T {
+        assert!(
+            self.n > T::zero(),
+            "Population variance requires at least 1 sample."
+        );
+        self.m2 / self.n
+    }
+    /// The (sample) standard deviation.
+    pub fn std_dev(&self) -> T {
+        self.variance().sqrt()
+    }
+    /// The population standard deviation.
+    pub fn population_std_dev(&self) -> T {
+        self.population_variance().sqrt()
     }
     /// Probability Density Function.
-    pub fn pdf(&self, x: f64) -> f64 {
+    pub fn pdf(&self, x: T) -> T {
         let m = self.mean();
         let v = self.variance();
-        1.0 / (2.0 * PI * v).sqrt() * (-0.5 * ((x - m).powi(2) / v)).exp()
+        T::one() / (lit::<T>(2.0 * PI) * v).sqrt() * (lit::<T>(-0.5) * ((x - m).powi(2) / v)).exp()
     }
     /// Cumulative Distribution Function.
-    pub fn cdf(&self, x: f64) -> f64 {
+    pub fn cdf(&self, x: T) -> T {
+        let m = self.mean();
+        let v = self.variance();
+        lit::<T>(0.5) * (T::one() + erf((x - m) / (lit::<T>(2.0) * v).sqrt()))
+    }
+    /// Natural logarithm of the [`pdf`](Gaussian::pdf), which doesn't underflow far from the mean.
+    pub fn log_pdf(&self, x: T) -> T {
         let m = self.mean();
         let v = self.variance();
-        0.5 * (1.0 + libm::erf((x - m) / (2.0 * v).sqrt()))
+        lit::<T>(-0.5) * ((lit::<T>(2.0 * PI) * v).ln() + (x - m).powi(2) / v)
+    }
+    /// Natural logarithm of the [`cdf`](Gaussian::cdf), accurate far into the lower tail.
+    pub fn log_cdf(&self, x: T) -> T {
+        lit(special::log_ndtr(wide(self.z_score(x))))
+    }
+    /// Survival function, `1 - cdf(x)`, without the cancellation in the upper tail.
+    pub fn sf(&self, x: T) -> T {
+        lit(0.5 * libm::erfc(wide(self.z_score(x)) / SQRT_2))
+    }
+    /// Natural logarithm of the [`sf`](Gaussian::sf), accurate far into the upper tail.
+    pub fn log_sf(&self, x: T) -> T {
+        lit(special::log_ndtr(-wide(self.z_score(x))))
+    }
+    /// Kullback–Leibler divergence `KL(self ‖ other)`, in nats; note that it isn't symmetric.
+    pub fn kl_divergence(&self, other: &Self) -> T {
+        let (v_p, v_q) = (self.variance(), other.variance());
+        lit::<T>(0.5)
+            * ((v_q / v_p).ln() + (v_p + (self.mean() - other.mean()).powi(2)) / v_q - T::one())
+    }
+    /// Hellinger distance, a metric between distributions taking values in `[0, 1]`.
+    pub fn hellinger(&self, other: &Self) -> T {
+        let (v_p, v_q) = (self.variance(), other.variance());
+        let d = self.mean() - other.mean();
+        let bc = (lit::<T>(2.0) * (v_p * v_q).sqrt() / (v_p + v_q)).sqrt()
+            * (lit::<T>(-0.25) * d * d / (v_p + v_q)).exp();
+        (T::one() - bc).max(T::zero()).sqrt()
+    }
+    /// 2-Wasserstein (earth mover's) distance, in the units of the data.
+    pub fn wasserstein2(&self, other: &Self) -> T {
+        let d = self.mean() - other.mean();
+        let s = self.std_dev() - other.std_dev();
+        (d * d + s * s).sqrt()
+    }
+    /// Standard error of the mean.
+    pub fn std_error(&self) -> T {
+        (self.variance() / self.n).sqrt()
+    }
+    /// Normal-approximation confidence interval for the mean at the given `level`, e.g. `0.95`; see
+    /// [`mean_ci`](Gaussian::mean_ci) for small samples.
+    pub fn confidence_interval(&self, level: T) -> (T, T) {
+        let z = lit::<T>(special::probit(wide((T::one() + level) / lit(2.0))));
+        self.interval(z * self.std_error())
+    }
+    /// Student's t confidence interval for the mean at the given `level`, e.g. `0.95`; exact for normally
+    /// distributed data of any sample size.
+    pub fn mean_ci(&self, level: T) -> (T, T) {
+        let p = wide((T::one() + level) / lit(2.0));
+        let t = lit::<T>(special::student_t_quantile(p, wide(self.n - T::one())));
+        self.interval(t * self.std_error())
+    }
+    /// The interval of the given half-width around the mean.
+    fn interval(&self, half_width: T) -> (T, T) {
+        (self.mean() - half_width, self.mean() + half_width)
+    }
+    /// Standard deviations from the mean.
+    fn z_score(&self, x: T) -> T {
+        (x - self.mean()) / self.std_dev()
+    }
+    /// Remove a data point previously added, e.g. one leaving a sliding window.
+    pub fn remove(&mut self, x: T) {
+        let n = self.n - T::one();
+        if n == T::zero() {
+            *self = Self::default();
+        } else if n == T::one() {
+            // What's left is a single point, which we can recover exactly.
+            *self = Self::new(self.m1 * lit(2.0) - x);
+        } else {
+            let m1_old = self.m1;
+            self.m1 = m1_old - (x - m1_old) / n;
+            self.m2 = self.m2 - (x - m1_old) * (x - self.m1);
+            self.n = n;
+        }
+    }
+    /// Remove a summary previously merged into this one, undoing [`Semigroup::op`].
+    pub fn subtract(&mut self, other: &Self) {
+        self.op_assign(&other.invert());
+    }
+    /// Add a batch of points, faster and more accurately than one at a time: short runs are summarized in two
+    /// passes, and the summaries merged pairwise.
+    pub fn extend_from_slice(&mut self, xs: &[T]) {
+        self.op_assign(&Self::from_slice(xs));
+    }
+    /// Summarize a slice by splitting it in half until the pieces are short.
+    fn from_slice(xs: &[T]) -> Self {
+        const CHUNK: usize = 256;
+        if xs.len() > CHUNK {
+            let (l, r) = xs.split_at(xs.len() / 2);
+            Semigroup::op(&Self::from_slice(l), &Self::from_slice(r))
+        } else if xs.is_empty() {
+            Self::default()
+        } else {
+            let n = lit::<T>(xs.len() as f64);
+            let m1 = xs.iter().fold(T::zero(), |s, &x| s + x) / n;
+            let m2 = xs.iter().fold(T::zero(), |s, &x| s + (x - m1).powi(2));
+            Gaussian { m1, m2, n }
+        }
+    }
+    /// Quantile function (inverse CDF): the `x` with `cdf(x) == p`.
+    ///
+    /// Returns ∓∞ for `p` of 0 or 1, and NaN outside `[0, 1]`.
+    pub fn quantile(&self, p: T) -> T {
+        let z = lit(special::probit(wide(p)));
+        self.mean() + self.std_dev() * z
     }
 }
 
 /// We can add a new data point to a Gaussian distribution.
-impl Add<f64> for Gaussian {
+impl<T: Float> Add<T> for Gaussian<T> {
     type Output = Self;
-    fn add(self, x: f64) -> Self::Output {
-        let n = self.n + 1.0;
+    fn add(self, x: T) -> Self::Output {
+        let n = self.n + T::one();
         let m1 = self.m1 + (x - self.m1) / n;
         let m2 = self.m2 + (x - self.m1) * (x - m1);
         Gaussian { m1, m2, n }
@@ -83,19 +229,19 @@ impl Add<f64> for Gaussian {
 }
 
 /// We can add a new data point to a Gaussian distribution.
-impl AddAssign<f64> for Gaussian {
-    fn add_assign(&mut self, x: f64) {
-        self.n += 1.0;
+impl<T: Float> AddAssign<T> for Gaussian<T> {
+    fn add_assign(&mut self, x: T) {
+        self.n = self.n + T::one();
         let m1_old = self.m1;
-        self.m1 += (x - m1_old) / self.n;
-        self.m2 += (x - m1_old) * (x - self.m1);
+        self.m1 = self.m1 + (x - m1_old) / self.n;
+        self.m2 = self.m2 + (x - m1_old) * (x - self.m1);
     }
 }
 
 /// Accumulate the points one at a time into a new Gaussian distribution.
-impl FromIterator<f64> for Gaussian {
-    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
-        let mut g = Default::default();
+impl<T: Float> FromIterator<T> for Gaussian<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut g = Self::default();
         for i in iter {
             g += i
         }
@@ -104,9 +250,9 @@ impl FromIterator<f64> for Gaussian {
 }
 
 /// Accumulate the points one at a time into a new Gaussian distribution.
-impl<'a> FromIterator<&'a f64> for Gaussian {
-    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
-        let mut g = Default::default();
+impl<'a, T: Float> FromIterator<&'a T> for Gaussian<T> {
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        let mut g = Self::default();
         for i in iter {
             g += *i
         }
@@ -114,8 +260,121 @@ impl<'a> FromIterator<&'a f64> for Gaussian {
     }
 }
 
+/// Add the points one at a time.
+impl<T: Float> Extend<T> for Gaussian<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            *self += x
+        }
+    }
+}
+
+/// Add the points one at a time.
+impl<'a, T: Float> Extend<&'a T> for Gaussian<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for x in iter {
+            *self += *x
+        }
+    }
+}
+
+/// Accumulate points on each thread, then merge the partial distributions.
+#[cfg(feature = "rayon")]
+impl<T: Float + Send> rayon::iter::FromParallelIterator<T> for Gaussian<T> {
+    fn from_par_iter<I: rayon::iter::IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+        use rayon::iter::ParallelIterator;
+        par_iter
+            .into_par_iter()
+            .fold(Self::default, |g, x| g + x)
+            .reduce(Self::default, |g, h| Semigroup::op(&g, &h))
+    }
+}
+
+/// Accumulate points on each thread, then merge the partial distributions.
+#[cfg(feature = "rayon")]
+impl<'a, T: Float + Send + Sync> rayon::iter::FromParallelIterator<&'a T> for Gaussian<T> {
+    fn from_par_iter<I: rayon::iter::IntoParallelIterator<Item = &'a T>>(par_iter: I) -> Self {
+        use rayon::iter::ParallelIterator;
+        par_iter.into_par_iter().copied().collect()
+    }
+}
+
+/// Compare every statistic within an absolute tolerance.
+#[cfg(feature = "approx")]
+impl<T: Float + approx::AbsDiffEq<Epsilon = T>> approx::AbsDiffEq for Gaussian<T> {
+    type Epsilon = T;
+    fn default_epsilon() -> T {
+        T::default_epsilon()
+    }
+    fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.n.abs_diff_eq(&other.n, epsilon)
+            && self.m1.abs_diff_eq(&other.m1, epsilon)
+            && self.m2.abs_diff_eq(&other.m2, epsilon)
+    }
+}
+
+/// Compare every statistic within a relative tolerance.
+#[cfg(feature = "approx")]
+impl<T: Float + approx::RelativeEq<Epsilon = T>> approx::RelativeEq for Gaussian<T> {
+    fn default_max_relative() -> T {
+        T::default_max_relative()
+    }
+    fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+        self.n.relative_eq(&other.n, epsilon, max_relative)
+            && self.m1.relative_eq(&other.m1, epsilon, max_relative)
+            && self.m2.relative_eq(&other.m2, epsilon, max_relative)
+    }
+}
+
+/// Compare every statistic within a number of units in the last place.
+#[cfg(feature = "approx")]
+impl<T: Float + approx::UlpsEq<Epsilon = T>> approx::UlpsEq for Gaussian<T> {
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+    fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+        self.n.ulps_eq(&other.n, epsilon, max_ulps)
+            && self.m1.ulps_eq(&other.m1, epsilon, max_ulps)
+            && self.m2.ulps_eq(&other.m2, epsilon, max_ulps)
+    }
+}
+
+/// Draw samples from the fitted distribution, via the Box–Muller transform; like
+/// [`variance`](Gaussian::variance), this needs at least two data points.
+#[cfg(feature = "rand")]
+impl<T: Float> rand::distributions::Distribution<T> for Gaussian<T> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> T {
+        // `gen` is in `[0, 1)`, so flip it to keep the logarithm finite.
+        let u = 1.0 - rng.gen::<f64>();
+        let v = rng.gen::<f64>();
+        let z = (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos();
+        self.mean() + self.std_dev() * lit(z)
+    }
+}
+
+/// The normal distribution with the sample mean and variance.
+impl<T: Float> Distribution for Gaussian<T> {
+    type Value = T;
+    type Real = T;
+    fn mean(&self) -> T {
+        self.mean()
+    }
+    fn variance(&self) -> T {
+        self.variance()
+    }
+    fn pdf(&self, x: T) -> T {
+        self.pdf(x)
+    }
+    fn log_pdf(&self, x: T) -> T {
+        self.log_pdf(x)
+    }
+    fn cdf(&self, x: T) -> T {
+        self.cdf(x)
+    }
+}
+
 /// Join together two gaussian distributions.
-impl Semigroup for Gaussian {
+impl<T: Float> Semigroup for Gaussian<T> {
     fn op(
         &Gaussian {
             m1: m1_a,
@@ -129,7 +388,7 @@ impl Semigroup for Gaussian {
         }: &Self,
     ) -> Self {
         let n = n_a + n_b;
-        if n == 0.0 {
+        if n == T::zero() {
             Self::default()
         } else {
             let m1 = m1_a * (n_a / n) + m1_b * (n_b / n);
@@ -139,9 +398,35 @@ impl Semigroup for Gaussian {
     }
 }
 
+/// Join another gaussian distribution into this one.
+impl<T: Float> SemigroupAssign for Gaussian<T> {
+    fn op_assign(&mut self, other: &Self) {
+        let n = self.n + other.n;
+        if n == T::zero() {
+            *self = Self::default();
+        } else {
+            let d = self.m1 - other.m1;
+            self.m2 = self.m2 + other.m2 + d.powi(2) * (self.n * other.n) / n;
+            self.m1 = self.m1 * (self.n / n) + other.m1 * (other.n / n);
+            self.n = n;
+        }
+    }
+}
+
 /// The "empty distribution."
-impl Monoid for Gaussian {
+impl<T: Float> Monoid for Gaussian<T> {
     fn zero() -> Self {
         Self::default()
     }
 }
+
+/// Negating the count and the second moment gives a "distribution" that merges with this one to nothing.
+impl<T: Float> Group for Gaussian<T> {
+    fn invert(&self) -> Self {
+        Gaussian {
+            m1: self.m1,
+            m2: -self.m2,
+            n: -self.n,
+        }
+    }
+}
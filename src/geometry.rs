@@ -0,0 +1,199 @@
+//! Geometric primitives whose natural combinations are monoids
+//!
+//! Bounding boxes merge by enclosure and transforms by composition, so both can be built up from pieces
+//! in parallel and folded with the generic machinery in [`crate::traits`].
+use std::iter::FromIterator;
+
+use crate::traits::{
+    Band, CommutativeMonoid, Monoid, MonoidAction, Semigroup, SemigroupAssign, Semilattice,
+};
+
+/// An axis-aligned bounding box in `D` dimensions, combined by taking the smallest box enclosing both
+///
+/// The identity is the empty box, with every `min` at `+∞` and every `max` at `-∞`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb<const D: usize> {
+    /// Lower corner.
+    pub min: [f64; D],
+    /// Upper corner.
+    pub max: [f64; D],
+}
+
+/// Bounding boxes in the plane.
+pub type Aabb2 = Aabb<2>;
+
+/// Bounding boxes in space.
+pub type Aabb3 = Aabb<3>;
+
+impl<const D: usize> Aabb<D> {
+    /// The degenerate box containing only `p`.
+    pub fn from_point(p: [f64; D]) -> Self {
+        Aabb { min: p, max: p }
+    }
+    /// The box with corners `a` and `b`, in either order.
+    pub fn from_corners(a: [f64; D], b: [f64; D]) -> Self {
+        Aabb {
+            min: std::array::from_fn(|i| a[i].min(b[i])),
+            max: std::array::from_fn(|i| a[i].max(b[i])),
+        }
+    }
+    /// Whether the box contains no points at all.
+    pub fn is_empty(&self) -> bool {
+        self.min.iter().zip(&self.max).any(|(lo, hi)| lo > hi)
+    }
+    /// Whether `p` lies in the (closed) box.
+    pub fn contains(&self, p: &[f64; D]) -> bool {
+        (0..D).all(|i| self.min[i] <= p[i] && p[i] <= self.max[i])
+    }
+    /// Whether the two (closed) boxes share a point.
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..D).all(|i| self.min[i].max(other.min[i]) <= self.max[i].min(other.max[i]))
+    }
+    /// The midpoint, or `None` for the empty box.
+    pub fn center(&self) -> Option<[f64; D]> {
+        (!self.is_empty()).then(|| std::array::from_fn(|i| (self.min[i] + self.max[i]) / 2.0))
+    }
+    /// Side lengths, or `None` for the empty box.
+    pub fn extent(&self) -> Option<[f64; D]> {
+        (!self.is_empty()).then(|| std::array::from_fn(|i| self.max[i] - self.min[i]))
+    }
+}
+
+impl<const D: usize> Default for Aabb<D> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const D: usize> FromIterator<[f64; D]> for Aabb<D> {
+    fn from_iter<I: IntoIterator<Item = [f64; D]>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::zero(), |mut b, p| {
+            b.op_assign(&Self::from_point(p));
+            b
+        })
+    }
+}
+
+/// The smallest box enclosing both.
+impl<const D: usize> Semigroup for Aabb<D> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Aabb {
+            min: std::array::from_fn(|i| x.min[i].min(y.min[i])),
+            max: std::array::from_fn(|i| x.max[i].max(y.max[i])),
+        }
+    }
+}
+
+/// The empty box.
+impl<const D: usize> Monoid for Aabb<D> {
+    fn zero() -> Self {
+        Aabb {
+            min: [f64::INFINITY; D],
+            max: [f64::NEG_INFINITY; D],
+        }
+    }
+}
+
+/// Grow in place.
+impl<const D: usize> SemigroupAssign for Aabb<D> {
+    fn op_assign(&mut self, other: &Self) {
+        for i in 0..D {
+            self.min[i] = self.min[i].min(other.min[i]);
+            self.max[i] = self.max[i].max(other.max[i]);
+        }
+    }
+}
+
+/// Enclosure doesn't care about order.
+impl<const D: usize> CommutativeMonoid for Aabb<D> {}
+
+/// A box already encloses itself.
+impl<const D: usize> Band for Aabb<D> {}
+
+/// Boxes under enclosure.
+impl<const D: usize> Semilattice for Aabb<D> {}
+
+/// An affine transformation of the plane, `p ↦ A p + t`, combined by composition
+///
+/// Stored as the top two rows `[[a, b, tx], [c, d, ty]]` of the usual 3×3 homogeneous matrix.  As with
+/// [`Endo`](crate::combinators::Endo), `op(f, g)` applies `g` first and then `f`, so a chain of transforms
+/// folds left-to-right into "outermost first" order and repeats with [`power_monoid`](crate::traits::power_monoid).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine2(pub [[f64; 3]; 2]);
+
+impl Affine2 {
+    /// Shift by `(dx, dy)`.
+    pub fn translation(dx: f64, dy: f64) -> Self {
+        Affine2([[1.0, 0.0, dx], [0.0, 1.0, dy]])
+    }
+    /// Stretch by `sx` horizontally and `sy` vertically.
+    pub fn scaling(sx: f64, sy: f64) -> Self {
+        Affine2([[sx, 0.0, 0.0], [0.0, sy, 0.0]])
+    }
+    /// Rotate counterclockwise about the origin by `theta` radians.
+    pub fn rotation(theta: f64) -> Self {
+        let (s, c) = theta.sin_cos();
+        Affine2([[c, -s, 0.0], [s, c, 0.0]])
+    }
+    /// Transform the point `p`.
+    pub fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let [[a, b, tx], [c, d, ty]] = self.0;
+        [a * x + b * y + tx, c * x + d * y + ty]
+    }
+    /// Determinant of the linear part; zero means the transform collapses the plane.
+    pub fn determinant(&self) -> f64 {
+        let [[a, b, _], [c, d, _]] = self.0;
+        a * d - b * c
+    }
+    /// The transform undoing this one, or `None` if it's singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let [[a, b, tx], [c, d, ty]] = self.0;
+        let (ia, ib, ic, id) = (d / det, -b / det, -c / det, a / det);
+        Some(Affine2([
+            [ia, ib, -(ia * tx + ib * ty)],
+            [ic, id, -(ic * tx + id * ty)],
+        ]))
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Composition, so that `op(f, g)` applies `g` first and then `f`.
+impl Semigroup for Affine2 {
+    fn op(Affine2(f): &Self, Affine2(g): &Self) -> Self {
+        let row = |r: [f64; 3]| {
+            [
+                r[0] * g[0][0] + r[1] * g[1][0],
+                r[0] * g[0][1] + r[1] * g[1][1],
+                r[0] * g[0][2] + r[1] * g[1][2] + r[2],
+            ]
+        };
+        Affine2([row(f[0]), row(f[1])])
+    }
+}
+
+/// The identity transform.
+impl Monoid for Affine2 {
+    fn zero() -> Self {
+        Affine2([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+    }
+}
+
+/// Compose in place.
+impl SemigroupAssign for Affine2 {}
+
+/// Transforms act on points by application.
+impl MonoidAction<[f64; 2]> for Affine2 {
+    fn act(&self, p: &[f64; 2]) -> [f64; 2] {
+        self.apply(*p)
+    }
+}
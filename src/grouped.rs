@@ -0,0 +1,153 @@
+//! Gaussian summaries split by a key, with one-way analysis of variance between the groups
+//!
+//! `HashMap<K, Gaussian>` is already a monoid, but filling it by hand means remembering to insert an empty
+//! summary first and to merge rather than overwrite; [`GroupedGaussian`] does the bookkeeping.
+
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+use crate::gaussian::Gaussian;
+use crate::special;
+use crate::stats::tests::TestResult;
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// A [`Gaussian`] summary of the values seen under each key
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupedGaussian<K: Eq + Hash>(HashMap<K, Gaussian>);
+
+impl<K: Eq + Hash> Default for GroupedGaussian<K> {
+    fn default() -> Self {
+        GroupedGaussian(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash> From<HashMap<K, Gaussian>> for GroupedGaussian<K> {
+    fn from(groups: HashMap<K, Gaussian>) -> Self {
+        GroupedGaussian(groups)
+    }
+}
+
+impl<K: Eq + Hash> GroupedGaussian<K> {
+    /// Construct from a single value `x` under `key`.
+    pub fn new(key: K, x: f64) -> Self {
+        let mut g = Self::default();
+        g.observe(key, x);
+        g
+    }
+    /// Record one more value `x` under `key`.
+    pub fn observe(&mut self, key: K, x: f64) {
+        *self.0.entry(key).or_default() += x;
+    }
+    /// The summary of the values seen under `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&Gaussian> {
+        self.0.get(key)
+    }
+    /// The number of groups.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether nothing has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The keys and their summaries, in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, K, Gaussian> {
+        self.0.iter()
+    }
+    /// The summary of every value seen, ignoring the keys.
+    pub fn total(&self) -> Gaussian {
+        self.0
+            .values()
+            .fold(Gaussian::default(), |acc, g| Semigroup::op(&acc, g))
+    }
+    /// Unwrap the underlying map.
+    pub fn into_inner(self) -> HashMap<K, Gaussian> {
+        self.0
+    }
+    /// The sum of squared deviations of each group's mean from the overall mean, weighted by group size.
+    fn ss_between(&self) -> f64 {
+        let grand = self.total().mean();
+        self.0
+            .values()
+            .map(|g| g.count() * (g.mean() - grand).powi(2))
+            .sum()
+    }
+    /// The sum of squared deviations of each value from its own group's mean.
+    fn ss_within(&self) -> f64 {
+        self.0
+            .values()
+            .map(|g| g.count() * g.population_variance())
+            .sum()
+    }
+    /// The between-group mean square: the variance of the group means, scaled by group size, on `k − 1`
+    /// degrees of freedom for `k` groups.
+    pub fn between_group_variance(&self) -> f64 {
+        assert!(self.len() > 1, "ANOVA requires at least 2 groups.");
+        self.ss_between() / (self.len() - 1) as f64
+    }
+    /// The within-group mean square: the pooled variance of the groups, on `n − k` degrees of freedom for `n`
+    /// values in `k` groups.
+    pub fn within_group_variance(&self) -> f64 {
+        let dof = self.total().count() - self.len() as f64;
+        assert!(dof > 0.0, "ANOVA requires more values than groups.");
+        self.ss_within() / dof
+    }
+    /// The F statistic, the ratio of the between-group to the within-group variance.
+    pub fn f_statistic(&self) -> f64 {
+        self.between_group_variance() / self.within_group_variance()
+    }
+    /// One-way ANOVA, for the null hypothesis that every group has the same mean; assumes the groups are
+    /// normally distributed with a common variance.
+    pub fn anova(&self) -> TestResult {
+        let statistic = self.f_statistic();
+        let d1 = (self.len() - 1) as f64;
+        let d2 = self.total().count() - self.len() as f64;
+        TestResult {
+            statistic,
+            p_value: special::f_sf(statistic, d1, d2),
+        }
+    }
+}
+
+/// Record the keyed values one at a time.
+impl<K: Eq + Hash> FromIterator<(K, f64)> for GroupedGaussian<K> {
+    fn from_iter<I: IntoIterator<Item = (K, f64)>>(iter: I) -> Self {
+        let mut g = Self::default();
+        g.extend(iter);
+        g
+    }
+}
+
+/// Record the keyed values one at a time.
+impl<K: Eq + Hash> Extend<(K, f64)> for GroupedGaussian<K> {
+    fn extend<I: IntoIterator<Item = (K, f64)>>(&mut self, iter: I) {
+        for (k, x) in iter {
+            self.observe(k, x)
+        }
+    }
+}
+
+/// Merge the summaries key by key.
+impl<K: Eq + Hash + Clone> Semigroup for GroupedGaussian<K> {
+    fn op(x: &Self, y: &Self) -> Self {
+        GroupedGaussian(Semigroup::op(&x.0, &y.0))
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        GroupedGaussian(Semigroup::op_owned(x.0, y.0))
+    }
+}
+
+/// Merge in place.
+impl<K: Eq + Hash + Clone> SemigroupAssign for GroupedGaussian<K> {
+    fn op_assign(&mut self, other: &Self) {
+        self.0.op_assign(&other.0);
+    }
+}
+
+/// No groups.
+impl<K: Eq + Hash + Clone> Monoid for GroupedGaussian<K> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,47 @@
+//! A seeded 64-bit hash shared by the sketches
+//!
+//! Sketches built on separate machines only merge meaningfully if they hash items identically, so rather
+//! than [`DefaultHasher`](std::collections::hash_map::DefaultHasher), whose algorithm may change between
+//! Rust releases, this mixes each word of input with the SplitMix64 finalizer.  Bear in mind that [`Hash`]
+//! impls for integers feed in native-endian bytes.
+
+use std::hash::{Hash, Hasher};
+
+/// The SplitMix64 finalizer: a bijection on `u64` with good avalanche.
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Hash `t` with the hash function numbered `seed`.
+pub(crate) fn hash<T: Hash + ?Sized>(t: &T, seed: u64) -> u64 {
+    let mut h = Mixer {
+        state: mix(seed.wrapping_add(0x9e37_79b9_7f4a_7c15)),
+        len: 0,
+    };
+    t.hash(&mut h);
+    h.finish()
+}
+
+/// Running state of [`hash`]
+struct Mixer {
+    /// Mixed words so far
+    state: u64,
+    /// Bytes written so far, mixed in at the end to tell apart inputs differing only by zero padding
+    len: u64,
+}
+
+impl Hasher for Mixer {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.state = mix(self.state ^ u64::from_le_bytes(word)).rotate_left(23);
+        }
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+    }
+    fn finish(&self) -> u64 {
+        mix(self.state ^ mix(self.len))
+    }
+}
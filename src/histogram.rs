@@ -0,0 +1,457 @@
+//! Histograms that can be built shard by shard and merged
+//!
+//! [`StreamingHistogram`] follows Ben-Haim & Tom-Tov, [A Streaming Parallel Decision Tree
+//! Algorithm](https://www.jmlr.org/papers/volume11/ben-haim10a/ben-haim10a.pdf): rather than fixing bin edges
+//! in advance, it keeps at most `BINS` centroids with counts, and whenever there are too many it fuses the
+//! two closest.  This adapts to whatever range the data turns out to cover, at the cost of merging being only
+//! approximately associative once bins start being fused.
+//!
+//! [`HdrHistogram`] instead fixes its buckets up front, in the manner of Gil Tene's
+//! [HdrHistogram](http://hdrhistogram.org/): exact below a threshold, then logarithmic with linear
+//! sub-buckets, so every bucket is narrow relative to the values it holds.  Since every shard agrees on the
+//! buckets, merging is just adding counts and loses nothing.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// An adaptive histogram of at most `BINS` centroids
+///
+/// Up to `BINS` distinct values are recorded exactly, and merging is then exact too; beyond that, CDF and
+/// quantile estimates interpolate between centroids, treating the extrema as empty bins at either end.  NaN
+/// is ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamingHistogram<const BINS: usize = 64> {
+    /// Centroids and their counts, sorted by centroid, with no centroid repeated
+    bins: Vec<(f64, u64)>,
+    /// Total count
+    count: u64,
+    /// Smallest value seen, or +∞
+    min: f64,
+    /// Largest value seen, or −∞
+    max: f64,
+}
+
+impl<const BINS: usize> Default for StreamingHistogram<BINS> {
+    fn default() -> Self {
+        const { assert!(BINS > 0, "a histogram needs at least one bin") };
+        StreamingHistogram {
+            bins: Vec::with_capacity(BINS + 1),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl<const BINS: usize> StreamingHistogram<BINS> {
+    /// Construct from a single data point.
+    pub fn new(x: f64) -> Self {
+        let mut h = Self::default();
+        h.insert(x);
+        h
+    }
+    /// Record a data point.
+    pub fn insert(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        self.add_bin(x, 1);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.shrink();
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Whether no data has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The smallest value seen, or +∞ if there are none.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+    /// The largest value seen, or −∞ if there are none.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+    /// The centroids and their counts, in increasing order of centroid.
+    pub fn bins(&self) -> &[(f64, u64)] {
+        &self.bins
+    }
+    /// The estimated fraction of the data at or below `x`, or NaN if there is none.
+    pub fn cdf(&self, x: f64) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        if x < self.min {
+            return 0.0;
+        }
+        if x >= self.max {
+            return 1.0;
+        }
+        let knots = self.knots();
+        let i = knots.partition_point(|&(p, _, _)| p <= x) - 1;
+        let ((p0, m0, s0), (p1, m1, _)) = (knots[i], knots[i + 1]);
+        let t = (x - p0) / (p1 - p0);
+        let mx = m0 + (m1 - m0) * t;
+        (s0 + (m0 + mx) / 2.0 * t) / self.count as f64
+    }
+    /// The estimated `q`-quantile, or NaN if there's no data.
+    ///
+    /// Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q), "Quantiles must lie in [0, 1].");
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        let target = q * self.count as f64;
+        let knots = self.knots();
+        let i = knots[1..]
+            .partition_point(|&(_, _, s)| s < target)
+            .min(knots.len() - 2);
+        let ((p0, m0, s0), (p1, m1, _)) = (knots[i], knots[i + 1]);
+        // Solve s0 + m0·t + (m1 − m0)·t²/2 = target for t ∈ [0, 1], in a form that's stable as m1 → m0.
+        let d = (target - s0).max(0.0);
+        let disc = (m0 * m0 + 2.0 * (m1 - m0) * d).max(0.0);
+        let denom = m0 + disc.sqrt();
+        let t = if denom > 0.0 { 2.0 * d / denom } else { 0.0 };
+        (p0 + t.clamp(0.0, 1.0) * (p1 - p0)).clamp(self.min, self.max)
+    }
+    /// The estimated median.
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+    /// Centroids with counts and the estimated number of points below each, padded with the extrema as
+    /// empty bins.  Each bin is assumed to have half its points on either side of its centroid.
+    fn knots(&self) -> Vec<(f64, f64, f64)> {
+        let mut knots = Vec::with_capacity(self.bins.len() + 2);
+        knots.push((self.min, 0.0, 0.0));
+        let mut below = 0.0;
+        for &(p, m) in &self.bins {
+            let m = m as f64;
+            knots.push((p, m, below + m / 2.0));
+            below += m;
+        }
+        knots.push((self.max, 0.0, below));
+        knots
+    }
+    /// Add `m` points at `p`, fusing with an existing centroid at exactly `p`.
+    fn add_bin(&mut self, p: f64, m: u64) {
+        self.count += m;
+        let i = self.bins.partition_point(|&(q, _)| q < p);
+        match self.bins.get_mut(i) {
+            Some((q, n)) if *q == p => *n += m,
+            _ => self.bins.insert(i, (p, m)),
+        }
+    }
+    /// Fuse the closest pair of adjacent centroids until there are at most `BINS`.
+    fn shrink(&mut self) {
+        while self.bins.len() > BINS {
+            let i = (0..self.bins.len() - 1)
+                .min_by(|&i, &j| {
+                    let gap = |k: usize| self.bins[k + 1].0 - self.bins[k].0;
+                    gap(i).total_cmp(&gap(j))
+                })
+                .unwrap();
+            let ((p, m), (q, n)) = (self.bins[i], self.bins.remove(i + 1));
+            let (mf, nf) = (m as f64, n as f64);
+            self.bins[i] = ((p * mf + q * nf) / (mf + nf), m + n);
+        }
+    }
+}
+
+/// We can add a new data point.
+impl<const BINS: usize> Add<f64> for StreamingHistogram<BINS> {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self.insert(x);
+        self
+    }
+}
+
+/// We can add a new data point.
+impl<const BINS: usize> AddAssign<f64> for StreamingHistogram<BINS> {
+    fn add_assign(&mut self, x: f64) {
+        self.insert(x);
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<const BINS: usize> FromIterator<f64> for StreamingHistogram<BINS> {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut h = Self::default();
+        h.extend(iter);
+        h
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a, const BINS: usize> FromIterator<&'a f64> for StreamingHistogram<BINS> {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<const BINS: usize> Extend<f64> for StreamingHistogram<BINS> {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.insert(x)
+        }
+    }
+}
+
+/// Pool the centroids, then fuse the closest until there are at most `BINS`.
+impl<const BINS: usize> Semigroup for StreamingHistogram<BINS> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty histogram.
+impl<const BINS: usize> Monoid for StreamingHistogram<BINS> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Pool the other side's centroids into this one.
+impl<const BINS: usize> SemigroupAssign for StreamingHistogram<BINS> {
+    fn op_assign(&mut self, other: &Self) {
+        for &(p, m) in &other.bins {
+            self.add_bin(p, m);
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.shrink();
+    }
+}
+
+/// Pooling centroids is symmetric, and ties between gaps are broken by position rather than by side.
+impl<const BINS: usize> CommutativeMonoid for StreamingHistogram<BINS> {}
+
+/// A histogram of non-negative integers (latencies, say) in buckets of bounded relative width
+///
+/// Values below `2^(PRECISION + 1)` get a bucket each; beyond that, each power-of-two range is split into
+/// `2^PRECISION` equal sub-buckets, so a value is only ever confused with others within a relative distance
+/// of `2^-PRECISION` of it.  The default of 10 bits resolves about three significant digits.  Storage grows
+/// with the largest value seen, logarithmically in its size.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HdrHistogram<const PRECISION: u32 = 10> {
+    /// Count in each bucket, up to the last non-empty one
+    counts: Vec<u64>,
+    /// Total count
+    count: u64,
+    /// Smallest value seen, or `u64::MAX`
+    min: u64,
+    /// Largest value seen, or zero
+    max: u64,
+}
+
+impl<const PRECISION: u32> Default for HdrHistogram<PRECISION> {
+    fn default() -> Self {
+        const {
+            assert!(
+                PRECISION >= 1 && PRECISION <= 16,
+                "PRECISION must be between 1 and 16 bits"
+            )
+        };
+        HdrHistogram {
+            counts: Vec::new(),
+            count: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+}
+
+impl<const PRECISION: u32> HdrHistogram<PRECISION> {
+    /// The number of sub-buckets each power of two is split into.
+    const SUB_BUCKETS: u64 = 1 << PRECISION;
+
+    /// Construct from a single value.
+    pub fn new(v: u64) -> Self {
+        let mut h = Self::default();
+        h.record(v);
+        h
+    }
+    /// Record a value.
+    pub fn record(&mut self, v: u64) {
+        self.record_n(v, 1);
+    }
+    /// Record `n` copies of a value.
+    pub fn record_n(&mut self, v: u64, n: u64) {
+        if n == 0 {
+            return;
+        }
+        let i = Self::index(v);
+        if i >= self.counts.len() {
+            self.counts.resize(i + 1, 0);
+        }
+        self.counts[i] += n;
+        self.count += n;
+        self.min = self.min.min(v);
+        self.max = self.max.max(v);
+    }
+    /// The number of values recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The smallest value recorded, exactly.
+    pub fn min(&self) -> Option<u64> {
+        (!self.is_empty()).then_some(self.min)
+    }
+    /// The largest value recorded, exactly.
+    pub fn max(&self) -> Option<u64> {
+        (!self.is_empty()).then_some(self.max)
+    }
+    /// The mean, taking each value to be the middle of its bucket.
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| {
+            let total: f64 = self
+                .buckets()
+                .map(|(lo, hi, n)| (lo as f64 + hi as f64) / 2.0 * n as f64)
+                .sum();
+            total / self.count as f64
+        })
+    }
+    /// The smallest value `v` such that at least a fraction `q` of the data lies in buckets at or below `v`'s,
+    /// reported as the top of its bucket (but never beyond the true maximum).
+    ///
+    /// Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        assert!((0.0..=1.0).contains(&q), "Quantiles must lie in [0, 1].");
+        if self.is_empty() {
+            return None;
+        }
+        let rank = ((q * self.count as f64).ceil() as u64).clamp(1, self.count);
+        let mut seen = 0;
+        for (lo, hi, n) in self.buckets() {
+            seen += n;
+            if seen >= rank {
+                return Some(hi.min(self.max).max(lo));
+            }
+        }
+        unreachable!("the counts add up to the total")
+    }
+    /// The `p`th percentile, for `p` in `[0, 100]`.
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        self.quantile(p / 100.0)
+    }
+    /// The non-empty buckets, as inclusive bounds and a count, in increasing order.
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &n)| n > 0)
+            .map(|(i, &n)| {
+                let (lo, hi) = Self::bounds(i);
+                (lo, hi, n)
+            })
+    }
+    /// Which bucket `v` falls in.
+    fn index(v: u64) -> usize {
+        let exact = 2 * Self::SUB_BUCKETS;
+        if v < exact {
+            return v as usize;
+        }
+        // Drop just enough low bits that the rest lies in [SUB_BUCKETS, 2 · SUB_BUCKETS).
+        let shift = (64 - v.leading_zeros()) - (PRECISION + 1);
+        (shift as u64 * Self::SUB_BUCKETS + (v >> shift)) as usize
+    }
+    /// The smallest and largest values falling in bucket `i`.
+    fn bounds(i: usize) -> (u64, u64) {
+        let i = i as u64;
+        if i < 2 * Self::SUB_BUCKETS {
+            return (i, i);
+        }
+        let shift = i / Self::SUB_BUCKETS - 1;
+        let top = i - shift * Self::SUB_BUCKETS;
+        let lo = top << shift;
+        (lo, lo + ((1 << shift) - 1))
+    }
+}
+
+/// We can record a new value.
+impl<const PRECISION: u32> Add<u64> for HdrHistogram<PRECISION> {
+    type Output = Self;
+    fn add(mut self, v: u64) -> Self::Output {
+        self.record(v);
+        self
+    }
+}
+
+/// We can record a new value.
+impl<const PRECISION: u32> AddAssign<u64> for HdrHistogram<PRECISION> {
+    fn add_assign(&mut self, v: u64) {
+        self.record(v);
+    }
+}
+
+/// Record the values one at a time.
+impl<const PRECISION: u32> FromIterator<u64> for HdrHistogram<PRECISION> {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut h = Self::default();
+        h.extend(iter);
+        h
+    }
+}
+
+/// Record the values one at a time.
+impl<'a, const PRECISION: u32> FromIterator<&'a u64> for HdrHistogram<PRECISION> {
+    fn from_iter<I: IntoIterator<Item = &'a u64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Record the values one at a time.
+impl<const PRECISION: u32> Extend<u64> for HdrHistogram<PRECISION> {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for v in iter {
+            self.record(v)
+        }
+    }
+}
+
+/// Add the counts bucket by bucket.
+impl<const PRECISION: u32> Semigroup for HdrHistogram<PRECISION> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty histogram.
+impl<const PRECISION: u32> Monoid for HdrHistogram<PRECISION> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Add the other side's counts in place.
+impl<const PRECISION: u32> SemigroupAssign for HdrHistogram<PRECISION> {
+    fn op_assign(&mut self, other: &Self) {
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (n, m) in self.counts.iter_mut().zip(&other.counts) {
+            *n += m;
+        }
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+/// Counts can be added in any order.
+impl<const PRECISION: u32> CommutativeMonoid for HdrHistogram<PRECISION> {}
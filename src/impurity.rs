@@ -0,0 +1,159 @@
+//! Impurity measures over category counts, as used to choose splits when training decision trees
+//!
+//! Both [`shannon_entropy`](EntropyCounter::shannon_entropy) and [`gini`](EntropyCounter::gini) are
+//! functions of the category frequencies alone, so counting each partition of the data separately and
+//! merging the counts gives exactly the same answer as counting everything at once.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// How often each category has been seen
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntropyCounter<T: Eq + Hash> {
+    /// Number of times each category has been seen; never zero
+    counts: HashMap<T, u64>,
+    /// Sum of the counts
+    total: u64,
+}
+
+impl<T: Eq + Hash> Default for EntropyCounter<T> {
+    fn default() -> Self {
+        EntropyCounter {
+            counts: HashMap::new(),
+            total: 0,
+        }
+    }
+}
+
+impl<T: Eq + Hash> EntropyCounter<T> {
+    /// Construct from a single observation.
+    pub fn new(t: T) -> Self {
+        let mut c = Self::default();
+        c.observe(t);
+        c
+    }
+    /// Record one more observation of `t`.
+    pub fn observe(&mut self, t: T) {
+        *self.counts.entry(t).or_insert(0) += 1;
+        self.total += 1;
+    }
+    /// How many times `t` has been seen.
+    pub fn count(&self, t: &T) -> u64 {
+        self.counts.get(t).copied().unwrap_or(0)
+    }
+    /// The total number of observations.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+    /// The number of distinct categories seen.
+    pub fn categories(&self) -> usize {
+        self.counts.len()
+    }
+    /// The observed frequency of `t`, or `None` if nothing has been seen.
+    pub fn frequency(&self, t: &T) -> Option<f64> {
+        (self.total > 0).then(|| self.count(t) as f64 / self.total as f64)
+    }
+    /// The categories and their counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> + '_ {
+        self.counts.iter().map(|(t, &c)| (t, c))
+    }
+    /// The Shannon entropy `−Σ pᵢ ln pᵢ` of the observed frequencies, in nats.
+    ///
+    /// Zero when every observation falls in one category, and also (by convention) when there are none.
+    pub fn shannon_entropy(&self) -> f64 {
+        let n = self.total as f64;
+        self.counts
+            .values()
+            .map(|&c| {
+                let p = c as f64 / n;
+                -p * p.ln()
+            })
+            .sum::<f64>()
+            .max(0.0)
+    }
+    /// The Gini impurity `1 − Σ pᵢ²` of the observed frequencies: the chance that two observations drawn with
+    /// replacement fall in different categories.
+    ///
+    /// Zero when every observation falls in one category, and also (by convention) when there are none.
+    pub fn gini(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let n = self.total as f64;
+        let sum_sq: f64 = self.counts.values().map(|&c| (c as f64 / n).powi(2)).sum();
+        (1.0 - sum_sq).max(0.0)
+    }
+}
+
+/// We can add a new observation.
+impl<T: Eq + Hash> Add<T> for EntropyCounter<T> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.observe(t);
+        self
+    }
+}
+
+/// We can add a new observation.
+impl<T: Eq + Hash> AddAssign<T> for EntropyCounter<T> {
+    fn add_assign(&mut self, t: T) {
+        self.observe(t);
+    }
+}
+
+/// Count the observations one at a time.
+impl<T: Eq + Hash> FromIterator<T> for EntropyCounter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut c = Self::default();
+        c.extend(iter);
+        c
+    }
+}
+
+/// Count the observations one at a time.
+impl<T: Eq + Hash> Extend<T> for EntropyCounter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.observe(t)
+        }
+    }
+}
+
+/// Add the counts category by category.
+impl<T: Eq + Hash + Clone> Semigroup for EntropyCounter<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        for (t, c) in y.counts {
+            *x.counts.entry(t).or_insert(0) += c;
+        }
+        x.total += y.total;
+        x
+    }
+}
+
+/// Nothing has been seen.
+impl<T: Eq + Hash + Clone> Monoid for EntropyCounter<T> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Add the other side's counts in place.
+impl<T: Eq + Hash + Clone> SemigroupAssign for EntropyCounter<T> {
+    fn op_assign(&mut self, other: &Self) {
+        for (t, &c) in other.counts.iter() {
+            *self.counts.entry(t.clone()).or_insert(0) += c;
+        }
+        self.total += other.total;
+    }
+}
+
+/// Counts can be added in any order.
+impl<T: Eq + Hash + Clone> CommutativeMonoid for EntropyCounter<T> {}
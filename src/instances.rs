@@ -0,0 +1,778 @@
+//! Instances of the core algebraic traits for standard library types
+use std::{
+    borrow::Cow,
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::traits::{
+    Band, BoundedLattice, CommutativeMonoid, Group, Lattice, Monoid, Monus, OrderedMonoid,
+    Semigroup, SemigroupAssign, Semilattice,
+};
+
+/// Strings form a monoid under concatenation.
+impl Semigroup for String {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = String::with_capacity(x.len() + y.len());
+        z.push_str(x);
+        z.push_str(y);
+        z
+    }
+    fn op_owned(mut x: Self, mut y: Self) -> Self {
+        if x.capacity() < x.len() + y.len() && y.capacity() >= x.len() + y.len() {
+            y.insert_str(0, &x);
+            y
+        } else {
+            x.push_str(&y);
+            x
+        }
+    }
+}
+
+/// The empty string is the identity for concatenation.
+impl Monoid for String {
+    fn zero() -> Self {
+        String::new()
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl SemigroupAssign for String {
+    fn op_assign(&mut self, other: &Self) {
+        self.push_str(other);
+    }
+}
+
+/// Vectors form a monoid under concatenation.
+impl<T: Clone> Semigroup for Vec<T> {
+    fn op(xs: &Self, ys: &Self) -> Self {
+        let mut zs = Vec::with_capacity(xs.len() + ys.len());
+        zs.extend_from_slice(xs);
+        zs.extend_from_slice(ys);
+        zs
+    }
+    fn op_owned(mut xs: Self, mut ys: Self) -> Self {
+        if xs.capacity() < xs.len() + ys.len() && ys.capacity() >= xs.len() + ys.len() {
+            ys.splice(0..0, xs);
+            ys
+        } else {
+            xs.append(&mut ys);
+            xs
+        }
+    }
+}
+
+/// The empty vector is the identity for concatenation.
+impl<T: Clone> Monoid for Vec<T> {
+    fn zero() -> Self {
+        Vec::new()
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl<T: Clone> SemigroupAssign for Vec<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.extend_from_slice(other);
+    }
+}
+
+/// Double-ended queues form a monoid under concatenation.
+impl<T: Clone> Semigroup for VecDeque<T> {
+    fn op(xs: &Self, ys: &Self) -> Self {
+        let mut zs = VecDeque::with_capacity(xs.len() + ys.len());
+        zs.extend(xs.iter().cloned());
+        zs.extend(ys.iter().cloned());
+        zs
+    }
+    fn op_owned(mut xs: Self, mut ys: Self) -> Self {
+        xs.append(&mut ys);
+        xs
+    }
+}
+
+/// The empty queue is the identity for concatenation.
+impl<T: Clone> Monoid for VecDeque<T> {
+    fn zero() -> Self {
+        VecDeque::new()
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl<T: Clone> SemigroupAssign for VecDeque<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.extend(other.iter().cloned());
+    }
+}
+
+/// Linked lists form a monoid under concatenation.
+impl<T: Clone> Semigroup for LinkedList<T> {
+    fn op(xs: &Self, ys: &Self) -> Self {
+        xs.iter().chain(ys.iter()).cloned().collect()
+    }
+    /// Splicing owned lists together is O(1).
+    fn op_owned(mut xs: Self, mut ys: Self) -> Self {
+        xs.append(&mut ys);
+        xs
+    }
+}
+
+/// The empty list is the identity for concatenation.
+impl<T: Clone> Monoid for LinkedList<T> {
+    fn zero() -> Self {
+        LinkedList::new()
+    }
+}
+
+/// Appending in place only clones the right list.
+impl<T: Clone> SemigroupAssign for LinkedList<T> {
+    fn op_assign(&mut self, other: &Self) {
+        self.extend(other.iter().cloned());
+    }
+}
+
+macro_rules! tuple_instances {
+    ($($t:ident $i:tt),+) => {
+        /// The direct product of semigroups is a semigroup.
+        impl<$($t: Semigroup),+> Semigroup for ($($t,)+) {
+            const IDEMPOTENT: bool = $($t::IDEMPOTENT &&)+ true;
+            fn op(x: &Self, y: &Self) -> Self {
+                ($($t::op(&x.$i, &y.$i),)+)
+            }
+            fn op_owned(x: Self, y: Self) -> Self {
+                ($($t::op_owned(x.$i, y.$i),)+)
+            }
+        }
+
+        /// The direct product of monoids is a monoid.
+        impl<$($t: Monoid),+> Monoid for ($($t,)+) {
+            fn zero() -> Self {
+                ($($t::zero(),)+)
+            }
+        }
+
+        /// Products combine in place componentwise.
+        impl<$($t: SemigroupAssign),+> SemigroupAssign for ($($t,)+) {
+            fn op_assign(&mut self, other: &Self) {
+                $(self.$i.op_assign(&other.$i);)+
+            }
+        }
+
+        /// The direct product of commutative monoids is commutative.
+        impl<$($t: CommutativeMonoid),+> CommutativeMonoid for ($($t,)+) {}
+
+        /// The direct product of bands is a band.
+        impl<$($t: Band),+> Band for ($($t,)+) {}
+
+        /// The direct product of semilattices is a semilattice.
+        impl<$($t: Semilattice),+> Semilattice for ($($t,)+) {}
+
+        /// The direct product of groups is a group.
+        impl<$($t: Group),+> Group for ($($t,)+) {
+            fn invert(&self) -> Self {
+                ($(self.$i.invert(),)+)
+            }
+        }
+    };
+}
+
+tuple_instances!(A 0);
+tuple_instances!(A 0, B 1);
+tuple_instances!(A 0, B 1, C 2);
+tuple_instances!(A 0, B 1, C 2, D 3);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_instances!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Fixed-size arrays of semigroups are semigroups, element-wise.
+impl<M: Semigroup, const N: usize> Semigroup for [M; N] {
+    const IDEMPOTENT: bool = M::IDEMPOTENT;
+    fn op(x: &Self, y: &Self) -> Self {
+        std::array::from_fn(|i| M::op(&x[i], &y[i]))
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        let mut ys = y.into_iter();
+        x.map(|a| M::op_owned(a, ys.next().expect("arrays have the same length")))
+    }
+}
+
+/// Fixed-size arrays of monoids are monoids, element-wise.
+impl<M: Monoid, const N: usize> Monoid for [M; N] {
+    fn zero() -> Self {
+        std::array::from_fn(|_| M::zero())
+    }
+}
+
+/// Combine each element in place.
+impl<M: SemigroupAssign, const N: usize> SemigroupAssign for [M; N] {
+    fn op_assign(&mut self, other: &Self) {
+        for (a, b) in self.iter_mut().zip(other.iter()) {
+            a.op_assign(b);
+        }
+    }
+}
+
+/// Element-wise commutativity.
+impl<M: CommutativeMonoid, const N: usize> CommutativeMonoid for [M; N] {}
+
+/// Element-wise idempotency.
+impl<M: Band, const N: usize> Band for [M; N] {}
+
+/// Element-wise idempotency and commutativity.
+impl<M: Semilattice, const N: usize> Semilattice for [M; N] {}
+
+/// Element-wise inverses.
+impl<M: Group, const N: usize> Group for [M; N] {
+    fn invert(&self) -> Self {
+        std::array::from_fn(|i| self[i].invert())
+    }
+}
+
+/// A Semigroup can be made into a monoid by adjoining a new identity element.
+impl<T: Semigroup + Clone> Semigroup for Option<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        match (x, y) {
+            (Some(a), Some(b)) => Some(T::op(a, b)),
+            (None, _) => y.clone(),
+            (_, None) => x.clone(),
+        }
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        match (x, y) {
+            (Some(a), Some(b)) => Some(T::op_owned(a, b)),
+            (None, y) => y,
+            (x, None) => x,
+        }
+    }
+}
+
+/// A Semigroup can be made into a monoid by adjoining a new identity element.
+impl<T: Semigroup + Clone> Monoid for Option<T> {
+    fn zero() -> Self {
+        None
+    }
+}
+
+/// Only the right operand needs cloning when combining in place.
+impl<T: SemigroupAssign + Clone> SemigroupAssign for Option<T> {
+    fn op_assign(&mut self, other: &Self) {
+        match (self.as_mut(), other) {
+            (Some(a), Some(b)) => a.op_assign(b),
+            (None, _) => *self = other.clone(),
+            (_, None) => {}
+        }
+    }
+}
+
+/// A map of {key ↦ value} is a semigroup if the values form one.
+impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Semigroup for HashMap<K, V> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut h = HashMap::new();
+        for (k, v) in x.iter().chain(y.iter()) {
+            h.entry((*k).clone())
+                .and_modify(|w| *w = V::op(w, v))
+                .or_insert_with(|| v.clone());
+        }
+        h
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        for (k, v) in y {
+            let w = match x.remove(&k) {
+                Some(w) => V::op_owned(w, v),
+                None => v,
+            };
+            x.insert(k, w);
+        }
+        x
+    }
+}
+
+/// A map of {key ↦ value} is a monoid if the values form a semigroup.
+impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Monoid for HashMap<K, V> {
+    fn zero() -> Self {
+        HashMap::new()
+    }
+}
+
+/// Merge the right map's entries into the left one.
+impl<K: Clone + Eq + Hash, V: SemigroupAssign + Clone> SemigroupAssign for HashMap<K, V> {
+    fn op_assign(&mut self, other: &Self) {
+        for (k, v) in other.iter() {
+            self.entry(k.clone())
+                .and_modify(|w| w.op_assign(v))
+                .or_insert_with(|| v.clone());
+        }
+    }
+}
+
+/// An ordered map of {key ↦ value} is a semigroup if the values form one.
+impl<K: Clone + Ord, V: Semigroup + Clone> Semigroup for BTreeMap<K, V> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut h = x.clone();
+        for (k, v) in y.iter() {
+            h.entry(k.clone())
+                .and_modify(|w| *w = V::op(w, v))
+                .or_insert_with(|| v.clone());
+        }
+        h
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        for (k, v) in y {
+            let w = match x.remove(&k) {
+                Some(w) => V::op_owned(w, v),
+                None => v,
+            };
+            x.insert(k, w);
+        }
+        x
+    }
+}
+
+/// An ordered map of {key ↦ value} is a monoid if the values form a semigroup.
+impl<K: Clone + Ord, V: Semigroup + Clone> Monoid for BTreeMap<K, V> {
+    fn zero() -> Self {
+        BTreeMap::new()
+    }
+}
+
+/// Merge the right map's entries into the left one.
+impl<K: Clone + Ord, V: SemigroupAssign + Clone> SemigroupAssign for BTreeMap<K, V> {
+    fn op_assign(&mut self, other: &Self) {
+        for (k, v) in other.iter() {
+            self.entry(k.clone())
+                .and_modify(|w| w.op_assign(v))
+                .or_insert_with(|| v.clone());
+        }
+    }
+}
+
+macro_rules! set_instances {
+    ($set:ident, $($bound:path),+) => {
+        /// Sets form a semigroup under union.
+        impl<T: Clone $(+ $bound)+> Semigroup for $set<T> {
+            const IDEMPOTENT: bool = true;
+            fn op(x: &Self, y: &Self) -> Self {
+                x.union(y).cloned().collect()
+            }
+            fn op_owned(x: Self, y: Self) -> Self {
+                let (mut big, small) = if x.len() >= y.len() { (x, y) } else { (y, x) };
+                big.extend(small);
+                big
+            }
+        }
+
+        /// The empty set is the identity for union.
+        impl<T: Clone $(+ $bound)+> Monoid for $set<T> {
+            fn zero() -> Self {
+                $set::new()
+            }
+        }
+
+        /// Insert the right set's elements into the left one.
+        impl<T: Clone $(+ $bound)+> SemigroupAssign for $set<T> {
+            fn op_assign(&mut self, other: &Self) {
+                self.extend(other.iter().cloned());
+            }
+        }
+
+        /// Union is commutative.
+        impl<T: Clone $(+ $bound)+> CommutativeMonoid for $set<T> {}
+
+        /// Union is idempotent.
+        impl<T: Clone $(+ $bound)+> Band for $set<T> {}
+
+        /// Union is the join of the subset lattice.
+        impl<T: Clone $(+ $bound)+> Semilattice for $set<T> {}
+
+        /// Set difference truncates at the empty set.
+        impl<T: Clone $(+ $bound)+> Monus for $set<T> {
+            fn monus(x: &Self, y: &Self) -> Self {
+                x.difference(y).cloned().collect()
+            }
+        }
+
+        /// Subsets form a lattice under union and intersection.
+        impl<T: Clone $(+ $bound)+> Lattice for $set<T> {
+            fn join(x: &Self, y: &Self) -> Self {
+                x.union(y).cloned().collect()
+            }
+            fn meet(x: &Self, y: &Self) -> Self {
+                x.intersection(y).cloned().collect()
+            }
+        }
+    };
+}
+
+set_instances!(HashSet, Eq, Hash);
+set_instances!(BTreeSet, Ord);
+
+/// Booleans form the two-element lattice under `||` and `&&`.
+impl Lattice for bool {
+    fn join(x: &Self, y: &Self) -> Self {
+        *x || *y
+    }
+    fn meet(x: &Self, y: &Self) -> Self {
+        *x && *y
+    }
+}
+
+/// `true` is above everything and `false` below.
+impl BoundedLattice for bool {
+    fn top() -> Self {
+        true
+    }
+    fn bottom() -> Self {
+        false
+    }
+}
+
+/// Lexicographic comparison: the first non-`Equal` result wins.
+impl Semigroup for Ordering {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        x.then(*y)
+    }
+}
+
+/// `Equal` defers to whatever comes next.
+impl Monoid for Ordering {
+    fn zero() -> Self {
+        Ordering::Equal
+    }
+}
+
+/// Chain in place.
+impl SemigroupAssign for Ordering {}
+
+/// Repeating a comparison changes nothing.
+impl Band for Ordering {}
+
+/// Durations add, saturating at [`Duration::MAX`].
+impl Semigroup for Duration {
+    fn op(x: &Self, y: &Self) -> Self {
+        x.saturating_add(*y)
+    }
+}
+
+/// No time at all.
+impl Monoid for Duration {
+    fn zero() -> Self {
+        Duration::ZERO
+    }
+}
+
+/// Add in place.
+impl SemigroupAssign for Duration {}
+
+/// Durations can be added in any order.
+impl CommutativeMonoid for Duration {}
+
+/// Longer inputs give longer totals.
+impl OrderedMonoid for Duration {}
+
+/// Truncated subtraction.
+impl Monus for Duration {
+    fn monus(x: &Self, y: &Self) -> Self {
+        x.saturating_sub(*y)
+    }
+}
+
+/// The trivial semigroup.
+impl Semigroup for () {
+    const IDEMPOTENT: bool = true;
+    fn op(_: &Self, _: &Self) -> Self {}
+}
+
+/// The trivial monoid.
+impl Monoid for () {
+    fn zero() -> Self {}
+}
+
+/// Nothing to do in place.
+impl SemigroupAssign for () {
+    fn op_assign(&mut self, _: &Self) {}
+}
+
+/// The trivial monoid is commutative.
+impl CommutativeMonoid for () {}
+
+/// The trivial monoid is idempotent.
+impl Band for () {}
+
+/// The trivial monoid is a (one-point) lattice.
+impl Semilattice for () {}
+
+/// The trivial group.
+impl Group for () {
+    fn invert(&self) -> Self {}
+}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> Semigroup for PhantomData<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(_: &Self, _: &Self) -> Self {
+        PhantomData
+    }
+}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> Monoid for PhantomData<T> {
+    fn zero() -> Self {
+        PhantomData
+    }
+}
+
+/// Nothing to do in place.
+impl<T> SemigroupAssign for PhantomData<T> {
+    fn op_assign(&mut self, _: &Self) {}
+}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> CommutativeMonoid for PhantomData<T> {}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> Band for PhantomData<T> {}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> Semilattice for PhantomData<T> {}
+
+/// A type-level marker is as trivial as `()`.
+impl<T> Group for PhantomData<T> {
+    fn invert(&self) -> Self {
+        PhantomData
+    }
+}
+
+/// Reversing the order doesn't change the operation.
+impl<T: Semigroup> Semigroup for Reverse<T> {
+    const IDEMPOTENT: bool = T::IDEMPOTENT;
+    fn op(Reverse(x): &Self, Reverse(y): &Self) -> Self {
+        Reverse(T::op(x, y))
+    }
+    fn op_owned(Reverse(x): Self, Reverse(y): Self) -> Self {
+        Reverse(T::op_owned(x, y))
+    }
+}
+
+/// Reversing the order doesn't change the identity.
+impl<T: Monoid> Monoid for Reverse<T> {
+    fn zero() -> Self {
+        Reverse(T::zero())
+    }
+}
+
+/// Delegate to the wrapped value.
+impl<T: SemigroupAssign> SemigroupAssign for Reverse<T> {
+    fn op_assign(&mut self, Reverse(other): &Self) {
+        self.0.op_assign(other);
+    }
+}
+
+/// Reversing the order doesn't change the operation.
+impl<T: CommutativeMonoid> CommutativeMonoid for Reverse<T> {}
+
+/// Reversing the order doesn't change the operation.
+impl<T: Band> Band for Reverse<T> {}
+
+/// Reversing the order doesn't change the operation.
+impl<T: Semilattice> Semilattice for Reverse<T> {}
+
+/// Reversing the order doesn't change the inverses.
+impl<T: Group> Group for Reverse<T> {
+    fn invert(&self) -> Self {
+        Reverse(self.0.invert())
+    }
+}
+
+/// Monotonicity is symmetric, so it survives reversing the order.
+impl<T: OrderedMonoid> OrderedMonoid for Reverse<T> {}
+
+/// Combine what's in the boxes.
+impl<T: Semigroup> Semigroup for Box<T> {
+    const IDEMPOTENT: bool = T::IDEMPOTENT;
+    fn op(x: &Self, y: &Self) -> Self {
+        Box::new(T::op(x, y))
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        Box::new(T::op_owned(*x, *y))
+    }
+}
+
+/// Box up the identity.
+impl<T: Monoid> Monoid for Box<T> {
+    fn zero() -> Self {
+        Box::new(T::zero())
+    }
+}
+
+/// Combine in place, reusing the allocation.
+impl<T: SemigroupAssign> SemigroupAssign for Box<T> {
+    fn op_assign(&mut self, other: &Self) {
+        (**self).op_assign(other);
+    }
+}
+
+/// Boxing doesn't change the operation.
+impl<T: CommutativeMonoid> CommutativeMonoid for Box<T> {}
+
+/// Boxing doesn't change the operation.
+impl<T: Band> Band for Box<T> {}
+
+/// Boxing doesn't change the operation.
+impl<T: Semilattice> Semilattice for Box<T> {}
+
+/// Box up the inverse.
+impl<T: Group> Group for Box<T> {
+    fn invert(&self) -> Self {
+        Box::new((**self).invert())
+    }
+}
+
+macro_rules! shared_pointer_instances {
+    ($ptr:ident) => {
+        /// Combine the shared values into a fresh allocation.
+        impl<T: Semigroup + Clone> Semigroup for $ptr<T> {
+            const IDEMPOTENT: bool = T::IDEMPOTENT;
+            fn op(x: &Self, y: &Self) -> Self {
+                $ptr::new(T::op(x, y))
+            }
+            fn op_owned(x: Self, y: Self) -> Self {
+                $ptr::new(T::op_owned(
+                    $ptr::unwrap_or_clone(x),
+                    $ptr::unwrap_or_clone(y),
+                ))
+            }
+        }
+
+        /// Share the identity.
+        impl<T: Monoid + Clone> Monoid for $ptr<T> {
+            fn zero() -> Self {
+                $ptr::new(T::zero())
+            }
+        }
+
+        /// Combine in place, cloning only if the value is shared.
+        impl<T: SemigroupAssign + Clone> SemigroupAssign for $ptr<T> {
+            fn op_assign(&mut self, other: &Self) {
+                $ptr::make_mut(self).op_assign(other);
+            }
+        }
+
+        /// Sharing doesn't change the operation.
+        impl<T: CommutativeMonoid + Clone> CommutativeMonoid for $ptr<T> {}
+
+        /// Sharing doesn't change the operation.
+        impl<T: Band + Clone> Band for $ptr<T> {}
+
+        /// Sharing doesn't change the operation.
+        impl<T: Semilattice + Clone> Semilattice for $ptr<T> {}
+
+        /// Share the inverse.
+        impl<T: Group + Clone> Group for $ptr<T> {
+            fn invert(&self) -> Self {
+                $ptr::new((**self).invert())
+            }
+        }
+    };
+}
+
+shared_pointer_instances!(Rc);
+shared_pointer_instances!(Arc);
+
+/// Concatenation, which only allocates when both sides are non-empty.
+impl<'a> Semigroup for Cow<'a, str> {
+    fn op(x: &Self, y: &Self) -> Self {
+        if y.is_empty() {
+            x.clone()
+        } else if x.is_empty() {
+            y.clone()
+        } else {
+            let mut z = String::with_capacity(x.len() + y.len());
+            z.push_str(x);
+            z.push_str(y);
+            Cow::Owned(z)
+        }
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.op_assign(&y);
+        x
+    }
+}
+
+/// The empty string, borrowed.
+impl<'a> Monoid for Cow<'a, str> {
+    fn zero() -> Self {
+        Cow::Borrowed("")
+    }
+}
+
+/// Append in place, taking ownership only when there's something to append to.
+impl<'a> SemigroupAssign for Cow<'a, str> {
+    fn op_assign(&mut self, other: &Self) {
+        if self.is_empty() {
+            *self = other.clone();
+        } else if !other.is_empty() {
+            self.to_mut().push_str(other);
+        }
+    }
+}
+
+/// Concatenation, which only allocates when both sides are non-empty.
+impl<'a, T: Clone> Semigroup for Cow<'a, [T]> {
+    fn op(x: &Self, y: &Self) -> Self {
+        if y.is_empty() {
+            x.clone()
+        } else if x.is_empty() {
+            y.clone()
+        } else {
+            let mut zs = Vec::with_capacity(x.len() + y.len());
+            zs.extend_from_slice(x);
+            zs.extend_from_slice(y);
+            Cow::Owned(zs)
+        }
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.op_assign(&y);
+        x
+    }
+}
+
+/// The empty slice, borrowed.
+impl<'a, T: Clone> Monoid for Cow<'a, [T]> {
+    fn zero() -> Self {
+        Cow::Borrowed(&[])
+    }
+}
+
+/// Append in place, taking ownership only when there's something to append to.
+impl<'a, T: Clone> SemigroupAssign for Cow<'a, [T]> {
+    fn op_assign(&mut self, other: &Self) {
+        if self.is_empty() {
+            *self = other.clone();
+        } else if !other.is_empty() {
+            self.to_mut().extend_from_slice(other);
+        }
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+mod array_vec;
+#[cfg(feature = "num-complex")]
+mod complex;
+#[cfg(feature = "indexmap")]
+mod index_map;
+#[cfg(feature = "nalgebra")]
+mod matrix;
+#[cfg(feature = "num-rational")]
+mod rational;
+#[cfg(feature = "smallvec")]
+mod small_vec;
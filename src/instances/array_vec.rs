@@ -0,0 +1,36 @@
+//! Instances for [`arrayvec::ArrayVec`]
+use arrayvec::ArrayVec;
+
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// Concatenation, keeping only the first `CAP` elements.
+///
+/// Truncating a concatenation is still associative, so this is lawful; elements past the capacity are
+/// dropped rather than panicking.
+impl<T: Clone, const CAP: usize> Semigroup for ArrayVec<T, CAP> {
+    fn op(xs: &Self, ys: &Self) -> Self {
+        let mut zs = xs.clone();
+        zs.op_assign(ys);
+        zs
+    }
+    fn op_owned(mut xs: Self, ys: Self) -> Self {
+        let room = xs.remaining_capacity();
+        xs.extend(ys.into_iter().take(room));
+        xs
+    }
+}
+
+/// The empty vector is the identity for concatenation.
+impl<T: Clone, const CAP: usize> Monoid for ArrayVec<T, CAP> {
+    fn zero() -> Self {
+        ArrayVec::new()
+    }
+}
+
+/// Append in place, up to the capacity.
+impl<T: Clone, const CAP: usize> SemigroupAssign for ArrayVec<T, CAP> {
+    fn op_assign(&mut self, other: &Self) {
+        let room = self.remaining_capacity();
+        self.extend(other.iter().take(room).cloned());
+    }
+}
@@ -0,0 +1,63 @@
+//! Instances for [`num_complex::Complex`]
+use num_complex::Complex;
+use num_traits::Num;
+use std::ops::Neg;
+
+use crate::traits::{
+    CommutativeMonoid, CommutativeSemiring, Field, Group, Monoid, Ring, Semigroup, SemigroupAssign,
+    Semiring,
+};
+
+/// Complex numbers under addition.
+impl<T: Clone + Num> Semigroup for Complex<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        x + y
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x + y
+    }
+}
+
+/// Zero is the additive identity.
+impl<T: Clone + Num> Monoid for Complex<T> {
+    fn zero() -> Self {
+        Complex::new(T::zero(), T::zero())
+    }
+}
+
+/// Add in place.
+impl<T: Clone + Num> SemigroupAssign for Complex<T> {}
+
+/// Addition is commutative.
+impl<T: Clone + Num> CommutativeMonoid for Complex<T> {}
+
+/// Negation gives additive inverses.
+impl<T: Clone + Num + Neg<Output = T>> Group for Complex<T> {
+    fn invert(&self) -> Self {
+        -self
+    }
+}
+
+/// Complex multiplication.
+impl<T: Clone + Num> Semiring for Complex<T> {
+    fn mul(x: &Self, y: &Self) -> Self {
+        x * y
+    }
+    fn one() -> Self {
+        Complex::new(T::one(), T::zero())
+    }
+}
+
+/// Complex multiplication is commutative.
+impl<T: Clone + Num> CommutativeSemiring for Complex<T> {}
+
+/// Complex numbers over a ring form a ring.
+impl<T: Clone + Num + Neg<Output = T>> Ring for Complex<T> {}
+
+/// Complex numbers over a field form a field; over the integers they're only a ring, since e.g. 2 has no
+/// inverse among the Gaussian integers.
+impl<T: Field + Clone + Num + Neg<Output = T>> Field for Complex<T> {
+    fn reciprocal(&self) -> Self {
+        self.inv()
+    }
+}
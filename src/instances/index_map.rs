@@ -0,0 +1,48 @@
+//! Instances for [`indexmap::IndexMap`]
+use std::hash::Hash;
+
+use indexmap::{map::Entry, IndexMap};
+
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// A map of {key ↦ value} is a semigroup if the values form one; keys keep their first-seen order.
+impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Semigroup for IndexMap<K, V> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut h = x.clone();
+        h.op_assign(y);
+        h
+    }
+    fn op_owned(mut x: Self, y: Self) -> Self {
+        x.reserve(y.len());
+        for (k, v) in y {
+            match x.entry(k) {
+                Entry::Occupied(mut e) => {
+                    let w = e.get_mut();
+                    *w = V::op(w, &v);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(v);
+                }
+            }
+        }
+        x
+    }
+}
+
+/// A map of {key ↦ value} is a monoid if the values form a semigroup.
+impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Monoid for IndexMap<K, V> {
+    fn zero() -> Self {
+        IndexMap::new()
+    }
+}
+
+/// Merge the right map's entries into the left one, appending new keys at the end.
+impl<K: Clone + Eq + Hash, V: Semigroup + Clone> SemigroupAssign for IndexMap<K, V> {
+    fn op_assign(&mut self, other: &Self) {
+        for (k, v) in other.iter() {
+            self.entry(k.clone())
+                .and_modify(|w| *w = V::op(w, v))
+                .or_insert_with(|| v.clone());
+        }
+    }
+}
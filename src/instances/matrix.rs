@@ -0,0 +1,76 @@
+//! Instances for fixed-size [`nalgebra`] matrices
+//!
+//! Matrices add to form a commutative group, and square ones multiply to form a (noncommutative) ring.  For
+//! fast matrix exponentiation, wrap in [`Product`](crate::newtypes::Product) and use
+//! [`power_monoid`](crate::traits::power_monoid).
+use std::ops::Neg;
+
+use nalgebra::{ClosedAddAssign, ClosedMulAssign, SMatrix, Scalar};
+use num_traits::{One, Zero};
+
+use crate::traits::{CommutativeMonoid, Group, Monoid, Ring, Semigroup, SemigroupAssign, Semiring};
+
+/// Matrices under element-wise addition.
+impl<T: Scalar + Zero + ClosedAddAssign, const R: usize, const C: usize> Semigroup
+    for SMatrix<T, R, C>
+{
+    fn op(x: &Self, y: &Self) -> Self {
+        x + y
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x + y
+    }
+}
+
+/// The zero matrix.
+impl<T: Scalar + Zero + ClosedAddAssign, const R: usize, const C: usize> Monoid
+    for SMatrix<T, R, C>
+{
+    fn zero() -> Self {
+        SMatrix::zeros()
+    }
+}
+
+/// Add in place.
+impl<T: Scalar + Zero + ClosedAddAssign, const R: usize, const C: usize> SemigroupAssign
+    for SMatrix<T, R, C>
+{
+    fn op_assign(&mut self, other: &Self) {
+        *self += other;
+    }
+}
+
+/// Matrix addition is commutative.
+impl<T: Scalar + Zero + ClosedAddAssign, const R: usize, const C: usize> CommutativeMonoid
+    for SMatrix<T, R, C>
+{
+}
+
+/// Negate every entry.
+impl<T: Scalar + Zero + ClosedAddAssign + Neg<Output = T>, const R: usize, const C: usize> Group
+    for SMatrix<T, R, C>
+{
+    fn invert(&self) -> Self {
+        -self
+    }
+}
+
+/// Square matrices under matrix multiplication, with the identity matrix as one.
+impl<T: Scalar + Zero + One + ClosedAddAssign + ClosedMulAssign, const N: usize> Semiring
+    for SMatrix<T, N, N>
+{
+    fn mul(x: &Self, y: &Self) -> Self {
+        x * y
+    }
+    fn one() -> Self {
+        SMatrix::identity()
+    }
+}
+
+/// Square matrices over a ring form a ring.
+impl<
+        T: Scalar + Zero + One + ClosedAddAssign + ClosedMulAssign + Neg<Output = T>,
+        const N: usize,
+    > Ring for SMatrix<T, N, N>
+{
+}
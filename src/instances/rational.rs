@@ -0,0 +1,62 @@
+//! Instances for [`num_rational::Ratio`]
+use num_integer::Integer;
+use num_rational::Ratio;
+use std::ops::Neg;
+
+use crate::traits::{
+    CommutativeMonoid, CommutativeSemiring, Field, Group, Monoid, Ring, Semigroup, SemigroupAssign,
+    Semiring,
+};
+
+/// Exact fractions under addition.
+impl<T: Clone + Integer> Semigroup for Ratio<T> {
+    fn op(x: &Self, y: &Self) -> Self {
+        x + y
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x + y
+    }
+}
+
+/// Zero is the additive identity.
+impl<T: Clone + Integer> Monoid for Ratio<T> {
+    fn zero() -> Self {
+        Ratio::from_integer(T::zero())
+    }
+}
+
+/// Add in place.
+impl<T: Clone + Integer> SemigroupAssign for Ratio<T> {}
+
+/// Addition is commutative.
+impl<T: Clone + Integer> CommutativeMonoid for Ratio<T> {}
+
+/// Negation gives additive inverses.
+impl<T: Clone + Integer + Neg<Output = T>> Group for Ratio<T> {
+    fn invert(&self) -> Self {
+        -self
+    }
+}
+
+/// Multiplication of fractions.
+impl<T: Clone + Integer> Semiring for Ratio<T> {
+    fn mul(x: &Self, y: &Self) -> Self {
+        x * y
+    }
+    fn one() -> Self {
+        Ratio::from_integer(T::one())
+    }
+}
+
+/// Multiplication is commutative.
+impl<T: Clone + Integer> CommutativeSemiring for Ratio<T> {}
+
+/// Signed fractions form a ring.
+impl<T: Clone + Integer + Neg<Output = T>> Ring for Ratio<T> {}
+
+/// Signed fractions form a field, the rationals.
+impl<T: Clone + Integer + Neg<Output = T>> Field for Ratio<T> {
+    fn reciprocal(&self) -> Self {
+        self.recip()
+    }
+}
@@ -0,0 +1,41 @@
+//! Instances for [`smallvec::SmallVec`]
+use smallvec::{Array, SmallVec};
+
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// Small vectors form a monoid under concatenation, spilling to the heap only when they outgrow `A`.
+impl<A: Array> Semigroup for SmallVec<A>
+where
+    A::Item: Clone,
+{
+    fn op(xs: &Self, ys: &Self) -> Self {
+        let mut zs = SmallVec::with_capacity(xs.len() + ys.len());
+        zs.extend(xs.iter().cloned());
+        zs.extend(ys.iter().cloned());
+        zs
+    }
+    fn op_owned(mut xs: Self, ys: Self) -> Self {
+        xs.extend(ys);
+        xs
+    }
+}
+
+/// The empty vector is the identity for concatenation.
+impl<A: Array> Monoid for SmallVec<A>
+where
+    A::Item: Clone,
+{
+    fn zero() -> Self {
+        SmallVec::new()
+    }
+}
+
+/// Appending in place reuses the existing buffer.
+impl<A: Array> SemigroupAssign for SmallVec<A>
+where
+    A::Item: Clone,
+{
+    fn op_assign(&mut self, other: &Self) {
+        self.extend(other.iter().cloned());
+    }
+}
@@ -1,8 +1,43 @@
 #![forbid(missing_docs)]
 #![forbid(unsafe_code)]
 //! Algebraic Structure ⇒ Computational Benefits
+pub mod anomaly;
+pub mod bivariate;
+pub mod cardinality;
+pub mod categorical;
+pub mod collections;
+pub mod combinators;
+pub mod compensated;
+pub mod decayed;
+pub mod distribution;
+pub mod exponential;
+pub mod free;
+pub mod frequency;
+pub mod gamma;
 pub mod gaussian;
+pub mod geometry;
+pub mod grouped;
+mod hashing;
+pub mod histogram;
+pub mod impurity;
+pub mod instances;
+pub mod logspace;
+pub mod means;
+pub mod modular;
+pub mod moments;
+pub mod multivariate;
+pub mod naive_bayes;
+pub mod newtypes;
+pub mod poisson;
+pub mod polynomial;
+pub mod proportion;
+pub mod quantiles;
+pub mod similarity;
+mod special;
+pub mod stats;
+pub mod summary;
 pub mod traits;
+pub mod windowed;
 
 #[cfg(test)]
 mod properties;
@@ -21,198 +56,4105 @@ mod tests {
         use rand::thread_rng;
         use rayon::prelude::*;
 
-        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
         pub struct Max(pub f64);
         impl Semigroup for Max {
+            const IDEMPOTENT: bool = true;
             fn op(&Max(x): &Self, &Max(y): &Self) -> Self {
                 Max(x.max(y))
             }
         }
-        impl Monoid for Max {
-            fn zero() -> Self {
-                Max(f64::NEG_INFINITY)
+        impl Monoid for Max {
+            fn zero() -> Self {
+                Max(f64::NEG_INFINITY)
+            }
+        }
+        impl CommutativeMonoid for Max {}
+        impl SemigroupAssign for Max {}
+        impl Monus for Max {
+            fn monus(&Max(x): &Self, &Max(y): &Self) -> Self {
+                if x <= y {
+                    Monoid::zero()
+                } else {
+                    Max(x)
+                }
+            }
+        }
+
+        mod monus {
+            use super::*;
+            monus_properties!(|| any::<f64>().prop_map(Max));
+        }
+
+        impl OrderedMonoid for Max {}
+
+        mod ordered {
+            use super::*;
+            ordered_monoid_properties!(|| any::<f64>().prop_map(Max));
+        }
+        impl Band for Max {}
+        impl Semilattice for Max {}
+
+        semilattice_properties!(|| any::<f64>().prop_map(Max));
+
+        proptest! {
+            #[test]
+            fn powers_are_trivial(x in any::<f64>(), n in any::<u64>()) {
+                let expected = if n == 0 { Monoid::zero() } else { Max(x) };
+                prop_assert_eq!(power_monoid(Max(x), n), expected);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn map_shuffle_reduce_sort_of(xs in vec(any::<f64>(), 0..1000)) {
+                let map_reduce = xs.iter().cloned().map(Max).fold(Monoid::zero(), |x, y| Semigroup::op(&x, &y));
+                let map_shuffle_reduce = {
+                    let mut ys = xs.into_iter().map(Max).collect::<Vec<_>>();
+                    ys.shuffle(&mut thread_rng());
+                    ys
+                }.par_chunks(4)
+                    .map(|c| c.iter().fold(Monoid::zero(), |x, &y| Semigroup::op(&x, &y)))
+                    .reduce(Monoid::zero, |x, y| Semigroup::op(&x, &y));
+                prop_assert_eq!(map_reduce, map_shuffle_reduce);
+            }
+        }
+    }
+
+    mod any {
+        use super::*;
+        use crate::newtypes::Any;
+
+        semilattice_properties!(|| any::<bool>().prop_map(Any));
+
+        mod semiring {
+            use super::*;
+            commutative_semiring_properties!(|| any::<bool>().prop_map(Any));
+        }
+
+        mod star {
+            use super::*;
+            star_semiring_properties!(|| any::<bool>().prop_map(Any));
+        }
+    }
+
+    mod all {
+        use super::*;
+        use crate::newtypes::All;
+
+        semilattice_properties!(|| any::<bool>().prop_map(All));
+    }
+
+    mod left_zero {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        struct LeftZero(u8);
+        impl Semigroup for LeftZero {
+            const IDEMPOTENT: bool = true;
+            fn op(&LeftZero(x): &Self, _: &Self) -> Self {
+                LeftZero(x)
+            }
+        }
+        impl Band for LeftZero {}
+
+        band_properties!(|| any::<u8>().prop_map(LeftZero));
+    }
+
+    mod count {
+        use super::*;
+
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct Count(u64);
+        impl Semigroup for Count {
+            fn op(&Count(x): &Self, &Count(y): &Self) -> Self {
+                Count(x + y)
+            }
+        }
+        impl Monoid for Count {
+            fn zero() -> Self {
+                Count(0)
+            }
+        }
+        impl CommutativeMonoid for Count {}
+        impl CancellativeMonoid for Count {}
+        impl OrderedMonoid for Count {}
+        impl Monus for Count {
+            fn monus(&Count(x): &Self, &Count(y): &Self) -> Self {
+                Count(x.saturating_sub(y))
+            }
+        }
+
+        // Small values make collisions in the cancellation laws likely.
+        mod cancellative {
+            use super::*;
+            cancellative_monoid_properties!(|| (0u64..8).prop_map(Count));
+        }
+
+        mod monus {
+            use super::*;
+            monus_properties!(|| (0..u32::MAX as u64).prop_map(Count));
+        }
+
+        mod ordered {
+            use super::*;
+            ordered_monoid_properties!(|| (0..u32::MAX as u64).prop_map(Count));
+        }
+    }
+
+    mod lattice {
+        use super::*;
+
+        mod boolean {
+            use super::*;
+            bounded_lattice_properties!(any::<bool>);
+        }
+
+        // Permission bits, ordered by inclusion.
+        #[derive(Debug, PartialEq)]
+        struct Perms(u8);
+        impl Lattice for Perms {
+            fn join(&Perms(x): &Self, &Perms(y): &Self) -> Self {
+                Perms(x | y)
+            }
+            fn meet(&Perms(x): &Self, &Perms(y): &Self) -> Self {
+                Perms(x & y)
+            }
+        }
+        impl BoundedLattice for Perms {
+            fn top() -> Self {
+                Perms(0b111)
+            }
+            fn bottom() -> Self {
+                Perms(0)
+            }
+        }
+
+        bounded_lattice_properties!(|| (0u8..8).prop_map(Perms));
+    }
+
+    // Boolean blindness :-(
+    // Other options are worse.
+    // I want feature(adt_const_params)!
+    #[derive(Clone, Debug, PartialEq)]
+    struct TaggedU64<const ADDING: bool>(u64);
+    impl Semigroup for TaggedU64<true> {
+        fn op(&TaggedU64(x): &Self, &TaggedU64(y): &Self) -> Self {
+            TaggedU64(x.wrapping_add(y))
+        }
+    }
+    impl Semigroup for TaggedU64<false> {
+        fn op(&TaggedU64(x): &Self, &TaggedU64(y): &Self) -> Self {
+            TaggedU64(x.wrapping_mul(y))
+        }
+    }
+    impl Monoid for TaggedU64<true> {
+        fn zero() -> Self {
+            TaggedU64(0)
+        }
+    }
+    impl Monoid for TaggedU64<false> {
+        fn zero() -> Self {
+            TaggedU64(1)
+        }
+    }
+    impl CommutativeMonoid for TaggedU64<true> {}
+    impl CommutativeMonoid for TaggedU64<false> {}
+    impl Group for TaggedU64<true> {
+        fn invert(&self) -> Self {
+            TaggedU64(self.0.wrapping_neg())
+        }
+    }
+
+    mod u64_add {
+        use super::*;
+        group_properties!(|| any::<u64>().prop_map(TaggedU64::<true>));
+
+        proptest! {
+            #[test]
+            fn power_group_is_scaling(x in any::<u64>(), n in any::<i64>()) {
+                prop_assert_eq!(power_group(TaggedU64::<true>(x), n), TaggedU64(x.wrapping_mul(n as u64)));
+            }
+        }
+    }
+
+    mod u64_mul {
+        use super::*;
+        monoid_properties!(|| any::<u64>().prop_map(TaggedU64::<false>));
+
+        impl MonoidAction<u64> for TaggedU64<false> {
+            fn act(&self, x: &u64) -> u64 {
+                self.0.wrapping_mul(*x)
+            }
+        }
+
+        mod action {
+            use super::*;
+            monoid_action_properties!(|| any::<u64>().prop_map(TaggedU64::<false>), any::<u64>);
+        }
+
+        proptest! {
+            #[test]
+            fn power_monoid_is_exponentiation(x in any::<u64>(), n in any::<u32>()) {
+                prop_assert_eq!(power_monoid(TaggedU64::<false>(x), n as u64), TaggedU64(x.wrapping_pow(n)));
+            }
+        }
+    }
+
+    mod modular {
+        use super::*;
+
+        const P: u64 = 65_521;
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Zp(u64);
+        impl Semigroup for Zp {
+            fn op(&Zp(x): &Self, &Zp(y): &Self) -> Self {
+                Zp((x + y) % P)
+            }
+        }
+        impl Monoid for Zp {
+            fn zero() -> Self {
+                Zp(0)
+            }
+        }
+        impl CommutativeMonoid for Zp {}
+        impl Group for Zp {
+            fn invert(&self) -> Self {
+                Zp((P - self.0) % P)
+            }
+        }
+        impl Semiring for Zp {
+            fn mul(&Zp(x): &Self, &Zp(y): &Self) -> Self {
+                Zp((x * y) % P)
+            }
+            fn one() -> Self {
+                Zp(1)
+            }
+        }
+        impl CommutativeSemiring for Zp {}
+        impl Ring for Zp {}
+        impl Field for Zp {
+            // Fermat's little theorem: x⁻¹ = x^(p - 2)
+            fn reciprocal(&self) -> Self {
+                let (mut x, mut n, mut y) = (*self, P - 2, Zp(1));
+                while n > 0 {
+                    if n & 1 == 1 {
+                        y = Semiring::mul(&y, &x);
+                    }
+                    x = Semiring::mul(&x, &x);
+                    n >>= 1;
+                }
+                y
+            }
+        }
+
+        field_properties!(|| (0..P).prop_map(Zp));
+
+        // Pairs of residues, scaled componentwise.
+        #[derive(Debug, PartialEq)]
+        struct Zp2(Zp, Zp);
+        impl Semigroup for Zp2 {
+            fn op(Zp2(a, b): &Self, Zp2(c, d): &Self) -> Self {
+                Zp2(Semigroup::op(a, c), Semigroup::op(b, d))
+            }
+        }
+        impl Monoid for Zp2 {
+            fn zero() -> Self {
+                Zp2(Monoid::zero(), Monoid::zero())
+            }
+        }
+        impl CommutativeMonoid for Zp2 {}
+        impl Semimodule<Zp> for Zp2 {
+            fn scale(r: &Zp, Zp2(a, b): &Self) -> Self {
+                Zp2(Semiring::mul(r, a), Semiring::mul(r, b))
+            }
+        }
+
+        mod semimodule {
+            use super::*;
+            semimodule_properties!(|| (0..P).prop_map(Zp), || (0..P, 0..P)
+                .prop_map(|(a, b)| Zp2(Zp(a), Zp(b))));
+        }
+    }
+
+    #[cfg(feature = "num-rational")]
+    mod rational {
+        use super::*;
+        use num_rational::Ratio;
+
+        fn ratio() -> impl Strategy<Value = Ratio<i64>> {
+            (-8i64..8, 1i64..8).prop_map(|(n, d)| Ratio::new(n, d))
+        }
+
+        field_properties!(ratio);
+        semigroup_assign_properties!(ratio);
+
+        #[cfg(feature = "num-complex")]
+        mod complex {
+            use super::*;
+            use num_complex::Complex;
+            field_properties!(|| (ratio(), ratio()).prop_map(|(re, im)| Complex::new(re, im)));
+        }
+
+        #[test]
+        fn harmonic_numbers() {
+            let h = fold_map(1..=4i64, |k| Ratio::new(1, k));
+            assert_eq!(h, Ratio::new(25, 12));
+        }
+    }
+
+    #[cfg(feature = "num-complex")]
+    mod complex {
+        use super::*;
+        use num_complex::Complex;
+
+        ring_properties!(
+            || any::<(i16, i16)>().prop_map(|(re, im)| Complex::new(i64::from(re), i64::from(im)))
+        );
+        mod commutative {
+            use super::*;
+            commutative_semiring_properties!(|| any::<(i16, i16)>()
+                .prop_map(|(re, im)| Complex::new(i64::from(re), i64::from(im))));
+        }
+
+        #[test]
+        fn gaussian_integer_powers() {
+            let i = Complex::new(0i64, 1);
+            let i4 = (0..4).fold(Semiring::one(), |z, _| Semiring::mul(&z, &i));
+            assert_eq!(i4, Complex::new(1, 0));
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    mod matrix {
+        use super::*;
+        use crate::modular::Mod;
+        use crate::newtypes::Product;
+        use nalgebra::{Matrix2, Matrix2x3, Matrix3};
+
+        type F = Mod<1_000_000_007>;
+
+        fn square() -> impl Strategy<Value = Matrix3<i64>> {
+            any::<[i8; 9]>().prop_map(|xs| Matrix3::from_iterator(xs.map(i64::from)))
+        }
+
+        ring_properties!(square);
+        semigroup_assign_properties!(square);
+        mod rectangular {
+            use super::*;
+            group_properties!(
+                || any::<[i32; 6]>().prop_map(|xs| Matrix2x3::from_iterator(xs.map(i64::from)))
+            );
+        }
+
+        #[test]
+        fn fibonacci_by_matrix_power() {
+            let step = Matrix2::new(F::new(1), F::new(1), F::new(1), F::new(0));
+            let fib = |n| power_monoid(Product(step), n).0[(0, 1)];
+            assert_eq!(fib(10), F::new(55));
+            assert_eq!(fib(90), F::new(2_880_067_194_370_816_120 % 1_000_000_007));
+        }
+    }
+
+    mod polynomials {
+        use super::*;
+        use crate::modular::Mod;
+        use crate::newtypes::Any;
+        use crate::polynomial::Poly;
+        use prop::collection::vec;
+
+        type F = Mod<65_521>;
+
+        fn arb() -> impl Strategy<Value = Poly<F>> {
+            vec(0u64..4, 0..6).prop_map(|cs| Poly::new(cs.into_iter().map(F::new).collect()))
+        }
+
+        commutative_semiring_properties!(arb);
+        semigroup_assign_properties!(arb);
+        mod ring {
+            use super::*;
+            ring_properties!(arb);
+        }
+        mod boolean {
+            use super::*;
+            commutative_semiring_properties!(|| vec(any::<bool>(), 0..6)
+                .prop_map(|cs| Poly::new(cs.into_iter().map(Any).collect())));
+        }
+
+        proptest! {
+            #[test]
+            fn evaluation_is_a_homomorphism(p in arb(), q in arb(), x in 0u64..65_521) {
+                let x = F::new(x);
+                prop_assert_eq!(Semigroup::op(&p, &q).eval(&x), p.eval(&x) + q.eval(&x));
+                prop_assert_eq!(Semiring::mul(&p, &q).eval(&x), p.eval(&x) * q.eval(&x));
+            }
+        }
+
+        #[test]
+        fn counts_change() {
+            // Ways to make 10 from coins of 1, 2 and 5, as the coefficient of x¹⁰ in ∏ 1/(1 - xᶜ)
+            let geometric =
+                |c: usize| Poly::new((0..=10).map(|k| F::new((k % c == 0) as u64)).collect());
+            let ways = [1, 2, 5]
+                .into_iter()
+                .map(geometric)
+                .fold(Poly::one(), |p, q| Semiring::mul(&p, &q));
+            assert_eq!(ways.coeff(10), F::new(10));
+            let binomial = (0..5).fold(Poly::one(), |p, _| {
+                Semiring::mul(&p, &Poly::new(vec![F::new(1), F::new(1)]))
+            });
+            assert_eq!(binomial.coeffs(), [1, 5, 10, 10, 5, 1].map(F::new));
+            assert_eq!(binomial.degree(), Some(5));
+        }
+    }
+
+    mod residues {
+        use super::*;
+        use crate::modular::Mod;
+        use crate::newtypes::Product;
+
+        // 2⁶⁴ - 59, the largest 64-bit prime, exercises overflow in addition and multiplication.
+        const BIG: u64 = u64::MAX - 58;
+
+        field_properties!(|| any::<u64>().prop_map(Mod::<65_521>::new));
+        semigroup_assign_properties!(|| any::<u64>().prop_map(Mod::<65_521>::new));
+        cancellative_monoid_properties!(|| any::<u64>().prop_map(Mod::<65_521>::new));
+
+        mod big {
+            use super::*;
+            field_properties!(|| any::<u64>().prop_map(Mod::<BIG>::new));
+        }
+
+        mod composite {
+            use super::*;
+            commutative_semiring_properties!(|| any::<u64>().prop_map(Mod::<12>::new));
+            mod ring {
+                use super::*;
+                ring_properties!(|| any::<u64>().prop_map(Mod::<12>::new));
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn pow_is_exponentiation(x in any::<u64>(), n in 0u64..64) {
+                let naive = (0..n).fold(Mod::<1_000_000_007>::new(1), |acc, _| acc * Mod::new(x));
+                prop_assert_eq!(Mod::<1_000_000_007>::new(x).pow(n), naive);
+                prop_assert_eq!(power_monoid(Product(Mod::<1_000_000_007>::new(x)), n).0, naive);
+            }
+
+            #[test]
+            fn fermat(x in 1u64..BIG) {
+                prop_assert_eq!(Mod::<BIG>::new(x).pow(BIG - 1), Mod::new(1));
+            }
+        }
+
+        #[test]
+        fn primality() {
+            let flags = [
+                Mod::<1>::IS_FIELD,
+                Mod::<2>::IS_FIELD,
+                Mod::<12>::IS_FIELD,
+                Mod::<65_521>::IS_FIELD,
+                Mod::<998_244_353>::IS_FIELD,
+                Mod::<BIG>::IS_FIELD,
+                // Carmichael number, and strong pseudoprime to bases 2, 3, 5 & 7
+                Mod::<561>::IS_FIELD,
+                Mod::<3_215_031_751>::IS_FIELD,
+            ];
+            assert_eq!(flags, [false, true, false, true, true, true, false, false]);
+        }
+    }
+
+    mod string {
+        use super::*;
+        monoid_properties!(any::<String>);
+        semigroup_assign_properties!(any::<String>);
+        op_owned_properties!(any::<String>);
+
+        struct Len;
+        impl MonoidHom<String, TaggedU64<true>> for Len {
+            fn apply(s: &String) -> TaggedU64<true> {
+                TaggedU64(s.len() as u64)
+            }
+        }
+
+        mod len {
+            use super::*;
+            homomorphism_properties!(Len, any::<String>);
+        }
+
+        // Strings act on byte counts by adding their own length.
+        impl MonoidAction<usize> for String {
+            fn act(&self, n: &usize) -> usize {
+                self.len() + n
+            }
+        }
+
+        mod action {
+            use super::*;
+            monoid_action_properties!(any::<String>, || 0..usize::MAX / 4);
+        }
+    }
+
+    mod vec {
+        use super::*;
+        monoid_properties!(any::<Vec<u8>>);
+        semigroup_assign_properties!(any::<Vec<u8>>);
+        op_owned_properties!(any::<Vec<u8>>);
+
+        mod cloned {
+            use super::*;
+            use prop::collection::vec;
+            monoid_properties!(|| vec("[a-z]{0,4}", 0..8));
+            semigroup_assign_properties!(|| vec("[a-z]{0,4}", 0..8));
+            op_owned_properties!(|| vec("[a-z]{0,4}", 0..8));
+        }
+
+        #[test]
+        fn op_owned_reuses_spare_capacity_on_the_right() {
+            let mut ys = Vec::with_capacity(8);
+            ys.extend(["c", "d"].map(String::from));
+            let xs = vec!["a".to_string(), "b".to_string()];
+            let ptr = ys.as_ptr();
+            let zs = Vec::op_owned(xs, ys);
+            assert_eq!(zs, ["a", "b", "c", "d"]);
+            assert_eq!(zs.as_ptr(), ptr);
+        }
+    }
+
+    #[cfg(feature = "smallvec")]
+    mod smallvec {
+        use super::*;
+        use ::smallvec::SmallVec;
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = SmallVec<[String; 4]>> {
+            vec("[a-z]{0,4}", 0..8).prop_map(SmallVec::from_vec)
+        }
+
+        monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+        op_owned_properties!(arb);
+
+        #[test]
+        fn stays_inline_when_small() {
+            let v: SmallVec<[u8; 8]> = fold_map(0..4u8, |x| SmallVec::from_slice(&[x, x]));
+            assert_eq!(&v[..], [0, 0, 1, 1, 2, 2, 3, 3]);
+            assert!(!v.spilled());
+        }
+    }
+
+    #[cfg(feature = "arrayvec")]
+    mod arrayvec {
+        use super::*;
+        use ::arrayvec::ArrayVec;
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = ArrayVec<u8, 6>> {
+            vec(any::<u8>(), 0..=6).prop_map(|xs| xs.into_iter().collect())
+        }
+
+        monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+        op_owned_properties!(arb);
+
+        proptest! {
+            #[test]
+            fn keeps_a_prefix(xss in vec(vec(any::<u8>(), 0..4), 0..8)) {
+                let v: ArrayVec<u8, 6> = fold_map(xss.iter(), |xs| xs.iter().copied().collect());
+                let flat = xss.concat();
+                prop_assert_eq!(&v[..], &flat[..flat.len().min(6)]);
+            }
+        }
+    }
+
+    mod dynamic {
+        use super::{max::Max, *};
+
+        proptest! {
+            #[test]
+            fn matching_types_combine(x in any::<f64>(), y in any::<f64>(), s in any::<String>(), t in any::<String>()) {
+                let xs: Vec<Box<dyn DynSemigroup>> = vec![Box::new(Max(x)), Box::new(s.clone())];
+                let ys: Vec<Box<dyn DynSemigroup>> = vec![Box::new(Max(y)), Box::new(t.clone())];
+                let zs = xs.iter().zip(ys.iter()).map(|(a, b)| a.op_dyn(b.as_ref()).unwrap()).collect::<Vec<_>>();
+                prop_assert_eq!(zs[0].downcast_ref::<Max>(), Some(&Semigroup::op(&Max(x), &Max(y))));
+                prop_assert_eq!(zs[1].downcast_ref::<String>(), Some(&Semigroup::op(&s, &t)));
+            }
+            #[test]
+            fn mismatched_types_do_not(x in any::<f64>(), s in any::<String>()) {
+                let a: Box<dyn DynSemigroup> = Box::new(Max(x));
+                let b: Box<dyn DynSemigroup> = Box::new(s);
+                prop_assert!(a.op_dyn(b.as_ref()).is_none());
+                prop_assert!(b.op_dyn(a.as_ref()).is_none());
+            }
+        }
+    }
+
+    mod deque {
+        use super::*;
+        use prop::collection::{linked_list, vec_deque};
+
+        monoid_properties!(|| vec_deque(any::<u8>(), 0..100));
+        semigroup_assign_properties!(|| vec_deque(any::<u8>(), 0..100));
+        op_owned_properties!(|| vec_deque(any::<u8>(), 0..100));
+
+        mod linked {
+            use super::*;
+            monoid_properties!(|| linked_list(any::<String>(), 0..10));
+            semigroup_assign_properties!(|| linked_list(any::<String>(), 0..10));
+            op_owned_properties!(|| linked_list(any::<String>(), 0..10));
+        }
+
+        proptest! {
+            #[test]
+            fn fold_map_splices_lists(xss in any::<Vec<Vec<u8>>>()) {
+                let spliced = fold_map(xss.iter(), |xs| xs.iter().copied().collect::<std::collections::LinkedList<_>>());
+                prop_assert!(spliced.into_iter().eq(xss.concat()));
+            }
+        }
+    }
+
+    mod dual {
+        use super::*;
+        use crate::combinators::Dual;
+
+        monoid_properties!(|| any::<String>().prop_map(Dual));
+        semigroup_assign_properties!(|| any::<String>().prop_map(Dual));
+        op_owned_properties!(|| any::<String>().prop_map(Dual));
+
+        mod group {
+            use super::*;
+            group_properties!(|| any::<u64>().prop_map(|x| Dual(TaggedU64::<true>(x))));
+        }
+
+        proptest! {
+            #[test]
+            fn folds_from_the_right(xs in any::<Vec<String>>()) {
+                let Dual(folded) = fold_map(xs.iter().cloned(), Dual);
+                prop_assert_eq!(folded, xs.into_iter().rev().collect::<String>());
+            }
+        }
+    }
+
+    mod validation {
+        use super::*;
+        use crate::combinators::Validation;
+        use crate::newtypes::{Any, Max, Sum};
+        use proptest::result::maybe_ok;
+
+        fn arb_strings() -> impl Strategy<Value = Validation<String, String>> {
+            maybe_ok("[a-z]{0,4}", "[A-Z]{0,4}").prop_map(Validation)
+        }
+
+        monoid_properties!(arb_strings);
+        semigroup_assign_properties!(arb_strings);
+        op_owned_properties!(arb_strings);
+
+        mod commutative {
+            use super::*;
+            commutative_monoid_properties!(|| maybe_ok(any::<u32>(), any::<u32>())
+                .prop_map(|r| Validation(r.map(|x| Sum(x as u64)).map_err(|e| Sum(e as u64)))));
+        }
+
+        mod band {
+            use super::*;
+            band_properties!(|| maybe_ok(any::<u8>(), any::<bool>())
+                .prop_map(|r| Validation(r.map(Max).map_err(Any))));
+        }
+
+        #[test]
+        fn fold_reports_every_error() {
+            let rs = vec![Ok(Sum(1)), Err(vec!["a"]), Ok(Sum(2)), Err(vec!["b"])];
+            assert_eq!(Validation::fold(rs), Err(vec!["a", "b"]));
+            let rs: Vec<Result<_, Vec<&str>>> = vec![Ok(Sum(1)), Ok(Sum(2))];
+            assert_eq!(
+                rs.into_iter().collect::<Validation<_, _>>(),
+                Validation::ok(Sum(3))
+            );
+        }
+
+        #[test]
+        fn collect_all() {
+            let parse = |s: &str| s.parse::<u8>().map_err(|_| s.to_string());
+            assert_eq!(
+                Validation::collect_all(["1", "x", "2", "y"].map(parse)),
+                Err(vec!["x".to_string(), "y".to_string()])
+            );
+            assert_eq!(
+                Validation::collect_all(["1", "2"].map(parse)),
+                Ok(vec![1, 2])
+            );
+        }
+    }
+
+    mod bitset {
+        use super::*;
+        use crate::collections::BitSet;
+        use crate::newtypes::{Intersection, SymmetricDifference};
+        use prop::collection::vec;
+        use std::collections::BTreeSet;
+
+        fn arb() -> impl Strategy<Value = BitSet<2>> {
+            vec(0usize..128, 0..32).prop_map(|xs| xs.into_iter().collect())
+        }
+
+        semilattice_properties!(arb);
+        semigroup_assign_properties!(arb);
+        mod monus {
+            use super::*;
+            monus_properties!(arb);
+        }
+        mod lattice {
+            use super::*;
+            bounded_lattice_properties!(arb);
+        }
+        mod intersection {
+            use super::*;
+            semilattice_properties!(|| arb().prop_map(Intersection));
+            semigroup_assign_properties!(|| arb().prop_map(Intersection));
+        }
+        mod xor {
+            use super::*;
+            group_properties!(|| arb().prop_map(SymmetricDifference));
+            semigroup_assign_properties!(|| arb().prop_map(SymmetricDifference));
+            mod cancellative {
+                use super::*;
+                cancellative_monoid_properties!(|| arb().prop_map(SymmetricDifference));
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn matches_btree_set(xs in vec(0usize..128, 0..32), ys in vec(0usize..128, 0..32)) {
+                let (a, b) = (xs.iter().copied().collect::<BitSet<2>>(), ys.iter().copied().collect::<BitSet<2>>());
+                let (s, t) = (xs.into_iter().collect::<BTreeSet<_>>(), ys.into_iter().collect::<BTreeSet<_>>());
+                let elems = |x: BitSet<2>| x.iter().collect::<Vec<_>>();
+                prop_assert_eq!(a.len(), s.len());
+                prop_assert_eq!(elems(BitSet::op(&a, &b)), s.union(&t).copied().collect::<Vec<_>>());
+                prop_assert_eq!(elems(Intersection::op(&Intersection(a), &Intersection(b)).0), s.intersection(&t).copied().collect::<Vec<_>>());
+                prop_assert_eq!(elems(SymmetricDifference::op(&SymmetricDifference(a), &SymmetricDifference(b)).0), s.symmetric_difference(&t).copied().collect::<Vec<_>>());
+                prop_assert_eq!(a.is_subset(&b), s.is_subset(&t));
+            }
+        }
+
+        #[test]
+        fn insert_and_remove() {
+            let mut s = BitSet::<1>::new();
+            assert!(s.insert(63) && !s.insert(63));
+            assert!(s.contains(63) && !s.contains(64));
+            assert!(s.remove(63) && !s.remove(63));
+            assert!(s.is_empty());
+            assert_eq!(BitSet::<3>::full().len(), BitSet::<3>::CAPACITY);
+        }
+    }
+
+    mod sorted_vec {
+        use super::*;
+        use crate::collections::{SortedSet, SortedVec};
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = SortedVec<u8>> {
+            vec(0u8..16, 0..16).prop_map(SortedVec::new)
+        }
+
+        commutative_monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+        op_owned_properties!(arb);
+
+        mod dedup {
+            use super::*;
+            semilattice_properties!(|| vec(0u8..16, 0..16).prop_map(SortedSet::new));
+            semigroup_assign_properties!(|| vec(0u8..16, 0..16).prop_map(SortedSet::new));
+        }
+
+        proptest! {
+            #[test]
+            fn parallel_merge_sorts(xs in vec(any::<u16>(), 0..200)) {
+                use rayon::prelude::*;
+                let merged: SortedVec<u16> = xs.par_chunks(16).map(|c| c.iter().copied().collect()).reduce(Monoid::zero, Semigroup::op_owned);
+                let mut sorted = xs.clone();
+                sorted.sort();
+                prop_assert_eq!(merged.as_slice(), &sorted[..]);
+                let unique = xs.iter().copied().map(SortedSet::of).fold(SortedSet::zero(), SortedSet::op_owned);
+                sorted.dedup();
+                prop_assert_eq!(unique.into_vec(), sorted);
+            }
+
+            #[test]
+            fn insert_keeps_order(xs in vec(0u8..16, 0..16), t in 0u8..16) {
+                let mut s = SortedVec::<u8>::new(xs.clone());
+                s.insert(t);
+                prop_assert!(s.contains(&t));
+                prop_assert_eq!(s, SortedVec::<u8>::new([xs.clone(), vec![t]].concat()));
+                let mut s = SortedSet::new(xs.clone());
+                s.insert(t);
+                prop_assert_eq!(s, SortedSet::new([xs, vec![t]].concat()));
+            }
+        }
+    }
+
+    mod interval_set {
+        use super::*;
+        use crate::collections::IntervalSet;
+        use prop::collection::vec;
+        use rayon::prelude::*;
+        use std::ops::Range;
+
+        fn ranges() -> impl Strategy<Value = Vec<Range<u8>>> {
+            vec((0u8..64, 0u8..64).prop_map(|(a, b)| a..b), 0..8)
+        }
+
+        fn arb() -> impl Strategy<Value = IntervalSet<u8>> {
+            ranges().prop_map(|rs| rs.into_iter().collect())
+        }
+
+        semilattice_properties!(arb);
+        semigroup_assign_properties!(arb);
+        op_owned_properties!(arb);
+
+        proptest! {
+            #[test]
+            fn matches_pointwise_model(rs in ranges(), q in (0u8..64, 0u8..64)) {
+                let covered = |t: u8| rs.iter().any(|r| r.contains(&t));
+                let set = rs.par_iter().cloned().map(IntervalSet::of).reduce(Monoid::zero, Semigroup::op_owned);
+                for t in 0..64 {
+                    prop_assert_eq!(set.contains(&t), covered(t));
+                }
+                let q = q.0..q.1;
+                prop_assert_eq!(set.covers(&q), q.clone().all(covered));
+                prop_assert_eq!(set.overlaps(&q), q.clone().any(covered));
+                prop_assert_eq!(set.measure::<u8>() as usize, (0..64).filter(|&t| covered(t)).count());
+                prop_assert!(set.ranges().zip(set.ranges().skip(1)).all(|(a, b)| a.end < b.start));
+            }
+        }
+
+        #[test]
+        fn covered_time() {
+            use std::time::{Duration, Instant};
+            let t0 = Instant::now();
+            let at = |s| t0 + Duration::from_secs(s);
+            let mut shard = IntervalSet::of(at(0)..at(10));
+            shard.insert(at(5)..at(12));
+            let other = IntervalSet::of(at(20)..at(25));
+            let all = IntervalSet::op(&shard, &other);
+            assert_eq!(all.len(), 2);
+            assert_eq!(all.measure::<Duration>(), Duration::from_secs(17));
+            assert!(all.covers(&(at(1)..at(12))) && !all.covers(&(at(11)..at(21))));
+        }
+    }
+
+    mod aabb {
+        use super::*;
+        use crate::geometry::{Aabb2, Aabb3};
+        use prop::collection::vec;
+        use rayon::prelude::*;
+
+        fn point<const D: usize>() -> impl Strategy<Value = [f64; D]> {
+            any::<[i16; D]>().prop_map(|p| p.map(f64::from))
+        }
+
+        semilattice_properties!(|| (point(), point()).prop_map(|(a, b)| Aabb3::from_corners(a, b)));
+        semigroup_assign_properties!(
+            || (point(), point()).prop_map(|(a, b)| Aabb3::from_corners(a, b))
+        );
+
+        proptest! {
+            #[test]
+            fn encloses_every_point(ps in vec(point::<2>(), 0..50)) {
+                let b = ps.par_iter().map(|&p| Aabb2::from_point(p)).reduce(Monoid::zero, Semigroup::op_owned);
+                prop_assert_eq!(b, ps.iter().copied().collect::<Aabb2>());
+                prop_assert_eq!(b.is_empty(), ps.is_empty());
+                prop_assert!(ps.iter().all(|p| b.contains(p)));
+                for i in 0..2 {
+                    prop_assert!(ps.is_empty() || ps.iter().any(|p| p[i] == b.min[i]));
+                    prop_assert!(ps.is_empty() || ps.iter().any(|p| p[i] == b.max[i]));
+                }
+            }
+        }
+
+        #[test]
+        fn queries() {
+            let b = Aabb2::from_corners([2.0, 0.0], [0.0, 4.0]);
+            assert_eq!(b.center(), Some([1.0, 2.0]));
+            assert_eq!(b.extent(), Some([2.0, 4.0]));
+            assert!(b.intersects(&Aabb2::from_point([2.0, 4.0])));
+            assert!(!b.intersects(&Aabb2::zero()));
+            assert_eq!(Aabb2::zero().center(), None);
+        }
+    }
+
+    mod affine {
+        use super::*;
+        use crate::geometry::Affine2;
+        use std::f64::consts::FRAC_PI_2;
+
+        // Small integer coefficients keep every product exact, so the laws hold on the nose.
+        fn arb() -> impl Strategy<Value = Affine2> {
+            any::<[[i8; 3]; 2]>().prop_map(|m| Affine2(m.map(|r| r.map(|x| f64::from(x % 8)))))
+        }
+
+        fn point() -> impl Strategy<Value = [f64; 2]> {
+            any::<[i8; 2]>().prop_map(|p| p.map(f64::from))
+        }
+
+        monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+        monoid_action_properties!(arb, point);
+
+        proptest! {
+            #[test]
+            fn repeated_translation(dx in -100i32..100, dy in -100i32..100, n in 0u64..1000) {
+                let t = Affine2::translation(dx.into(), dy.into());
+                let (nx, ny) = (n as f64 * f64::from(dx), n as f64 * f64::from(dy));
+                prop_assert_eq!(power_monoid(t, n), Affine2::translation(nx, ny));
+            }
+
+            #[test]
+            fn inverse_undoes(f in arb(), p in point()) {
+                if let Some(g) = f.inverse() {
+                    let [x, y] = g.apply(f.apply(p));
+                    prop_assert!((x - p[0]).abs() < 1e-9 && (y - p[1]).abs() < 1e-9);
+                } else {
+                    prop_assert_eq!(f.determinant(), 0.0);
+                }
+            }
+        }
+
+        #[test]
+        fn scene_graph_chain() {
+            let chain = [
+                Affine2::translation(1.0, 0.0),
+                Affine2::rotation(FRAC_PI_2),
+                Affine2::scaling(2.0, 3.0),
+            ];
+            let world = fold_map(chain.iter().copied(), |t| t);
+            let [x, y] = world.apply([1.0, 1.0]);
+            assert!((x - -2.0).abs() < 1e-12 && (y - 2.0).abs() < 1e-12);
+            let [x, y] = power_monoid(Affine2::rotation(FRAC_PI_2), 4).apply([3.0, 4.0]);
+            assert!((x - 3.0).abs() < 1e-12 && (y - 4.0).abs() < 1e-12);
+        }
+    }
+
+    mod zip_vec {
+        use super::*;
+        use crate::combinators::ZipVec;
+        use crate::newtypes::{Max, Sum};
+        use prop::collection::vec;
+
+        fn sums() -> impl Strategy<Value = ZipVec<Sum<u64>>> {
+            vec(any::<u32>(), 0..8).prop_map(|xs| xs.into_iter().map(|x| Sum(x as u64)).collect())
+        }
+
+        commutative_monoid_properties!(sums);
+        semigroup_assign_properties!(sums);
+        op_owned_properties!(sums);
+
+        mod semilattice {
+            use super::*;
+            semilattice_properties!(|| vec(any::<u8>(), 0..8)
+                .prop_map(|xs| xs.into_iter().map(Max).collect::<ZipVec<_>>()));
+        }
+
+        #[test]
+        fn merges_histograms_of_different_lengths() {
+            let h = |xs: &[u64]| xs.iter().copied().map(Sum).collect::<ZipVec<_>>();
+            assert_eq!(ZipVec::op(&h(&[1, 2]), &h(&[10, 20, 30])), h(&[11, 22, 30]));
+            let total = fold_map([h(&[1]), h(&[0, 1, 1]), h(&[2, 0])].into_iter(), |x| x);
+            assert_eq!(total, h(&[3, 1, 1]));
+        }
+    }
+
+    mod endo {
+        use super::*;
+        use crate::combinators::{ArcEndo, Endo};
+        use rayon::prelude::*;
+
+        fn affine((a, b): (u64, u64)) -> Endo<u64> {
+            Endo::new(move |x: u64| x.wrapping_mul(a).wrapping_add(b))
+        }
+
+        proptest! {
+            #[test]
+            fn associativity(f in any::<(u64, u64)>(), g in any::<(u64, u64)>(), h in any::<(u64, u64)>(), x in any::<u64>()) {
+                let (f, g, h) = (affine(f), affine(g), affine(h));
+                prop_assert_eq!(
+                    Semigroup::op(&f, &Semigroup::op(&g, &h)).apply(x),
+                    Semigroup::op(&Semigroup::op(&f, &g), &h).apply(x)
+                );
+            }
+            #[test]
+            fn identities(f in any::<(u64, u64)>(), x in any::<u64>()) {
+                let f = affine(f);
+                prop_assert_eq!(Semigroup::op(&Endo::zero(), &f).apply(x), f.apply(x));
+                prop_assert_eq!(Semigroup::op(&f, &Endo::zero()).apply(x), f.apply(x));
+            }
+            #[test]
+            fn composition_order(f in any::<(u64, u64)>(), g in any::<(u64, u64)>(), x in any::<u64>()) {
+                let (f, g) = (affine(f), affine(g));
+                prop_assert_eq!(Semigroup::op(&f, &g).act(&x), f.act(&g.act(&x)));
+            }
+            #[test]
+            fn parallel_pipelines(fs in any::<Vec<(u64, u64)>>(), x in any::<u64>()) {
+                let sequential = fold_map(fs.iter().copied(), affine);
+                let parallel = fs
+                    .par_iter()
+                    .map(|&(a, b)| ArcEndo::new(move |x: u64| x.wrapping_mul(a).wrapping_add(b)))
+                    .reduce(Monoid::zero, |f, g| Semigroup::op(&f, &g));
+                prop_assert_eq!(sequential.apply(x), parallel.apply(x));
+            }
+        }
+    }
+
+    mod cayley {
+        use super::*;
+        use crate::combinators::Cayley;
+        use prop::collection::vec;
+
+        struct Run;
+        impl MonoidHom<Cayley<String>, String> for Run {
+            fn apply(c: &Cayley<String>) -> String {
+                c.run()
+            }
+        }
+
+        homomorphism_properties!(Run, || vec("[a-z]{0,8}", 0..50)
+            .prop_map(|ss| fold_map(ss.into_iter(), Cayley::new)));
+
+        proptest! {
+            #[test]
+            fn runs_in_order(ss in vec("[a-z]{0,8}", 0..50)) {
+                prop_assert_eq!(fold_map(ss.iter().cloned(), Cayley::new).run(), ss.concat());
+            }
+        }
+
+        #[test]
+        fn long_chains_are_fine() {
+            let n = 100_000;
+            let c = fold_map(0..n, |i| Cayley::new(vec![i]));
+            assert_eq!(c.run(), (0..n).collect::<Vec<_>>());
+        }
+    }
+
+    mod free {
+        use super::*;
+        use crate::free::{FreeMonoid, Multiset, NonEmpty};
+        use prop::collection::vec;
+
+        mod nonempty {
+            use super::*;
+            use crate::newtypes::{First, Min};
+
+            fn arb() -> impl Strategy<Value = NonEmpty<u8>> {
+                (any::<u8>(), vec(any::<u8>(), 0..8)).prop_map(|(h, t)| NonEmpty::new(h, t))
+            }
+
+            semigroup_properties!(arb);
+            semigroup_assign_properties!(arb);
+            op_owned_properties!(arb);
+
+            proptest! {
+                #[test]
+                fn reduce_without_zero(xs in arb()) {
+                    let least = *xs.iter().min().unwrap();
+                    prop_assert_eq!(xs.fold_hom(|&x| Min(x)), Min(least));
+                    prop_assert_eq!(xs.fold_hom(|&x| First::new(x)), First::new(*xs.first()));
+                    let mins = NonEmpty::from_vec(xs.iter().map(|&x| Min(x)).collect()).unwrap();
+                    prop_assert_eq!(mins.reduce(), Min(least));
+                }
+
+                #[test]
+                fn round_trips_through_vec(xs in vec(any::<u8>(), 0..8)) {
+                    let ne = NonEmpty::from_vec(xs.clone());
+                    prop_assert_eq!(ne.is_none(), xs.is_empty());
+                    if let Some(ne) = ne {
+                        prop_assert_eq!(ne.len(), xs.len());
+                        prop_assert_eq!(ne.last(), xs.last().unwrap());
+                        prop_assert_eq!(ne.into_iter().collect::<Vec<_>>(), xs);
+                    }
+                }
+            }
+        }
+
+        mod sequences {
+            use super::*;
+            cancellative_monoid_properties!(|| vec(0u8..4, 0..4).prop_map(FreeMonoid));
+            semigroup_assign_properties!(|| any::<Vec<u8>>().prop_map(FreeMonoid));
+            op_owned_properties!(|| any::<Vec<u8>>().prop_map(FreeMonoid));
+
+            proptest! {
+                #[test]
+                fn fold_hom_concatenates(ss in vec("[a-z]{0,8}", 0..50)) {
+                    let free = ss.iter().cloned().collect::<FreeMonoid<_>>();
+                    prop_assert_eq!(free.fold_hom(|s| s.clone()), ss.concat());
+                }
+            }
+        }
+
+        mod multisets {
+            use super::*;
+
+            fn bag() -> impl Strategy<Value = Multiset<u8>> {
+                vec(0u8..8, 0..20).prop_map(Multiset::from_iter)
+            }
+
+            monus_properties!(bag);
+            semigroup_assign_properties!(bag);
+            op_owned_properties!(bag);
+
+            mod cancellative {
+                use super::*;
+                cancellative_monoid_properties!(|| vec(0u8..2, 0..3).prop_map(Multiset::from_iter));
+            }
+
+            proptest! {
+                #[test]
+                fn counts(xs in vec(0u8..8, 0..100), t in 0u8..8) {
+                    let m = xs.iter().copied().collect::<Multiset<_>>();
+                    prop_assert_eq!(m.count(&t), xs.iter().filter(|&&x| x == t).count() as u64);
+                    prop_assert_eq!(m.len(), xs.len() as u64);
+                }
+                #[test]
+                fn fold_hom_forgets_order(xs in vec(0u8..8, 0..100)) {
+                    let m = xs.iter().copied().collect::<Multiset<_>>();
+                    let total = |&x: &u8| TaggedU64::<true>(x as u64);
+                    prop_assert_eq!(m.fold_hom(total), fold_map(xs.iter(), total));
+                }
+                #[test]
+                fn scale_is_power(m in bag(), n in 0u64..100) {
+                    prop_assert_eq!(m.scale(n), power_monoid(m, n));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    mod sum_array {
+        use super::*;
+        use crate::newtypes::SumArray;
+        use ndarray::{arr1, arr2, Array, Array2, IxDyn};
+        use rayon::prelude::*;
+
+        // Shapes drawn from 2×3 and its broadcastable relatives.
+        fn arb() -> impl Strategy<Value = SumArray<i64, ndarray::Ix2>> {
+            let shape = prop_oneof![Just((2, 3)), Just((1, 3)), Just((2, 1)), Just((1, 1))];
+            (shape, prop::collection::vec(-100i64..100, 6)).prop_map(|((r, c), xs)| {
+                SumArray(Array2::from_shape_vec((r, c), xs[..r * c].to_vec()).unwrap())
+            })
+        }
+
+        commutative_monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+        op_owned_properties!(arb);
+
+        #[test]
+        fn broadcasting() {
+            let m = SumArray(arr2(&[[1, 2, 3], [4, 5, 6]]));
+            let row = SumArray(arr2(&[[10, 20, 30]]));
+            assert_eq!(
+                SumArray::op(&m, &row),
+                SumArray(arr2(&[[11, 22, 33], [14, 25, 36]]))
+            );
+            assert_eq!(SumArray::op(&SumArray::zero(), &m), m);
+            let dynamic = SumArray(arr1(&[1.0, 2.0]).into_dyn());
+            assert_eq!(SumArray::op(&dynamic, &SumArray::zero()), dynamic);
+            assert_eq!(SumArray::<f64, IxDyn>::zero().0.ndim(), 0);
+        }
+
+        #[test]
+        fn parallel_partitions() {
+            let total = (0..100)
+                .into_par_iter()
+                .map(|i| SumArray(Array::from_elem((2, 2), i as u64)))
+                .reduce(SumArray::zero, SumArray::op_owned);
+            assert_eq!(total, SumArray(Array::from_elem((2, 2), 4950)));
+        }
+    }
+
+    mod sum_product {
+        use super::*;
+        use crate::newtypes::*;
+
+        mod sum {
+            use super::*;
+            group_properties!(|| (-1_000_000i64..1_000_000).prop_map(Sum));
+
+            mod unsigned {
+                use super::*;
+                monus_properties!(|| (0..u32::MAX as u64).prop_map(Sum));
+                ordered_monoid_properties!(|| (0..u32::MAX as u64).prop_map(Sum));
+            }
+        }
+
+        mod product {
+            use super::*;
+            commutative_monoid_properties!(|| (-1000i64..1000).prop_map(Product));
+        }
+
+        mod wrapping_sum {
+            use super::*;
+            group_properties!(|| any::<u8>().prop_map(WrappingSum));
+        }
+
+        mod wrapping_product {
+            use super::*;
+            commutative_monoid_properties!(|| any::<i32>().prop_map(WrappingProduct));
+        }
+
+        mod checked_sum {
+            use super::*;
+            commutative_monoid_properties!(|| any::<Option<u8>>().prop_map(CheckedSum));
+
+            proptest! {
+                #[test]
+                fn overflow_is_detected(xs in any::<Vec<u8>>()) {
+                    let total = xs.iter().map(|&x| x as u64).sum::<u64>();
+                    let CheckedSum(checked) = fold_map(xs.into_iter(), CheckedSum::new);
+                    prop_assert_eq!(checked, u8::try_from(total).ok());
+                }
+            }
+        }
+
+        mod checked_product {
+            use super::*;
+            commutative_monoid_properties!(|| any::<Option<u8>>().prop_map(CheckedProduct));
+        }
+    }
+
+    mod min_max {
+        use super::*;
+        use crate::newtypes::{Max, Min};
+
+        mod max {
+            use super::*;
+            semilattice_properties!(|| any::<i16>().prop_map(Max));
+            op_owned_properties!(|| any::<i16>().prop_map(Max));
+
+            mod monus {
+                use super::*;
+                monus_properties!(|| any::<i16>().prop_map(Max));
+            }
+
+            mod ordered {
+                use super::*;
+                ordered_monoid_properties!(|| any::<i16>().prop_map(Max));
+            }
+
+            mod unbounded {
+                use super::*;
+                monoid_properties!(|| any::<Option<String>>().prop_map(|o| o.map(Max)));
+            }
+        }
+
+        mod min {
+            use super::*;
+            semilattice_properties!(|| any::<u64>().prop_map(Min));
+
+            mod ordered {
+                use super::*;
+                ordered_monoid_properties!(|| any::<u64>().prop_map(Min));
+            }
+
+            mod unbounded {
+                use super::*;
+                band_properties!(|| any::<Vec<u8>>().prop_map(Min));
+            }
+        }
+    }
+
+    #[cfg(feature = "ordered-float")]
+    mod total_extrema {
+        use super::*;
+        use crate::newtypes::{TotalMax, TotalMin};
+        use ordered_float::{NotNan, OrderedFloat};
+
+        // Every float, including NaN and the infinities.
+        fn ordered() -> impl Strategy<Value = OrderedFloat<f64>> {
+            prop_oneof![
+                any::<f64>(),
+                Just(f64::NAN),
+                Just(f64::INFINITY),
+                Just(f64::NEG_INFINITY)
+            ]
+            .prop_map(OrderedFloat)
+        }
+
+        fn not_nan() -> impl Strategy<Value = NotNan<f64>> {
+            ordered().prop_filter_map("NaN", |x| NotNan::new(x.0).ok())
+        }
+
+        mod max {
+            use super::*;
+            semilattice_properties!(|| ordered().prop_map(TotalMax));
+            mod monus {
+                use super::*;
+                monus_properties!(|| ordered().prop_map(TotalMax));
+            }
+            mod ordered {
+                use super::*;
+                ordered_monoid_properties!(|| ordered().prop_map(TotalMax));
+            }
+            mod not_nan {
+                use super::*;
+                semilattice_properties!(|| not_nan().prop_map(TotalMax));
+            }
+        }
+
+        mod min {
+            use super::*;
+            semilattice_properties!(|| ordered().prop_map(TotalMin));
+            mod ordered {
+                use super::*;
+                ordered_monoid_properties!(|| ordered().prop_map(TotalMin));
+            }
+            mod not_nan {
+                use super::*;
+                semilattice_properties!(|| not_nan().prop_map(TotalMin));
+            }
+        }
+
+        #[test]
+        fn infinities_are_not_swallowed() {
+            let xs = [f64::NEG_INFINITY, -1.0].map(OrderedFloat);
+            assert_eq!(
+                fold_map(xs.into_iter(), TotalMax),
+                TotalMax(OrderedFloat(-1.0))
+            );
+            assert_eq!(
+                fold_map(xs.into_iter(), TotalMin),
+                TotalMin(OrderedFloat(f64::NEG_INFINITY))
+            );
+            assert_eq!(
+                fold_map(std::iter::empty(), TotalMin::<NotNan<f64>>),
+                TotalMin(NotNan::new(f64::INFINITY).unwrap())
+            );
+        }
+    }
+
+    mod float_extrema {
+        use super::*;
+        use crate::newtypes::{IgnoreNan, MaxF64, MinF64, PropagateNan};
+
+        fn float() -> impl Strategy<Value = f64> {
+            prop_oneof![any::<f64>(), Just(f64::NAN), Just(-0.0)]
+        }
+
+        mod max_ignore {
+            use super::*;
+            semilattice_properties!(|| float().prop_map(MaxF64::<IgnoreNan>::new));
+        }
+
+        mod max_propagate {
+            use super::*;
+            semilattice_properties!(|| float().prop_map(MaxF64::<PropagateNan>::new));
+        }
+
+        mod min_ignore {
+            use super::*;
+            semilattice_properties!(|| float().prop_map(MinF64::<IgnoreNan>::new));
+        }
+
+        mod min_propagate {
+            use super::*;
+            semilattice_properties!(|| float().prop_map(MinF64::<PropagateNan>::new));
+        }
+
+        proptest! {
+            #[test]
+            fn policies(xs in prop::collection::vec(float(), 0..100)) {
+                let clean = xs.iter().copied().filter(|x| !x.is_nan());
+                let has_nan = xs.iter().any(|x| x.is_nan());
+                let max = clean.clone().fold(f64::NEG_INFINITY, f64::max);
+                let min = clean.fold(f64::INFINITY, f64::min);
+                prop_assert_eq!(fold_map(xs.iter().copied(), MaxF64::<IgnoreNan>::new).get(), max);
+                prop_assert_eq!(fold_map(xs.iter().copied(), MinF64::<IgnoreNan>::new).get(), min);
+                prop_assert_eq!(fold_map(xs.iter().copied(), MaxF64::<PropagateNan>::new).get().is_nan(), has_nan);
+                prop_assert_eq!(fold_map(xs.iter().copied(), MinF64::<PropagateNan>::new).get().is_nan(), has_nan);
+            }
+        }
+    }
+
+    mod bitwise {
+        use super::*;
+        use crate::newtypes::{BitAndM, BitOrM, BitXorM};
+
+        mod or {
+            use super::*;
+            semilattice_properties!(|| any::<u32>().prop_map(BitOrM));
+        }
+
+        mod and {
+            use super::*;
+            semilattice_properties!(|| any::<i8>().prop_map(BitAndM));
+        }
+
+        mod xor {
+            use super::*;
+            group_properties!(|| any::<u128>().prop_map(BitXorM));
+
+            mod ring {
+                use super::*;
+                ring_properties!(|| any::<i64>().prop_map(BitXorM));
+            }
+        }
+    }
+
+    mod arg_extrema {
+        use super::*;
+        use crate::newtypes::{ArgMax, ArgMin};
+        use prop::collection::vec;
+
+        fn entry() -> impl Strategy<Value = Option<(u8, String)>> {
+            any::<Option<(u8, String)>>().prop_map(|e| e.map(|(k, v)| (k % 8, v)))
+        }
+
+        mod arg_max {
+            use super::*;
+            monoid_properties!(|| entry().prop_map(ArgMax));
+            semigroup_assign_properties!(|| entry().prop_map(ArgMax));
+            op_owned_properties!(|| entry().prop_map(ArgMax));
+
+            mod band {
+                use super::*;
+                band_properties!(|| entry().prop_map(ArgMax));
+            }
+        }
+
+        mod arg_min {
+            use super::*;
+            monoid_properties!(|| entry().prop_map(ArgMin));
+            semigroup_assign_properties!(|| entry().prop_map(ArgMin));
+            op_owned_properties!(|| entry().prop_map(ArgMin));
+
+            mod band {
+                use super::*;
+                band_properties!(|| entry().prop_map(ArgMin));
+            }
+        }
+
+        #[test]
+        fn ties_keep_the_first() {
+            let scores = [("ann", 3), ("bob", 5), ("cat", 1), ("dan", 5), ("eve", 1)];
+            let best = fold_map(scores.iter(), |&(who, score)| ArgMax::new(score, who));
+            let worst = fold_map(scores.iter(), |&(who, score)| ArgMin::new(score, who));
+            assert_eq!((best.key(), best.value()), (Some(&5), Some(&"bob")));
+            assert_eq!((worst.key(), worst.value()), (Some(&1), Some(&"cat")));
+        }
+
+        proptest! {
+            #[test]
+            fn matches_iterator_extrema(xs in vec(any::<u8>(), 0..100)) {
+                let best = fold_map(xs.iter().enumerate(), |(i, &x)| ArgMax::new(x, i));
+                let worst = fold_map(xs.iter().enumerate(), |(i, &x)| ArgMin::new(x, i));
+                // `Iterator::max_by_key` keeps the last maximum, so search from the back for the first.
+                let first_max = xs.iter().enumerate().rev().max_by_key(|(_, &x)| x).map(|(i, &x)| (x, i));
+                prop_assert_eq!(best.0, first_max);
+                prop_assert_eq!(worst.0, xs.iter().enumerate().min_by_key(|(_, &x)| x).map(|(i, &x)| (x, i)));
+            }
+        }
+    }
+
+    mod first_last {
+        use super::*;
+        use crate::newtypes::{First, Last};
+        use prop::collection::vec;
+        use std::collections::HashMap;
+
+        mod first {
+            use super::*;
+            monoid_properties!(|| any::<Option<String>>().prop_map(First));
+            semigroup_assign_properties!(|| any::<Option<String>>().prop_map(First));
+            op_owned_properties!(|| any::<Option<String>>().prop_map(First));
+
+            mod band {
+                use super::*;
+                band_properties!(|| any::<Option<String>>().prop_map(First));
+            }
+        }
+
+        mod last {
+            use super::*;
+            monoid_properties!(|| any::<Option<String>>().prop_map(Last));
+            semigroup_assign_properties!(|| any::<Option<String>>().prop_map(Last));
+            op_owned_properties!(|| any::<Option<String>>().prop_map(Last));
+
+            mod band {
+                use super::*;
+                band_properties!(|| any::<Option<String>>().prop_map(Last));
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn earliest_and_latest_per_key(events in vec((0u8..4, any::<u32>()), 0..100)) {
+                let seen: HashMap<u8, (First<u32>, Last<u32>)> =
+                    fold_map(events.iter(), |&(k, t)| HashMap::from([(k, (First::new(t), Last::new(t)))]));
+                for (k, (First(first), Last(last))) in seen {
+                    let ts = events.iter().filter(|e| e.0 == k).map(|e| e.1).collect::<Vec<_>>();
+                    prop_assert_eq!(first.as_ref(), ts.first());
+                    prop_assert_eq!(last.as_ref(), ts.last());
+                }
+            }
+        }
+    }
+
+    mod compensated {
+        use super::*;
+        use crate::compensated::CompensatedSum;
+        use prop::collection::vec;
+        use rayon::prelude::*;
+
+        group_properties!(|| vec(-1e3..1e3, 0..100).prop_map(CompensatedSum::from_iter));
+        semigroup_assign_properties!(|| vec(-1e3..1e3, 0..100).prop_map(CompensatedSum::from_iter));
+
+        #[test]
+        fn recovers_lost_bits() {
+            let xs = [1e16, 1.0, -1e16];
+            assert_eq!(xs.iter().sum::<f64>(), 0.0);
+            assert_eq!(xs.iter().collect::<CompensatedSum>().value(), 1.0);
+        }
+
+        proptest! {
+            #[test]
+            fn parallel_agrees_with_sequential(xs in vec(-1e10..1e10, 0..1000)) {
+                let sequential = xs.iter().collect::<CompensatedSum>();
+                let parallel: CompensatedSum = xs
+                    .par_chunks(4)
+                    .map(|c| c.iter().collect())
+                    .reduce(Monoid::zero, |s, t| Semigroup::op(&s, &t));
+                prop_assert_eq!(sequential, parallel);
+            }
+        }
+    }
+
+    mod ordering {
+        use super::*;
+        use prop::collection::vec;
+        use std::cmp::Ordering;
+
+        fn ordering() -> impl Strategy<Value = Ordering> {
+            prop_oneof![
+                Just(Ordering::Less),
+                Just(Ordering::Equal),
+                Just(Ordering::Greater)
+            ]
+        }
+
+        monoid_properties!(ordering);
+
+        mod band {
+            use super::*;
+            band_properties!(ordering);
+        }
+
+        proptest! {
+            #[test]
+            fn chains_comparators(mut xs in vec(any::<(u8, i8, bool)>(), 0..100)) {
+                let mut ys = xs.clone();
+                xs.sort_by(|a, b| fold_map(
+                    [a.2.cmp(&b.2), b.0.cmp(&a.0), a.1.cmp(&b.1)].into_iter(),
+                    |o| o
+                ));
+                ys.sort_by_key(|&(u, i, b)| (b, std::cmp::Reverse(u), i));
+                prop_assert_eq!(xs, ys);
+            }
+        }
+    }
+
+    mod time {
+        use super::*;
+        use crate::newtypes::{Earliest, Latest};
+        use std::collections::HashMap;
+        use std::time::{Duration, Instant, UNIX_EPOCH};
+
+        fn duration() -> impl Strategy<Value = Duration> {
+            (any::<u64>(), 0u32..1_000_000_000).prop_map(|(s, n)| Duration::new(s, n))
+        }
+
+        monus_properties!(duration);
+        semigroup_assign_properties!(duration);
+        mod ordered {
+            use super::*;
+            ordered_monoid_properties!(duration);
+        }
+
+        mod earliest {
+            use super::*;
+            semilattice_properties!(|| any::<Option<u32>>()
+                .prop_map(|t| Earliest(t.map(|s| UNIX_EPOCH + Duration::from_secs(s as u64)))));
+        }
+
+        mod latest {
+            use super::*;
+            semilattice_properties!(|| {
+                let now = Instant::now();
+                any::<Option<u32>>()
+                    .prop_map(move |t| Latest(t.map(|ms| now + Duration::from_millis(ms as u64))))
+            });
+        }
+
+        #[test]
+        fn per_key_latency_and_last_seen() {
+            let t = |s| Some(UNIX_EPOCH + Duration::from_secs(s));
+            let events = [("a", 3, 10), ("b", 5, 20), ("a", 1, 30), ("b", 2, 15)];
+            let stats = fold_map(events.iter(), |&(k, secs, ms)| {
+                let seen = t(secs);
+                HashMap::from([(k, (Duration::from_millis(ms), Earliest(seen), Latest(seen)))])
+            });
+            let expect = |ms, first, last| {
+                (
+                    Duration::from_millis(ms),
+                    Earliest(t(first)),
+                    Latest(t(last)),
+                )
+            };
+            assert_eq!(stats["a"], expect(40, 1, 3));
+            assert_eq!(stats["b"], expect(35, 2, 5));
+        }
+    }
+
+    mod trivial {
+        use super::*;
+        use std::marker::PhantomData;
+
+        mod unit {
+            use super::*;
+            group_properties!(any::<()>);
+        }
+
+        mod phantom {
+            use super::*;
+            semilattice_properties!(|| Just(PhantomData::<String>));
+        }
+    }
+
+    mod reverse {
+        use super::*;
+        use crate::newtypes::{Max, Sum};
+        use std::cmp::Reverse;
+
+        semilattice_properties!(|| any::<i32>().prop_map(|x| Reverse(Max(x))));
+        op_owned_properties!(|| any::<String>().prop_map(Reverse));
+
+        mod ordered {
+            use super::*;
+            ordered_monoid_properties!(|| any::<u32>().prop_map(|x| Reverse(Sum(x as u64))));
+        }
+    }
+
+    mod pointers {
+        use super::*;
+        use crate::newtypes::{Max, Sum};
+        use std::{rc::Rc, sync::Arc};
+
+        mod boxed {
+            use super::*;
+            group_properties!(|| any::<i32>().prop_map(|x| Box::new(Sum(x as i64))));
+            semigroup_assign_properties!(|| any::<String>().prop_map(Box::new));
+            op_owned_properties!(|| any::<String>().prop_map(Box::new));
+        }
+
+        mod rc {
+            use super::*;
+            semilattice_properties!(|| any::<u8>().prop_map(|x| Rc::new(Max(x))));
+            semigroup_assign_properties!(|| any::<String>().prop_map(Rc::new));
+            op_owned_properties!(|| any::<String>().prop_map(Rc::new));
+        }
+
+        mod arc {
+            use super::*;
+            monoid_properties!(|| any::<Vec<u8>>().prop_map(Arc::new));
+            semigroup_assign_properties!(|| any::<Vec<u8>>().prop_map(Arc::new));
+            op_owned_properties!(|| any::<Vec<u8>>().prop_map(Arc::new));
+        }
+    }
+
+    mod cow {
+        use super::*;
+        use std::borrow::Cow;
+
+        fn text() -> impl Strategy<Value = Cow<'static, str>> {
+            prop_oneof![
+                prop_oneof![Just(""), Just("log: "), Just("\n")].prop_map(Cow::Borrowed),
+                any::<String>().prop_map(Cow::Owned),
+            ]
+        }
+
+        fn bytes() -> impl Strategy<Value = Cow<'static, [u8]>> {
+            prop_oneof![
+                prop_oneof![Just(&b""[..]), Just(&b"abc"[..])].prop_map(Cow::Borrowed),
+                any::<Vec<u8>>().prop_map(Cow::Owned),
+            ]
+        }
+
+        mod str {
+            use super::*;
+            monoid_properties!(text);
+            semigroup_assign_properties!(text);
+            op_owned_properties!(text);
+        }
+
+        mod slice {
+            use super::*;
+            monoid_properties!(bytes);
+            semigroup_assign_properties!(bytes);
+            op_owned_properties!(bytes);
+        }
+
+        proptest! {
+            #[test]
+            fn empty_stays_borrowed(x in text()) {
+                if let Cow::Borrowed(_) = x {
+                    prop_assert!(matches!(Semigroup::op(&x, &Monoid::zero()), Cow::Borrowed(_)));
+                    prop_assert!(matches!(Semigroup::op(&Monoid::zero(), &x), Cow::Borrowed(_)));
+                }
+            }
+        }
+    }
+
+    mod gaussian {
+        use super::*;
+        use crate::gaussian::Gaussian;
+        use prop::collection::vec;
+        use rayon::prelude::*;
+        use std::iter::FromIterator;
+
+        group_properties!(|| vec(-1e3..1e3, 0..1000).prop_map(Gaussian::from_iter));
+        semigroup_assign_properties!(|| vec(-1e3..1e3, 0..1000).prop_map(Gaussian::from_iter));
+
+        struct Fit;
+        impl MonoidHom<Vec<f64>, Gaussian> for Fit {
+            fn apply(xs: &Vec<f64>) -> Gaussian {
+                xs.iter().collect()
+            }
+        }
+
+        mod fit {
+            use super::*;
+            homomorphism_properties!(Fit, || vec(-1e3..1e3, 0..1000));
+        }
+
+        proptest! {
+            #[test]
+            fn homomorphisms_and_associativity_are_cool(xs in vec(-1e3..1e3, 0..1000)) {
+                let from_iter = xs.iter().collect();
+                let by_hand = xs.iter().fold(Gaussian::default(), |g, &x| g + x);
+                let mapped = xs.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2));
+                let mapped_chunks = xs
+                    .chunks(4)
+                    .map(|c| c.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2)))
+                    .fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2));
+                let mapped_par_chunks = xs
+                    .par_chunks(4)
+                    .map(|c| c.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2)))
+                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
+                let sharper_par = xs
+                    .par_chunks(4)
+                    .map(|c| {let mut g = Gaussian::default(); c.iter().for_each(|&x| g += x); g})
+                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
+                let par_from_iter = xs
+                    .par_chunks(4)
+                    .map(|c| c.iter().collect())
+                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
+                for w in [from_iter, by_hand, mapped, mapped_chunks, mapped_par_chunks, sharper_par, par_from_iter].windows(2) {
+                    prop_assert_eq!(w[0], w[1]);
+                }
+            }
+        }
+
+        fn fitted(mean: f64, sd: f64) -> Gaussian {
+            let s = sd * 0.5f64.sqrt();
+            [mean - s, mean + s].iter().collect()
+        }
+
+        fn standard() -> Gaussian {
+            fitted(0.0, 1.0)
+        }
+
+        #[test]
+        fn batches_are_accurate() {
+            let xs: Vec<f64> = (0..1_000_000).map(|i| 1e9 + f64::from(i % 4)).collect();
+            let exact = 1.25 * 1e6 / (1e6 - 1.0);
+            let mut batched = Gaussian::default();
+            batched.extend_from_slice(&xs);
+            assert!((batched.mean() - (1e9 + 1.5)).abs() < 1e-6);
+            assert!((batched.variance() - exact).abs() < 1e-9);
+        }
+
+        #[test]
+        fn small_samples() {
+            let empty = Gaussian::<f64>::default();
+            assert_eq!(empty.try_variance(), None);
+            let one = Gaussian::new(3.0);
+            assert_eq!(one.try_variance(), None);
+            assert_eq!(one.population_variance(), 0.0);
+            let g: Gaussian = [1.0, 3.0].iter().collect();
+            assert_eq!(g.try_variance(), Some(2.0));
+            assert_eq!(g.population_variance(), 1.0);
+            assert_eq!(g.std_dev(), 2f64.sqrt());
+            assert_eq!(g.population_std_dev(), 1.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Variance requires more than 1 sample.")]
+        fn variance_of_one_panics() {
+            Gaussian::new(3.0).variance();
+        }
+
+        #[test]
+        fn intervals_known_values() {
+            let g: Gaussian = (0..11).map(f64::from).collect();
+            assert_eq!(g.std_error(), 1.0);
+            let (lo, hi) = g.mean_ci(0.95);
+            assert!(
+                (lo - (5.0 - 2.228_138_851_986_274)).abs() < 1e-12
+                    && (hi - (5.0 + 2.228_138_851_986_274)).abs() < 1e-12
+            );
+            let (lo, hi) = g.confidence_interval(0.95);
+            assert!(
+                (lo - (5.0 - 1.959_963_984_540_054)).abs() < 1e-12
+                    && (hi - (5.0 + 1.959_963_984_540_054)).abs() < 1e-12
+            );
+            let (_, hi) = standard().mean_ci(0.95);
+            assert!((hi - 12.706_204_736_174_698 * 0.5f64.sqrt()).abs() < 1e-10);
+        }
+
+        #[test]
+        fn divergences_known_values() {
+            let (p, q) = (standard(), fitted(1.0, 2.0));
+            assert!((p.kl_divergence(&q) - 0.4431471805599453).abs() < 1e-12);
+            assert!((p.hellinger(&q) - 0.38625708776326656).abs() < 1e-12);
+            assert!((p.wasserstein2(&q) - 2f64.sqrt()).abs() < 1e-12);
+        }
+
+        proptest! {
+            #[test]
+            fn batches_agree(xs in vec(-1e3..1e3, 0..1000), ys in vec(-1e3..1e3, 0..1000)) {
+                let mut batched: Gaussian = xs.iter().collect();
+                batched.extend_from_slice(&ys);
+                let mut extended: Gaussian = xs.iter().collect();
+                extended.extend(&ys);
+                prop_assert_eq!(batched, extended);
+                prop_assert_eq!(extended, xs.iter().chain(&ys).collect());
+            }
+            #[test]
+            fn removing_slides_the_window(xs in vec(-1e3..1e3, 0..100), k in 0usize..100) {
+                let k = k.min(xs.len());
+                let mut g: Gaussian = xs.iter().collect();
+                xs[..k].iter().for_each(|&x| g.remove(x));
+                prop_assert_eq!(g, xs[k..].iter().collect());
+            }
+            #[test]
+            fn subtracting_undoes_merging(xs in vec(-1e3..1e3, 2..100), ys in vec(-1e3..1e3, 0..100)) {
+                let (g, h): (Gaussian, Gaussian) = (xs.iter().collect(), ys.iter().collect());
+                let mut merged = Semigroup::op(&g, &h);
+                merged.subtract(&h);
+                prop_assert_eq!(merged, g);
+            }
+        }
+
+        #[test]
+        fn quantile_known_values() {
+            let g = standard();
+            assert_eq!(g.quantile(0.5), 0.0);
+            assert!((g.quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-14);
+            assert!((g.quantile(1e-10) + 6.361_340_902_404_056).abs() < 1e-12);
+            assert_eq!(g.quantile(0.0), f64::NEG_INFINITY);
+            assert_eq!(g.quantile(1.0), f64::INFINITY);
+            assert!(g.quantile(1.5).is_nan());
+        }
+
+        #[test]
+        fn log_space_tails() {
+            let g = standard();
+            assert_eq!(g.cdf(-40.0), 0.0);
+            assert!((g.log_cdf(-40.0) / -804.608_442_013_753_8 - 1.0).abs() < 1e-12);
+            assert!((g.log_cdf(-10.0) / -53.231_285_150_512_46 - 1.0).abs() < 1e-12);
+            assert!((g.log_cdf(-20.0 - 1e-9) / g.log_cdf(-20.0 + 1e-9) - 1.0).abs() < 1e-9);
+            assert_eq!(g.log_sf(40.0), g.log_cdf(-40.0));
+            assert!((g.sf(10.0) / g.log_cdf(-10.0).exp() - 1.0).abs() < 1e-12);
+            assert!(
+                (g.log_pdf(40.0) - (-800.0 - 0.5 * (2.0 * std::f64::consts::PI).ln())).abs()
+                    < 1e-12
+            );
+        }
+
+        proptest! {
+            #[test]
+            fn t_intervals_are_wider(xs in vec(-1e3..1e3, 2..100), level in 0.5..0.999) {
+                let g: Gaussian = xs.iter().collect();
+                prop_assume!(g.variance() > 1e-6);
+                let (t_lo, t_hi) = g.mean_ci(level);
+                let (z_lo, z_hi) = g.confidence_interval(level);
+                prop_assert!(t_lo < z_lo && z_hi < t_hi);
+                prop_assert!(t_lo < g.mean() && g.mean() < t_hi);
+            }
+            #[test]
+            fn divergences_are_sensible(xs in vec(-1e3..1e3, 2..100), ys in vec(-1e3..1e3, 2..100)) {
+                let (p, q): (Gaussian, Gaussian) = (xs.iter().collect(), ys.iter().collect());
+                prop_assume!(p.variance() > 1e-6 && q.variance() > 1e-6);
+                prop_assert!(p.kl_divergence(&p).abs() < 1e-12);
+                prop_assert!(p.kl_divergence(&q) >= 0.0);
+                prop_assert!(p.hellinger(&p) < 1e-6);
+                prop_assert!((0.0..=1.0).contains(&p.hellinger(&q)));
+                prop_assert_eq!(p.hellinger(&q), q.hellinger(&p));
+                prop_assert_eq!(p.wasserstein2(&p), 0.0);
+                prop_assert_eq!(p.wasserstein2(&q), q.wasserstein2(&p));
+            }
+            #[test]
+            fn log_space_agrees(xs in vec(-1e3..1e3, 2..100), x in -1e3..1e3) {
+                let g: Gaussian = xs.iter().collect();
+                prop_assume!(g.variance() > 1e-6);
+                prop_assert!((g.cdf(x) + g.sf(x) - 1.0).abs() < 1e-12);
+                prop_assert!((g.log_cdf(x).exp() - g.cdf(x)).abs() < 1e-12);
+                prop_assert!((g.log_sf(x).exp() - g.sf(x)).abs() < 1e-12);
+                prop_assert!((g.log_pdf(x).exp() - g.pdf(x)).abs() < 1e-12 * (1.0 + g.pdf(x)));
+            }
+            #[test]
+            fn quantile_inverts_cdf(xs in vec(-1e3..1e3, 2..100), p in 1e-6..(1.0 - 1e-6)) {
+                let g: Gaussian = xs.iter().collect();
+                prop_assume!(g.variance() > 1e-6);
+                prop_assert!((g.cdf(g.quantile(p)) - p).abs() < 1e-9);
+            }
+        }
+
+        #[test]
+        fn bitwise_equality() {
+            let g: Gaussian = [0.1, 0.2, 0.3].iter().collect();
+            let h: Gaussian = [0.3, 0.2, 0.1].iter().collect();
+            assert_eq!(g, h);
+            assert!(g.bitwise_eq(&g) && !g.bitwise_eq(&h));
+            let nan = Gaussian::new(f64::NAN);
+            assert!(nan.bitwise_eq(&nan) && nan != nan);
+        }
+
+        #[cfg(feature = "approx")]
+        #[test]
+        fn approx_tolerances() {
+            use approx::{
+                assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq,
+            };
+            let g: Gaussian = [1e6, 1e6 + 1.0].iter().collect();
+            let h: Gaussian = [1e6, 1e6 + 1.0 + 1e-6].iter().collect();
+            assert_eq!(g, h);
+            assert_relative_ne!(g, h);
+            assert_relative_eq!(g, h, max_relative = 1e-5);
+            assert_abs_diff_eq!(g, h, epsilon = 1e-5);
+            let k: Gaussian = [1e6 + 1.0, 1e6].iter().collect();
+            assert_ulps_eq!(g, k, max_ulps = 4);
+        }
+
+        #[cfg(feature = "rayon")]
+        proptest! {
+            #[test]
+            fn collects_in_parallel(xs in vec(-1e3..1e3, 0..1000)) {
+                let g: Gaussian = xs.par_iter().collect();
+                prop_assert_eq!(g, xs.iter().collect());
+                prop_assert_eq!(g, xs.into_par_iter().collect());
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        #[test]
+        fn sampling_recovers_the_fit() {
+            use rand::distributions::Distribution;
+            use rand::{rngs::StdRng, SeedableRng};
+            let g: Gaussian = [1.0, 2.0, 3.0, 6.0].iter().collect();
+            let refit: Gaussian = g
+                .sample_iter(StdRng::seed_from_u64(0))
+                .take(100_000)
+                .collect();
+            assert!((refit.mean() - g.mean()).abs() < 0.05);
+            assert!((refit.variance() / g.variance() - 1.0).abs() < 0.05);
+        }
+
+        mod single_precision {
+            use super::*;
+            monoid_properties!(|| vec(1f32..1e2, 0..100).prop_map(Gaussian::from_iter));
+
+            #[test]
+            fn agrees_with_double_precision() {
+                let xs = [1.0, 2.0, 4.0, 8.0];
+                let single: Gaussian<f32> = xs.iter().collect();
+                let double: Gaussian = xs.iter().map(|&x| f64::from(x)).collect();
+                assert_eq!(f64::from(single.mean()), double.mean());
+                assert!((f64::from(single.variance()) - double.variance()).abs() < 1e-5);
+                assert!((f64::from(single.cdf(3.0)) - double.cdf(3.0)).abs() < 1e-6);
+            }
+        }
+    }
+
+    mod distribution {
+        use crate::distribution::Distribution;
+        use crate::exponential::Exponential;
+        use crate::gamma::GammaSummary;
+        use crate::gaussian::Gaussian;
+        use crate::poisson::Poisson;
+        use crate::proportion::Proportion;
+
+        /// The density integrates to the CDF, by Simpson's rule on each interval between `xs`.
+        fn integrates_to_cdf<D: Distribution<Value = f64, Real = f64>>(d: &D, xs: &[f64]) {
+            for w in xs.windows(2) {
+                let (a, b) = (w[0], w[1]);
+                let n = 1000;
+                let h = (b - a) / n as f64;
+                let simpson: f64 = (0..=n)
+                    .map(|i| {
+                        let weight = if i == 0 || i == n {
+                            1.0
+                        } else if i % 2 == 1 {
+                            4.0
+                        } else {
+                            2.0
+                        };
+                        weight * d.pdf(a + i as f64 * h)
+                    })
+                    .sum::<f64>()
+                    * h
+                    / 3.0;
+                assert!((simpson - (d.cdf(b) - d.cdf(a))).abs() < 1e-9);
+                assert!((d.log_pdf(a) - d.pdf(a).ln()).abs() < 1e-12);
+            }
+        }
+
+        #[test]
+        fn continuous() {
+            let data = [1.0, 2.0, 3.0, 6.0];
+            let xs = [0.5, 1.0, 2.5, 4.0, 8.0];
+            integrates_to_cdf(&data.iter().collect::<Gaussian>(), &xs);
+            integrates_to_cdf(&data.iter().collect::<Exponential>(), &xs);
+            integrates_to_cdf(&data.iter().collect::<GammaSummary>(), &xs);
+        }
+
+        #[test]
+        fn gamma_fit_moments() {
+            let g: GammaSummary = [1.0, 2.0, 3.0, 6.0].iter().collect();
+            let (k, theta) = g.mle();
+            assert_eq!(Distribution::mean(&g), 3.0);
+            assert!((Distribution::variance(&g) - k * theta * theta).abs() < 1e-12);
+        }
+
+        #[test]
+        fn discrete() {
+            let p: Poisson = [0, 2, 3, 7].into_iter().collect();
+            let mut total = 0.0;
+            for k in 0..20 {
+                total += Distribution::pdf(&p, k);
+                assert!((total - Distribution::cdf(&p, k)).abs() < 1e-12);
+            }
+            assert_eq!(Distribution::mean(&p), Distribution::variance(&p));
+        }
+
+        #[test]
+        fn bernoulli() {
+            let p: Proportion = [true, false, false, true, false].into_iter().collect();
+            assert_eq!(
+                (Distribution::mean(&p), Distribution::variance(&p)),
+                (0.4, 0.24)
+            );
+            assert_eq!((p.pdf(true), p.pdf(false)), (0.4, 0.6));
+            assert_eq!((p.cdf(false), p.cdf(true)), (0.6, 1.0));
+            assert_eq!(p.log_pdf(true), 0.4f64.ln());
+        }
+
+        /// Sampling reproduces the fitted mean and variance.
+        #[cfg(feature = "rand")]
+        fn sampled_moments<D: crate::distribution::Sample>(d: &D)
+        where
+            D::Value: num_traits::ToPrimitive,
+        {
+            use num_traits::ToPrimitive;
+            use rand::{rngs::StdRng, SeedableRng};
+            let mut rng = StdRng::seed_from_u64(0);
+            let g: Gaussian = (0..100_000)
+                .map(|_| {
+                    rand::distributions::Distribution::sample(d, &mut rng)
+                        .to_f64()
+                        .unwrap()
+                })
+                .collect();
+            assert!((g.mean() / d.mean().to_f64().unwrap() - 1.0).abs() < 0.02);
+            assert!((g.variance() / d.variance().to_f64().unwrap() - 1.0).abs() < 0.05);
+        }
+
+        #[cfg(feature = "rand")]
+        #[test]
+        fn sampling() {
+            let data = [1.0, 2.0, 3.0, 6.0];
+            sampled_moments(&data.iter().collect::<Gaussian>());
+            sampled_moments(&data.iter().collect::<Exponential>());
+            sampled_moments(&data.iter().collect::<GammaSummary>());
+            // A shape below 1 takes the boosted path.
+            sampled_moments(&[0.01, 1.0, 5.0, 20.0].iter().collect::<GammaSummary>());
+            sampled_moments(&[0, 2, 3, 7].into_iter().collect::<Poisson>());
+            let p: Proportion = [true, false, false, true, false].into_iter().collect();
+            let mut rng = rand::thread_rng();
+            let draws: Proportion = (0..100_000)
+                .map(|_| rand::distributions::Distribution::sample(&p, &mut rng))
+                .collect();
+            assert!((draws.p_hat() - 0.4).abs() < 0.01);
+        }
+    }
+
+    mod exponential {
+        use super::*;
+        use crate::exponential::Exponential;
+        use prop::collection::vec;
+
+        group_properties!(|| vec(0.0..1e3, 0..100).prop_map(Exponential::from_iter));
+        semigroup_assign_properties!(|| vec(0.0..1e3, 0..100).prop_map(Exponential::from_iter));
+        mod commutative {
+            use super::*;
+            commutative_monoid_properties!(
+                || vec(0.0..1e3, 0..100).prop_map(Exponential::from_iter)
+            );
+        }
+
+        #[test]
+        fn known_values() {
+            let e: Exponential = [1.0, 2.0, 3.0, 6.0].iter().collect();
+            assert_eq!(
+                (e.count(), e.sum(), e.mean(), e.rate()),
+                (4.0, 12.0, 3.0, 1.0 / 3.0)
+            );
+            assert_eq!(e.variance(), 9.0);
+            assert!((e.pdf(3.0) - (-1.0f64).exp() / 3.0).abs() < 1e-15);
+            assert!((e.log_pdf(3.0) - e.pdf(3.0).ln()).abs() < 1e-15);
+            assert!((e.cdf(3.0) - (1.0 - (-1.0f64).exp())).abs() < 1e-15);
+            assert!((e.cdf(3.0) + e.sf(3.0) - 1.0).abs() < 1e-15);
+            assert!((e.quantile(0.5) - 3.0 * 2f64.ln()).abs() < 1e-14);
+            assert_eq!(
+                (e.pdf(-1.0), e.log_pdf(-1.0), e.cdf(-1.0), e.sf(-1.0)),
+                (0.0, f64::NEG_INFINITY, 0.0, 1.0)
+            );
+            assert_eq!(e.quantile(1.0), f64::INFINITY);
+            assert!(e.sf(1e3) > 0.0 && e.cdf(1e3) == 1.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Rate requires at least 1 sample.")]
+        fn empty_rate() {
+            Exponential::<f64>::default().rate();
+        }
+
+        #[cfg(feature = "rand")]
+        #[test]
+        fn sampling_recovers_the_fit() {
+            use rand::distributions::Distribution;
+            use rand::{rngs::StdRng, SeedableRng};
+            let e = Exponential::new(0.5) + 1.5;
+            let refit: Exponential = e
+                .sample_iter(StdRng::seed_from_u64(0))
+                .take(100_000)
+                .collect();
+            assert!((refit.rate() / e.rate() - 1.0).abs() < 0.02);
+        }
+
+        proptest! {
+            #[test]
+            fn quantile_inverts_cdf(xs in vec(1e-3..1e3, 1..100), p in 0.0..1.0f64) {
+                let e: Exponential = xs.iter().collect();
+                prop_assert!((e.cdf(e.quantile(p)) - p).abs() < 1e-12);
+            }
+        }
+    }
+
+    mod poisson {
+        use super::*;
+        use crate::poisson::Poisson;
+        use prop::collection::vec;
+
+        commutative_monoid_properties!(|| vec(0u64..1_000, 0..100).prop_map(Poisson::from_iter));
+        semigroup_assign_properties!(|| vec(0u64..1_000, 0..100).prop_map(Poisson::from_iter));
+
+        #[test]
+        fn known_values() {
+            let p: Poisson = [1, 2, 3, 6].iter().collect();
+            assert_eq!(
+                (p.count(), p.total(), p.lambda(), p.variance()),
+                (4, 12, 3.0, 3.0)
+            );
+            assert!((p.pmf(2) - 4.5 * (-3.0f64).exp()).abs() < 1e-15);
+            assert!((p.cdf(2) - 8.5 * (-3.0f64).exp()).abs() < 1e-15);
+            assert!((p.cdf(100) - 1.0).abs() < 1e-15);
+            assert!(p.log_pmf(1_000) < -3_000.0);
+            let none: Poisson = [0, 0].iter().collect();
+            assert_eq!((none.pmf(0), none.pmf(1), none.cdf(0)), (1.0, 0.0, 1.0));
+        }
+
+        #[test]
+        #[should_panic(expected = "Rate requires at least 1 sample.")]
+        fn empty_rate() {
+            Poisson::default().lambda();
+        }
+
+        #[cfg(feature = "rand")]
+        #[test]
+        fn sampling_recovers_the_fit() {
+            use rand::distributions::Distribution;
+            use rand::{rngs::StdRng, SeedableRng};
+            for p in [Poisson::new(3), Poisson::new(0) + 1, Poisson::new(10_000)] {
+                let refit: Poisson = p
+                    .sample_iter(StdRng::seed_from_u64(0))
+                    .take(10_000)
+                    .collect();
+                assert!((refit.lambda() - p.lambda()).abs() < 0.05 * p.lambda().max(1.0));
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn cdf_sums_pmf(ks in vec(0u64..50, 1..20), k in 0u64..100) {
+                let p: Poisson = ks.iter().collect();
+                let summed: f64 = (0..=k).map(|i| p.pmf(i)).sum();
+                prop_assert!((p.cdf(k) - summed).abs() < 1e-12);
+            }
+        }
+    }
+
+    mod proportion {
+        use super::*;
+        use crate::proportion::Proportion;
+        use crate::stats::tests::two_proportion_z_test;
+        use prop::collection::vec;
+
+        commutative_monoid_properties!(
+            || vec(any::<bool>(), 0..100).prop_map(Proportion::from_iter)
+        );
+        semigroup_assign_properties!(|| vec(any::<bool>(), 0..100).prop_map(Proportion::from_iter));
+
+        #[test]
+        fn intervals() {
+            let p = Proportion::of(8, 10);
+            assert_eq!((p.p_hat(), p.failures()), (0.8, 2));
+            let (lo, hi) = p.wilson_interval(0.95);
+            assert!((lo - 0.490_162).abs() < 1e-6 && (hi - 0.943_318).abs() < 1e-6);
+            let (lo, hi) = p.jeffreys_interval(0.95);
+            assert!((lo - 0.497_226).abs() < 1e-5 && (hi - 0.955_941).abs() < 1e-5);
+            let none = Proportion::new(false) + false;
+            assert_eq!(none.jeffreys_interval(0.9).0, 0.0);
+            assert_eq!(none.wilson_interval(0.9).0, 0.0);
+            assert_eq!(Proportion::of(3, 3).jeffreys_interval(0.9).1, 1.0);
+        }
+
+        #[test]
+        fn ab_test() {
+            let a = Proportion::of(45, 100);
+            let b: Proportion = (0..100).map(|i| i % 10 < 3).collect();
+            let z = two_proportion_z_test(&a, &b);
+            assert!((z.statistic - 2.190_890_230_020_664_7).abs() < 1e-12);
+            assert!((z.p_value - 0.028_459_736_916_310_57).abs() < 1e-12);
+            let all = Proportion::of(5, 5);
+            assert_eq!(two_proportion_z_test(&all, &all).p_value, 1.0);
+        }
+
+        #[test]
+        #[should_panic(expected = "Successes can't exceed trials.")]
+        fn too_many_successes() {
+            Proportion::of(2, 1);
+        }
+
+        proptest! {
+            #[test]
+            fn intervals_cover_estimate(s in 0u64..200, f in 0u64..200, level in 0.5..0.999) {
+                prop_assume!(s + f > 0);
+                let p = Proportion::of(s, s + f);
+                for (lo, hi) in [p.wilson_interval(level), p.jeffreys_interval(level)] {
+                    prop_assert!(0.0 <= lo && lo <= p.p_hat() && p.p_hat() <= hi && hi <= 1.0);
+                }
+            }
+        }
+    }
+
+    mod gamma {
+        use super::*;
+        use crate::gamma::GammaSummary;
+        use crate::special::{digamma, gamma_p, trigamma};
+        use prop::collection::vec;
+
+        group_properties!(|| vec(1e-2..1e2, 0..100).prop_map(GammaSummary::from_iter));
+        semigroup_assign_properties!(|| vec(1e-2..1e2, 0..100).prop_map(GammaSummary::from_iter));
+
+        #[test]
+        fn polygamma() {
+            let euler = 0.577_215_664_901_532_9;
+            assert!((digamma(1.0) + euler).abs() < 1e-13);
+            assert!((digamma(0.5) + euler + 2.0 * 2f64.ln()).abs() < 1e-13);
+            assert!((digamma(100.0) - 4.600_161_852_738_087).abs() < 1e-14);
+            assert!((trigamma(1.0) - std::f64::consts::PI.powi(2) / 6.0).abs() < 1e-13);
+            assert!((trigamma(0.5) - std::f64::consts::PI.powi(2) / 2.0).abs() < 1e-13);
+        }
+
+        #[test]
+        fn recovers_parameters() {
+            // Evenly spaced quantiles of shape 3, scale 2 stand in for a sample, summarized in shards.
+            let quantile = |p: f64| {
+                let (mut lo, mut hi) = (0.0, 100.0);
+                for _ in 0..60 {
+                    let mid = 0.5 * (lo + hi);
+                    if gamma_p(3.0, mid / 2.0) < p {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                lo
+            };
+            let xs: Vec<f64> = (0..10_000)
+                .map(|i| quantile((i as f64 + 0.5) / 10_000.0))
+                .collect();
+            let g = xs
+                .chunks(1_000)
+                .map(|c| c.iter().collect::<GammaSummary>())
+                .fold(GammaSummary::zero(), |a, b| Semigroup::op(&a, &b));
+            let (k, theta) = g.mle();
+            assert!(
+                (k - 3.0).abs() < 0.1 && (theta - 2.0).abs() < 0.1,
+                "{} {}",
+                k,
+                theta
+            );
+            let (k, theta) = g.method_of_moments();
+            assert!(
+                (k - 3.0).abs() < 0.1 && (theta - 2.0).abs() < 0.1,
+                "{} {}",
+                k,
+                theta
+            );
+            assert!((g.cdf(6.0) + g.sf(6.0) - 1.0).abs() < 1e-12);
+            assert!((g.cdf(6.0) - 0.5768).abs() < 0.01);
+            assert_eq!(g.pdf(-1.0), 0.0);
+        }
+
+        #[test]
+        fn density_matches_fit() {
+            let g: GammaSummary = [1.0, 2.0, 4.0].iter().collect();
+            let (k, theta) = g.mle();
+            let x: f64 = 1.5;
+            let closed_form =
+                x.powf(k - 1.0) * (-x / theta).exp() / (libm::tgamma(k) * theta.powf(k));
+            assert!((g.pdf(x) - closed_form).abs() < 1e-12);
+            let h = 1e-5;
+            assert!(((g.cdf(x + h) - g.cdf(x - h)) / (2.0 * h) - g.pdf(x)).abs() < 1e-8);
+        }
+
+        #[test]
+        #[should_panic(expected = "Gamma-distributed data must be positive.")]
+        fn rejects_non_positive() {
+            let _ = GammaSummary::new(1.0) + 0.0;
+        }
+
+        proptest! {
+            #[test]
+            fn mle_solves_likelihood_equation(xs in vec(1e-2..1e2, 2..100)) {
+                let g: GammaSummary = xs.iter().collect();
+                let s = g.mean().ln() - g.mean_log();
+                prop_assume!(s > 1e-6);
+                let (k, theta) = g.mle();
+                prop_assert!((k.ln() - digamma(k) - s).abs() < 1e-9 * s.max(1.0));
+                prop_assert!((k * theta - g.mean()).abs() < 1e-9 * g.mean());
+            }
+        }
+    }
+
+    mod anomaly {
+        use super::*;
+        use crate::anomaly::AnomalyScorer;
+        use crate::gaussian::Gaussian;
+        use prop::collection::vec;
+
+        fn scorer() -> impl Strategy<Value = AnomalyScorer> {
+            vec(-1e3..1e3, 0..100).prop_map(|xs| Gaussian::from_iter(xs).into())
+        }
+
+        monoid_properties!(scorer);
+        semigroup_assign_properties!(scorer);
+
+        #[test]
+        fn scores_streams() {
+            let mut s: AnomalyScorer = AnomalyScorer::new();
+            assert_eq!(s.score_and_observe(1.0), None);
+            assert_eq!(s.score_and_observe(1.0), None);
+            let constant = s.score(1.0).unwrap();
+            assert_eq!((constant.z, constant.tail), (0.0, 1.0));
+            assert_eq!(s.score(2.0).unwrap().z, f64::INFINITY);
+            s.observe(4.0);
+            let score = s.score(8.0).unwrap();
+            assert!((score.z - 2.0 * 3f64.sqrt()).abs() < 1e-12);
+            assert!((score.tail - 5.320_055_051_392_503e-4).abs() < 1e-15);
+        }
+
+        proptest! {
+            #[test]
+            fn merged_scorers_agree(xs in vec(-1e3..1e3, 0..100), ys in vec(-1e3..1e3, 0..100), x in -1e3..1e3) {
+                let mut whole: AnomalyScorer = AnomalyScorer::new();
+                xs.iter().chain(&ys).for_each(|&x| whole.observe(x));
+                let (mut left, mut right) = (AnomalyScorer::new(), AnomalyScorer::new());
+                xs.iter().for_each(|&x| left.observe(x));
+                ys.iter().for_each(|&y| right.observe(y));
+                let merged = Semigroup::op(&left, &right);
+                prop_assert_eq!(merged, whole);
+                match (merged.score(x), whole.score(x)) {
+                    (Some(m), Some(w)) => prop_assert!((m.tail - w.tail).abs() < 1e-6),
+                    (m, w) => prop_assert_eq!(m, w),
+                }
+            }
+        }
+    }
+
+    mod multivariate {
+        use super::*;
+        use crate::gaussian::Gaussian;
+        use crate::multivariate::MultivariateGaussian;
+        use prop::{array::uniform3, collection::vec};
+
+        fn mvn() -> impl Strategy<Value = MultivariateGaussian<3>> {
+            vec(uniform3(-1e3..1e3), 0..100).prop_map(MultivariateGaussian::from_iter)
+        }
+
+        commutative_monoid_properties!(mvn);
+        semigroup_assign_properties!(mvn);
+
+        #[test]
+        fn independent_coordinates() {
+            let corners = [[-1.0, -2.0], [-1.0, 2.0], [1.0, -2.0], [1.0, 2.0]];
+            let g: MultivariateGaussian<2> = corners.iter().collect();
+            let close = |a: [[f64; 2]; 2], b: [[f64; 2]; 2]| {
+                (0..4).all(|k| (a[k / 2][k % 2] - b[k / 2][k % 2]).abs() < 1e-15)
+            };
+            assert_eq!(g.mean(), [0.0, 0.0]);
+            assert!(close(g.covariance(), [[4.0 / 3.0, 0.0], [0.0, 16.0 / 3.0]]));
+            assert!(close(g.correlation(), [[1.0, 0.0], [0.0, 1.0]]));
+            let xs: Gaussian = corners.iter().map(|p| p[0]).collect();
+            let ys: Gaussian = corners.iter().map(|p| p[1]).collect();
+            let p = [0.5, -3.0];
+            assert!((g.pdf(&p) - xs.pdf(p[0]) * ys.pdf(p[1])).abs() < 1e-15);
+        }
+
+        #[test]
+        fn perfectly_correlated() {
+            let g: MultivariateGaussian<2> = [[0.0, 1.0], [1.0, 3.0], [2.0, 5.0]].iter().collect();
+            let r = g.correlation();
+            assert!((r[0][1] - 1.0).abs() < 1e-15 && (r[1][0] - 1.0).abs() < 1e-15);
+        }
+
+        proptest! {
+            #[test]
+            fn marginals_match(ps in vec(uniform3(-1e3..1e3), 2..100), x in uniform3(-1e3..1e3)) {
+                let g: MultivariateGaussian<3> = ps.iter().collect();
+                for i in 0..3 {
+                    let marginal: Gaussian = ps.iter().map(|p| p[i]).collect();
+                    prop_assert!((g.mean()[i] - marginal.mean()).abs() < 1e-9);
+                    prop_assert!((g.covariance()[i][i] / marginal.variance() - 1.0).abs() < 1e-9);
+                }
+                let single: MultivariateGaussian<1> = ps.iter().map(|p| [p[0]]).collect();
+                let marginal: Gaussian = ps.iter().map(|p| p[0]).collect();
+                prop_assert!((single.log_pdf(&[x[0]]) - marginal.log_pdf(x[0])).abs() < 1e-9 * (1.0 + marginal.log_pdf(x[0]).abs()));
+            }
+        }
+    }
+
+    mod stats {
+        use super::*;
+        use crate::gaussian::Gaussian;
+        use crate::stats::tests::{welch_t_test, z_test};
+        use prop::collection::vec;
+
+        #[test]
+        fn known_values() {
+            let a: Gaussian = [1.0, 2.0, 3.0, 4.0, 5.0].iter().collect();
+            let b: Gaussian = [2.0, 4.0, 6.0, 8.0, 10.0].iter().collect();
+            let welch = welch_t_test(&a, &b);
+            assert!((welch.statistic + 1.897_366_596_101_027_5).abs() < 1e-12);
+            assert!((welch.p_value - 0.107_531_194_930_636).abs() < 1e-10);
+            let z = z_test(&a, &b);
+            assert_eq!(z.statistic, welch.statistic);
+            assert!((z.p_value - 0.057_779_571_123_597_27).abs() < 1e-12);
+            assert_eq!(welch_t_test(&a, &a).p_value, 1.0);
+        }
+
+        #[test]
+        fn chi_square_goodness_of_fit() {
+            use crate::categorical::Categorical;
+            use crate::stats::tests::chi_square_test;
+            let rolls = [(1, 5), (2, 8), (3, 9), (4, 8), (5, 10), (6, 20)];
+            // Tally the rolls in two shards, as if from separate machines.
+            let tally = |range: std::ops::Range<usize>| -> Categorical<u8> {
+                rolls[range]
+                    .iter()
+                    .flat_map(|&(face, n)| std::iter::repeat_n(face, n))
+                    .collect()
+            };
+            let observed = Semigroup::op(&tally(0..3), &tally(3..6));
+            let fair: Categorical<u8> = (1..=6).collect();
+            let result = chi_square_test(&observed, &fair);
+            assert!((result.statistic - 13.4).abs() < 1e-12);
+            assert_eq!(result.dof, 5);
+            assert!((result.p_value - 0.019_905_220_334_774_373).abs() < 1e-12);
+
+            let perfect = chi_square_test(&fair, &fair);
+            assert_eq!((perfect.statistic, perfect.p_value), (0.0, 1.0));
+            let impossible = chi_square_test(&(fair.clone() + 7), &fair);
+            assert_eq!(
+                (impossible.statistic, impossible.p_value),
+                (f64::INFINITY, 0.0)
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Chi-square test requires at least 1 expected category.")]
+        fn chi_square_needs_expected_categories() {
+            use crate::categorical::Categorical;
+            use crate::stats::tests::chi_square_test;
+            chi_square_test(&Categorical::new(1u8), &Categorical::default());
+        }
+
+        proptest! {
+            #[test]
+            fn p_values_are_probabilities(xs in vec(-1e3..1e3, 2..100), ys in vec(-1e3..1e3, 2..100)) {
+                let (a, b): (Gaussian, Gaussian) = (xs.iter().collect(), ys.iter().collect());
+                prop_assume!(a.variance() > 1e-6 && b.variance() > 1e-6);
+                let (welch, z) = (welch_t_test(&a, &b), z_test(&a, &b));
+                prop_assert!((0.0..=1.0).contains(&welch.p_value));
+                prop_assert!((0.0..=1.0).contains(&z.p_value));
+                // The t distribution has heavier tails than the normal.
+                prop_assert!(welch.p_value >= z.p_value);
+                prop_assert_eq!(welch.p_value, welch_t_test(&b, &a).p_value);
+            }
+        }
+    }
+
+    mod bivariate {
+        use super::*;
+        use crate::bivariate::{Covariance, Ols};
+        use crate::multivariate::MultivariateGaussian;
+        use prop::collection::vec;
+
+        fn pairs() -> impl Strategy<Value = Vec<(f64, f64)>> {
+            vec((-1e3..1e3, -1e3..1e3), 0..100)
+        }
+
+        commutative_monoid_properties!(|| pairs().prop_map(Covariance::from_iter));
+        semigroup_assign_properties!(|| pairs().prop_map(Covariance::from_iter));
+
+        #[test]
+        fn known_correlations() {
+            let c: Covariance = [(1.0, 2.0), (2.0, 4.0), (3.0, 6.0)].iter().collect();
+            assert_eq!((c.mean_x(), c.mean_y()), (2.0, 4.0));
+            assert_eq!(c.covariance(), 2.0);
+            assert_eq!(c.pearson_r(), 1.0);
+            let c: Covariance = [(1.0, 2.0), (2.0, 1.0), (3.0, 0.0)].iter().collect();
+            assert_eq!(c.pearson_r(), -1.0);
+        }
+
+        mod ols {
+            use super::*;
+            commutative_monoid_properties!(|| pairs().prop_map(Ols::from_iter));
+        }
+
+        #[test]
+        fn fits_lines() {
+            let fit: Ols = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)]
+                .iter()
+                .collect();
+            assert_eq!((fit.slope(), fit.intercept()), (2.0, 1.0));
+            assert_eq!(fit.predict(10.0), 21.0);
+            assert_eq!(fit.r_squared(), 1.0);
+            assert_eq!(fit.residual_variance(), 0.0);
+            let noisy: Ols = [(0.0, 0.0), (1.0, 2.0), (2.0, 1.0), (3.0, 3.0)]
+                .iter()
+                .collect();
+            assert!((noisy.slope() - 0.8).abs() < 1e-15 && (noisy.intercept() - 0.3).abs() < 1e-15);
+            assert!((noisy.r_squared() - 0.64).abs() < 1e-15);
+            assert!((noisy.residual_variance() - 0.9).abs() < 1e-15);
+        }
+
+        proptest! {
+            #[test]
+            fn sharded_fits_agree(ps in pairs(), k in 0usize..100) {
+                let (l, r) = ps.split_at(k.min(ps.len()));
+                let merged = Semigroup::op(&l.iter().collect::<Ols>(), &r.iter().collect());
+                prop_assert_eq!(merged, ps.iter().collect());
+            }
+            #[test]
+            fn agrees_with_multivariate(ps in pairs()) {
+                prop_assume!(ps.len() > 1);
+                let c: Covariance = ps.iter().collect();
+                let m: MultivariateGaussian<2> = ps.iter().map(|&(x, y)| [x, y]).collect();
+                let cov = m.covariance();
+                prop_assert!((c.variance_x() / cov[0][0] - 1.0).abs() < 1e-9);
+                prop_assert!((c.variance_y() / cov[1][1] - 1.0).abs() < 1e-9);
+                prop_assert!((c.covariance() - cov[0][1]).abs() < 1e-9 * cov[0][0].max(cov[1][1]));
+                prop_assert!((c.pearson_r() - m.correlation()[0][1]).abs() < 1e-9);
+            }
+        }
+    }
+
+    mod decayed {
+        use super::*;
+        use crate::decayed::DecayedGaussian;
+        use prop::collection::vec;
+
+        fn decayed() -> impl Strategy<Value = DecayedGaussian> {
+            vec((-1e3..1e3, 0.0..100.0), 0..100).prop_map(|xts| {
+                let mut g = DecayedGaussian::new(10.0);
+                xts.into_iter().for_each(|(x, t)| g.observe(x, t));
+                g
+            })
+        }
+
+        commutative_monoid_properties!(decayed);
+        semigroup_assign_properties!(decayed);
+
+        #[test]
+        fn recent_data_dominates() {
+            let mut g = DecayedGaussian::new(1.0);
+            g.observe(0.0, 0.0);
+            g.observe(1.0, 1.0);
+            assert_eq!((g.time(), g.weight()), (1.0, 1.5));
+            assert!((g.mean() - 2.0 / 3.0).abs() < 1e-15);
+            assert!((g.variance() - 2.0 / 9.0).abs() < 1e-15);
+            assert_eq!(g.weight_at(3.0), 0.375);
+            let mut late = DecayedGaussian::new(1.0);
+            late.observe(1.0, 1.0);
+            late.observe(0.0, 0.0);
+            assert_eq!(g, late);
+        }
+
+        #[test]
+        #[should_panic(expected = "different half-lives")]
+        fn half_lives_must_match() {
+            Semigroup::op(
+                &DecayedGaussian::of(0.0, 0.0, 1.0),
+                &DecayedGaussian::of(0.0, 0.0, 2.0),
+            );
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Can't observe without a half-life; start from DecayedGaussian::new."
+        )]
+        fn identity_has_no_half_life() {
+            DecayedGaussian::zero().observe(1.0, 0.0);
+        }
+
+        #[test]
+        fn identity_keeps_half_life() {
+            let (h, z) = (DecayedGaussian::new(5.0), DecayedGaussian::zero());
+            assert_eq!(Semigroup::op(&h, &z).half_life(), 5.0);
+            assert_eq!(Semigroup::op(&z, &h).half_life(), 5.0);
+            let mut g = fold_map(0..3, |_| DecayedGaussian::new(5.0));
+            g.observe(1.0, 0.0);
+            assert_eq!((g.half_life(), g.weight()), (5.0, 1.0));
+        }
+
+        mod ewma {
+            use super::*;
+            use crate::decayed::{Ewma, Ewvar};
+
+            fn ewma() -> impl Strategy<Value = Ewma> {
+                vec((-1e3..1e3, 0.0..100.0), 0..100).prop_map(|xts| {
+                    let mut e = Ewma::new(0.9);
+                    xts.into_iter().for_each(|(x, t)| e.observe(x, t));
+                    e
+                })
+            }
+
+            fn ewvar() -> impl Strategy<Value = Ewvar> {
+                vec((-1e3..1e3, 0.0..100.0), 0..100).prop_map(|xts| {
+                    let mut e = Ewvar::new(0.9);
+                    xts.into_iter().for_each(|(x, t)| e.observe(x, t));
+                    e
+                })
+            }
+
+            commutative_monoid_properties!(ewma);
+            semigroup_assign_properties!(ewma);
+
+            mod variance {
+                use super::*;
+                commutative_monoid_properties!(ewvar);
+                semigroup_assign_properties!(ewvar);
+            }
+
+            #[test]
+            fn matches_adjusted_ewma() {
+                // On evenly spaced data, weights are (1 − α)^age with α = 1 − decay.
+                let xs = [3.0, -1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+                let alpha = 0.25;
+                let weights: Vec<f64> = (0..xs.len())
+                    .map(|i| (1.0 - alpha).powi((xs.len() - 1 - i) as i32))
+                    .collect();
+                let total: f64 = weights.iter().sum();
+                let mean = xs.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>() / total;
+                let var = xs
+                    .iter()
+                    .zip(&weights)
+                    .map(|(x, w)| w * (x - mean).powi(2))
+                    .sum::<f64>()
+                    / total;
+
+                // Observe the second half first, in a separate shard, to exercise alignment.
+                let shard = |range: std::ops::Range<usize>| {
+                    let mut e = Ewvar::new(1.0 - alpha);
+                    range.for_each(|i| e.observe(xs[i], i as f64));
+                    e
+                };
+                let e = Semigroup::op(&shard(4..8), &shard(0..4));
+                assert!((e.mean().unwrap() - mean).abs() < 1e-12);
+                assert!((e.variance() - var).abs() < 1e-12);
+                assert!((e.weight() - total).abs() < 1e-12);
+                assert!((e.decay() - 0.75).abs() < 1e-15);
+
+                let mut m = Ewma::new(1.0 - alpha);
+                xs.iter()
+                    .enumerate()
+                    .rev()
+                    .for_each(|(i, &x)| m.observe(x, i as f64));
+                assert!((m.mean().unwrap() - mean).abs() < 1e-12);
+                assert_eq!(m.time(), 7.0);
+                assert_eq!(Ewma::new(0.5).mean(), None);
+                assert_eq!(Ewvar::new(0.5).mean(), None);
+            }
+
+            #[test]
+            fn identity_keeps_decay() {
+                let (m, z) = (Ewma::new(0.9), Ewma::zero());
+                assert_eq!(Semigroup::op(&m, &z).decay(), 0.9);
+                assert_eq!(Semigroup::op(&z, &m).decay(), 0.9);
+                let mut m = fold_map(0..3, |_| Ewma::new(0.9));
+                m.observe(1.0, 0.0);
+                assert_eq!((m.decay(), m.mean()), (0.9, Some(1.0)));
+
+                let (v, z) = (Ewvar::new(0.5), Ewvar::zero());
+                assert_eq!(Semigroup::op(&v, &z).decay(), 0.5);
+                assert_eq!(Semigroup::op(&z, &v).decay(), 0.5);
+                let mut v = fold_map(0..3, |_| Ewvar::new(0.5));
+                v.observe(1.0, 0.0);
+                assert_eq!((v.decay(), v.mean()), (0.5, Some(1.0)));
+            }
+
+            #[test]
+            #[should_panic(expected = "Decay must be strictly between 0 and 1.")]
+            fn decay_in_range() {
+                Ewma::new(1.0);
+            }
+
+            #[test]
+            #[should_panic(expected = "different decays")]
+            fn decays_must_match() {
+                Semigroup::op(&Ewma::of(0.0, 0.0, 0.5), &Ewma::of(0.0, 0.0, 0.25));
+            }
+        }
+    }
+
+    mod windowed {
+        use super::*;
+        use crate::newtypes::Sum;
+        use crate::windowed::TimeWindowed;
+        use prop::collection::vec;
+
+        type Series = TimeWindowed<Sum<i64>, 10>;
+
+        fn arb() -> impl Strategy<Value = Series> {
+            vec((-1000..1000i64, -100..100i64), 0..50)
+                .prop_map(|v| v.into_iter().map(|(t, x)| (t, Sum(x))).collect())
+        }
+
+        commutative_monoid_properties!(arb);
+        op_owned_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn buckets_and_rollups() {
+            let mut w: Series = [(-1, 1), (0, 2), (9, 3), (10, 4), (25, 5)]
+                .into_iter()
+                .map(|(t, x)| (t, Sum(x)))
+                .collect();
+            assert_eq!(Series::bucket_start(-1), -10);
+            assert_eq!(w.len(), 4);
+            assert_eq!(w.get(5), Some(&Sum(5)));
+            assert_eq!((w.get(15), w.get(35)), (Some(&Sum(4)), None));
+            assert_eq!(w.span(), Some((-10, 30)));
+            assert_eq!(w.rollup(0..20), Sum(9));
+            assert_eq!(w.rollup(0..=20), Sum(14));
+            let (late, early) = (20, 0);
+            assert_eq!(w.rollup(late..early), Sum(0));
+            assert_eq!(w.total(), Sum(15));
+            let starts: Vec<i64> = w.buckets().map(|(&t, _)| t).collect();
+            assert_eq!(starts, [-10, 0, 10, 20]);
+
+            // The bucket [0, 10) ends at 10, so expires along with [-10, 0).
+            assert_eq!(w.expire(10), Sum(6));
+            assert_eq!(w.span(), Some((10, 30)));
+            assert_eq!(w.expire(29), Sum(4));
+            assert_eq!(w.expire(i64::MIN), Sum(0));
+            assert_eq!(w.total(), Sum(5));
+            assert_eq!(w.expire(i64::MAX), Sum(5));
+            assert!(w.is_empty() && w.span().is_none());
+        }
+
+        #[test]
+        fn extreme_timestamps() {
+            // 2⁶³ ≡ 8 (mod 10), so the last bucket is cut short at i64::MAX and the first, keyed at i64::MIN,
+            // holds only the 8 timestamps before i64::MIN + 8.
+            let w = Series::new(i64::MAX, Sum(1));
+            assert_eq!(Series::bucket_start(i64::MAX), i64::MAX - 7);
+            assert_eq!(w.span(), Some((i64::MAX - 7, i64::MAX)));
+            assert_eq!(Series::bucket_start(i64::MIN + 9), i64::MIN + 8);
+
+            let mut w = Series::new(i64::MIN, Sum(1));
+            w.insert(i64::MIN + 7, Sum(2));
+            assert_eq!(Series::bucket_start(i64::MIN), i64::MIN);
+            assert_eq!(w.get(i64::MIN + 3), Some(&Sum(3)));
+            assert_eq!(w.span(), Some((i64::MIN, i64::MIN + 8)));
+            w.insert(i64::MIN + 8, Sum(4));
+            assert_eq!(w.span(), Some((i64::MIN, i64::MIN + 18)));
+            assert_eq!(w.expire(i64::MIN + 7), Sum(0));
+            assert_eq!(w.expire(i64::MIN + 8), Sum(3));
+            assert_eq!(w.total(), Sum(4));
+        }
+
+        proptest! {
+            #[test]
+            fn expiring_splits_the_total(w in arb(), cutoff in -1100..1100i64) {
+                let mut kept = w.clone();
+                let expired = kept.expire(cutoff);
+                prop_assert_eq!(Semigroup::op(&expired, &kept.total()), w.total());
+                prop_assert!(kept.buckets().all(|(&t, _)| t + 10 > cutoff));
+            }
+        }
+    }
+
+    mod means {
+        use super::*;
+        use crate::means::{GeometricMean, HarmonicMean, Mean};
+        use prop::collection::vec;
+
+        commutative_monoid_properties!(|| vec(-1e3..1e3, 0..100).prop_map(Mean::from_iter));
+        semigroup_assign_properties!(|| vec(-1e3..1e3, 0..100).prop_map(Mean::from_iter));
+
+        #[test]
+        fn accurate_means() {
+            assert_eq!(Mean::default().mean(), None);
+            let m: Mean = [1e16, 1.0, -1e16, 1.0].iter().collect();
+            assert_eq!((m.count(), m.sum(), m.mean()), (4, 2.0, Some(0.5)));
+            let tenths: Mean = [0.1; 10].iter().collect();
+            assert_eq!(tenths.mean(), Some(0.1));
+        }
+
+        fn mixed() -> impl Strategy<Value = Vec<f64>> {
+            vec(
+                prop_oneof![8 => 1e-3..1e3, 1 => Just(0.0), 1 => -1e3..0.0],
+                0..100,
+            )
+        }
+
+        mod geometric {
+            use super::*;
+            commutative_monoid_properties!(|| mixed().prop_map(GeometricMean::from_iter));
+            semigroup_assign_properties!(|| mixed().prop_map(GeometricMean::from_iter));
+        }
+
+        mod harmonic {
+            use super::*;
+            commutative_monoid_properties!(|| mixed().prop_map(HarmonicMean::from_iter));
+            semigroup_assign_properties!(|| mixed().prop_map(HarmonicMean::from_iter));
+        }
+
+        #[test]
+        fn pythagorean_means() {
+            let xs = [1.0, 2.0, 4.0];
+            let g: GeometricMean = xs.iter().collect();
+            let h: HarmonicMean = xs.iter().collect();
+            assert!((g.mean().unwrap() - 2.0).abs() < 1e-15);
+            assert!((h.mean().unwrap() - 12.0 / 7.0).abs() < 1e-15);
+            assert_eq!((g + 0.0).mean(), Some(0.0));
+            assert_eq!((h + 0.0).mean(), Some(0.0));
+            assert_eq!((g + 0.0 + -1.0).mean(), None);
+            assert_eq!((h + f64::NAN).mean(), None);
+            assert_eq!(GeometricMean::default().mean(), None);
+            assert_eq!((g.count(), (g + -1.0).count()), (3, 4));
+        }
+
+        #[test]
+        #[should_panic(expected = "count overflowed u64")]
+        fn count_overflow_panics() {
+            let mut m = Mean::new(1.0);
+            for _ in 0..64 {
+                m = Semigroup::op(&m, &m);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn weighted_by_count(xs in vec(-1e3..1e3, 1..100), ys in vec(-1e3..1e3, 1..100)) {
+                let (a, b): (Mean, Mean) = (xs.iter().collect(), ys.iter().collect());
+                let merged = Semigroup::op(&a, &b).mean().unwrap();
+                let (na, nb) = (xs.len() as f64, ys.len() as f64);
+                let weighted = (a.mean().unwrap() * na + b.mean().unwrap() * nb) / (na + nb);
+                prop_assert!((merged - weighted).abs() < 1e-9);
+            }
+            #[test]
+            fn harmonic_geometric_arithmetic(xs in vec(1e-3..1e3, 1..100)) {
+                let h = xs.iter().collect::<HarmonicMean>().mean().unwrap();
+                let g = xs.iter().collect::<GeometricMean>().mean().unwrap();
+                let a = xs.iter().collect::<Mean>().mean().unwrap();
+                prop_assert!(h <= g * (1.0 + 1e-12) && g <= a * (1.0 + 1e-12));
+            }
+        }
+    }
+
+    mod grouped {
+        use super::*;
+        use crate::gaussian::Gaussian;
+        use crate::grouped::GroupedGaussian;
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = GroupedGaussian<u8>> {
+            vec((0..4u8, -1e3..1e3), 0..50).prop_map(GroupedGaussian::from_iter)
+        }
+
+        monoid_properties!(arb);
+        op_owned_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn accessors() {
+            let g: GroupedGaussian<&str> =
+                [("a", 1.0), ("b", 10.0), ("a", 3.0)].into_iter().collect();
+            assert_eq!(g.len(), 2);
+            assert_eq!(g.get(&"a"), Some(&Gaussian::from_iter([1.0, 3.0])));
+            assert_eq!(g.get(&"c"), None);
+            assert_eq!(g.total(), Gaussian::from_iter([1.0, 10.0, 3.0]));
+            assert!(GroupedGaussian::<&str>::default().is_empty());
+            let sum: f64 = g.iter().map(|(_, s)| s.count()).sum();
+            assert_eq!(sum, 3.0);
+        }
+
+        #[test]
+        fn one_way_anova() {
+            let groups = [
+                [6.0, 8.0, 4.0, 5.0, 3.0, 4.0],
+                [8.0, 12.0, 9.0, 11.0, 6.0, 8.0],
+                [13.0, 9.0, 11.0, 8.0, 7.0, 12.0],
+            ];
+            let g = fold_map(groups.iter().enumerate(), |(k, xs)| {
+                xs.iter()
+                    .map(|&x| (k, x))
+                    .collect::<GroupedGaussian<usize>>()
+            });
+            assert!((g.between_group_variance() - 42.0).abs() < 1e-12);
+            assert!((g.within_group_variance() - 68.0 / 15.0).abs() < 1e-12);
+            let result = g.anova();
+            assert!((result.statistic - 9.264705882352942).abs() < 1e-12);
+            // With 2 numerator degrees of freedom the tail has the closed form (1 + 2F/d₂)^(-d₂/2).
+            assert!((result.p_value - 0.002398777329392908).abs() < 1e-12);
+        }
+
+        #[test]
+        #[should_panic(expected = "ANOVA requires at least 2 groups.")]
+        fn anova_one_group() {
+            GroupedGaussian::from_iter([(0, 1.0), (0, 2.0)]).anova();
+        }
+    }
+
+    mod histogram {
+        use super::*;
+        use crate::histogram::StreamingHistogram;
+        use prop::collection::vec;
+
+        // With no more distinct values than bins, nothing is ever fused and the laws hold exactly.
+        fn exact() -> impl Strategy<Value = StreamingHistogram<16>> {
+            vec((0..16).prop_map(f64::from), 0..50).prop_map(StreamingHistogram::from_iter)
+        }
+
+        commutative_monoid_properties!(exact);
+        semigroup_assign_properties!(exact);
+
+        #[test]
+        fn small_data_is_exact() {
+            let h: StreamingHistogram<4> = [3.0, 1.0, 2.0, 1.0, f64::NAN].iter().collect();
+            assert_eq!(h.bins(), &[(1.0, 2), (2.0, 1), (3.0, 1)]);
+            assert_eq!((h.count(), h.min(), h.max()), (4, 1.0, 3.0));
+            assert_eq!((h.cdf(0.5), h.cdf(3.0)), (0.0, 1.0));
+            assert_eq!((h.quantile(0.0), h.quantile(1.0)), (1.0, 3.0));
+            let empty = StreamingHistogram::<4>::default();
+            assert!(empty.cdf(0.0).is_nan() && empty.median().is_nan());
+        }
+
+        #[test]
+        fn fuses_closest_bins() {
+            let h: StreamingHistogram<2> = [0.0, 10.0, 11.0].iter().collect();
+            assert_eq!(h.bins(), &[(0.0, 1), (10.5, 2)]);
+            assert_eq!((h.min(), h.max()), (0.0, 11.0));
+        }
+
+        #[test]
+        fn uniform_quantiles() {
+            let xs: Vec<f64> = (0..10_000).map(|i| i as f64 / 10_000.0).collect();
+            let shards = xs
+                .chunks(1_000)
+                .map(|c| c.iter().collect::<StreamingHistogram<32>>());
+            let h = shards.fold(StreamingHistogram::zero(), |a, b| Semigroup::op(&a, &b));
+            assert_eq!(h.count(), 10_000);
+            for q in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+                assert!((h.quantile(q) - q).abs() < 0.02, "{}", q);
+                assert!((h.cdf(q) - q).abs() < 0.02, "{}", q);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn bounded_and_monotone(xs in vec(-1e3..1e3, 1..300), q in 0.0..1.0) {
+                let h: StreamingHistogram<8> = xs.iter().collect();
+                prop_assert!(h.bins().len() <= 8);
+                prop_assert_eq!(h.count(), xs.len() as u64);
+                let (lo, hi) = (h.quantile(q * 0.5), h.quantile(q));
+                prop_assert!(h.min() <= lo && lo <= hi && hi <= h.max());
+                prop_assert!(h.cdf(lo) <= h.cdf(hi));
+                prop_assert!((h.cdf(hi) - q).abs() < 1e-9 || hi == h.max() || hi == h.min());
+            }
+        }
+
+        mod hdr {
+            use super::*;
+            use crate::histogram::HdrHistogram;
+
+            fn arb() -> impl Strategy<Value = HdrHistogram<4>> {
+                vec(any::<u64>().prop_map(|v| v >> (v % 64)), 0..50)
+                    .prop_map(HdrHistogram::from_iter)
+            }
+
+            commutative_monoid_properties!(arb);
+            semigroup_assign_properties!(arb);
+
+            #[test]
+            fn bucket_edges() {
+                let h: HdrHistogram<2> = (0..64).collect();
+                let buckets: Vec<_> = h.buckets().map(|(lo, hi, _)| (lo, hi)).collect();
+                assert_eq!(
+                    &buckets[..9],
+                    &[
+                        (0, 0),
+                        (1, 1),
+                        (2, 2),
+                        (3, 3),
+                        (4, 4),
+                        (5, 5),
+                        (6, 6),
+                        (7, 7),
+                        (8, 9)
+                    ]
+                );
+                assert_eq!(buckets.last(), Some(&(56, 63)));
+                assert_eq!(h.buckets().map(|(_, _, n)| n).sum::<u64>(), 64);
+                let top = HdrHistogram::<16>::new(u64::MAX);
+                assert_eq!(top.buckets().next().map(|(_, hi, _)| hi), Some(u64::MAX));
+                assert_eq!(
+                    (top.quantile(0.5), top.max()),
+                    (Some(u64::MAX), Some(u64::MAX))
+                );
+            }
+
+            #[test]
+            fn percentiles() {
+                let empty = HdrHistogram::<10>::default();
+                assert_eq!(
+                    (empty.percentile(50.0), empty.min(), empty.mean()),
+                    (None, None, None)
+                );
+                let h: HdrHistogram = (1..=100_000).collect();
+                assert_eq!(
+                    (h.min(), h.max(), h.percentile(100.0)),
+                    (Some(1), Some(100_000), Some(100_000))
+                );
+                for p in [50.0, 90.0, 99.0, 99.9] {
+                    let exact = p * 1000.0;
+                    let approx = h.percentile(p).unwrap() as f64;
+                    assert!(
+                        approx >= exact && approx <= exact * (1.0 + 1.0 / 1024.0),
+                        "{}",
+                        p
+                    );
+                }
+                assert!((h.mean().unwrap() - 50_000.5).abs() < 50.0);
+            }
+
+            proptest! {
+                #[test]
+                fn relative_error_bounded(v in any::<u64>(), shift in 0u32..64) {
+                    let v = v >> shift;
+                    let h = HdrHistogram::<7>::new(v);
+                    let (lo, hi, _) = h.buckets().next().unwrap();
+                    prop_assert!(lo <= v && v <= hi);
+                    prop_assert!((hi - lo) as f64 <= lo as f64 / 128.0);
+                }
+            }
+        }
+    }
+
+    mod categorical {
+        use super::*;
+        use crate::categorical::Categorical;
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = Categorical<u8>> {
+            vec(0u8..8, 0..50).prop_map(Categorical::from_iter)
+        }
+
+        commutative_monoid_properties!(arb);
+        op_owned_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn known_values() {
+            let c: Categorical<&str> = ["a", "b", "a", "c"].into_iter().collect();
+            assert_eq!((c.count(), c.prob(&"a"), c.prob(&"z")), (4, 0.5, 0.0));
+            assert_eq!(c.log_prob(&"z"), f64::NEG_INFINITY);
+            assert_eq!(c.mode(), Some(&"a"));
+            assert!((c.entropy() - 1.5 * 2f64.ln()).abs() < 1e-15);
+            assert_eq!(c.counts().categories(), 3);
+            assert_eq!(Categorical::<u8>::default().mode(), None);
+        }
+
+        #[test]
+        #[should_panic(expected = "Probabilities require at least 1 sample.")]
+        fn empty_prob() {
+            Categorical::<u8>::default().prob(&0);
+        }
+
+        #[cfg(feature = "rand")]
+        #[test]
+        fn sampling_recovers_the_fit() {
+            use rand::distributions::Distribution;
+            use rand::{rngs::StdRng, SeedableRng};
+            let c = Categorical::new('x') + 'y' + 'y' + 'z';
+            let refit: Categorical<char> = (&c)
+                .sample_iter(StdRng::seed_from_u64(0))
+                .take(100_000)
+                .collect();
+            for t in ['x', 'y', 'z'] {
+                assert!((refit.prob(&t) - c.prob(&t)).abs() < 0.01);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn probabilities_sum_to_one(xs in vec(0u8..8, 1..100)) {
+                let c: Categorical<u8> = xs.iter().copied().collect();
+                let total: f64 = (0..8).map(|t| c.prob(&t)).sum();
+                prop_assert!((total - 1.0).abs() < 1e-12);
+                let mode = *c.mode().unwrap();
+                prop_assert!((0..8).all(|t| c.prob(&t) <= c.prob(&mode)));
+            }
+        }
+    }
+
+    mod impurity {
+        use super::*;
+        use crate::impurity::EntropyCounter;
+        use prop::collection::vec;
+
+        fn arb() -> impl Strategy<Value = EntropyCounter<u8>> {
+            vec(0u8..8, 0..50).prop_map(EntropyCounter::from_iter)
+        }
+
+        commutative_monoid_properties!(arb);
+        op_owned_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn known_impurities() {
+            let empty = EntropyCounter::<char>::default();
+            assert_eq!((empty.shannon_entropy(), empty.gini()), (0.0, 0.0));
+            assert_eq!(empty.frequency(&'a'), None);
+            let pure: EntropyCounter<char> = "aaaa".chars().collect();
+            assert_eq!((pure.shannon_entropy(), pure.gini()), (0.0, 0.0));
+            let even: EntropyCounter<char> = "abab".chars().collect();
+            assert!((even.shannon_entropy() - 2f64.ln()).abs() < 1e-15);
+            assert_eq!(even.gini(), 0.5);
+            let skewed = even + 'a' + 'c';
+            assert_eq!(
+                (skewed.total(), skewed.categories(), skewed.count(&'a')),
+                (6, 3, 3)
+            );
+            assert_eq!(skewed.frequency(&'c'), Some(1.0 / 6.0));
+            assert!((skewed.gini() - (1.0 - 14.0 / 36.0)).abs() < 1e-15);
+        }
+
+        proptest! {
+            #[test]
+            fn partitions_agree(xs in vec(0u8..8, 0..100), split in 0usize..100) {
+                let split = split.min(xs.len());
+                let whole: EntropyCounter<u8> = xs.iter().copied().collect();
+                let left: EntropyCounter<u8> = xs[..split].iter().copied().collect();
+                let right: EntropyCounter<u8> = xs[split..].iter().copied().collect();
+                let merged = Semigroup::op(&left, &right);
+                prop_assert!((merged.shannon_entropy() - whole.shannon_entropy()).abs() < 1e-12);
+                prop_assert!((merged.gini() - whole.gini()).abs() < 1e-12);
+                prop_assert!(whole.shannon_entropy() <= (whole.categories().max(1) as f64).ln() + 1e-12);
+                prop_assert_eq!(merged, whole);
+            }
+        }
+    }
+
+    mod logspace {
+        use super::*;
+        use crate::logspace::LogSumExp;
+        use prop::collection::vec;
+
+        commutative_semiring_properties!(|| vec(-1e2..1e2, 0..20).prop_map(LogSumExp::from_iter));
+        semigroup_assign_properties!(|| vec(-1e2..1e2, 0..20).prop_map(LogSumExp::from_iter));
+
+        #[test]
+        fn no_overflow_or_underflow() {
+            let big: LogSumExp = [1000.0, 1000.0].iter().collect();
+            assert!((big.ln() - (1000.0 + 2f64.ln())).abs() < 1e-12);
+            let tiny: LogSumExp = [-1000.0, -1000.0 - 3f64.ln()].iter().collect();
+            assert!((tiny.ln() - (-1000.0 + (4.0f64 / 3.0).ln())).abs() < 1e-12);
+            assert_eq!(LogSumExp::default().ln(), f64::NEG_INFINITY);
+            assert_eq!((LogSumExp::default() + f64::INFINITY).ln(), f64::INFINITY);
+            assert!((LogSumExp::new(0.0) + f64::NAN).ln().is_nan());
+        }
+
+        proptest! {
+            #[test]
+            fn matches_naive_sum(ps in vec(1e-3..1.0, 0..100)) {
+                let naive: f64 = ps.iter().sum();
+                let s: LogSumExp = ps.iter().map(|p| p.ln()).collect();
+                prop_assert!((s.exp() - naive).abs() <= 1e-12 * naive.max(1.0));
+                prop_assert_eq!(s, ps.iter().map(|&p| LogSumExp::from_prob(p)).fold(LogSumExp::zero(), |a, b| Semigroup::op(&a, &b)));
+            }
+        }
+    }
+
+    mod quantiles {
+        use super::*;
+        use crate::quantiles::DdSketch;
+        use prop::collection::vec;
+
+        fn sketch(xs: Vec<f64>) -> DdSketch {
+            let mut s = DdSketch::new(0.01);
+            s.extend(xs);
+            s
+        }
+
+        fn arb() -> impl Strategy<Value = DdSketch> {
+            vec(prop_oneof![-1e6..1e6, Just(0.0)], 0..100).prop_map(sketch)
+        }
+
+        commutative_monoid_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn extremes_and_empty() {
+            let empty = DdSketch::new(0.05);
+            assert_eq!((empty.quantile(0.5), empty.buckets()), (None, 0));
+            assert_eq!(Semigroup::op(&empty, &DdSketch::zero()), DdSketch::zero());
+            let s = DdSketch::of(-3.0, 0.05) + 0.0 + 7.0 + f64::NAN;
+            assert_eq!(
+                (s.count(), s.buckets(), s.min(), s.max()),
+                (3, 3, -3.0, 7.0)
+            );
+            assert_eq!(
+                (s.quantile(0.0), s.median(), s.quantile(1.0)),
+                (Some(-3.0), Some(0.0), Some(7.0))
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Can't insert without an accuracy; start from DdSketch::new.")]
+        fn identity_has_no_accuracy() {
+            let _ = DdSketch::zero() + 5.0;
+        }
+
+        #[test]
+        fn identity_keeps_accuracy() {
+            let (d, z) = (DdSketch::new(0.01), DdSketch::zero());
+            assert_eq!(Semigroup::op(&d, &z).alpha(), 0.01);
+            assert_eq!(Semigroup::op(&z, &d).alpha(), 0.01);
+            let d = fold_map(0..3, |_| DdSketch::new(0.01)) + 5.0;
+            assert_eq!(d.alpha(), 0.01);
+            assert_eq!(d.count(), 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "Can't merge sketches with different accuracies.")]
+        fn mismatched_accuracies() {
+            Semigroup::op(&DdSketch::of(1.0, 0.01), &DdSketch::of(1.0, 0.02));
+        }
+
+        #[test]
+        fn heavy_tail() {
+            // Pareto-distributed latencies spanning many orders of magnitude, sketched in shards.
+            let xs: Vec<f64> = (1..=100_000)
+                .map(|i| (1.0 - i as f64 / 100_001.0).powf(-2.0))
+                .collect();
+            let s = xs
+                .chunks(1_000)
+                .map(|c| sketch(c.to_vec()))
+                .fold(DdSketch::zero(), |a, b| Semigroup::op(&a, &b));
+            assert!(s.buckets() < 1_000);
+            for q in [0.5, 0.9, 0.99, 0.999, 0.9999] {
+                let exact = xs[(q * (xs.len() - 1) as f64).floor() as usize];
+                assert!(
+                    (s.quantile(q).unwrap() - exact).abs() <= 0.01 * exact,
+                    "{}",
+                    q
+                );
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn relative_error_bounded(mut xs in vec(-1e9..1e9, 1..200), q in 0.0..=1.0f64) {
+                let s = sketch(xs.clone());
+                xs.sort_by(f64::total_cmp);
+                let exact = xs[(q * (xs.len() - 1) as f64).floor() as usize];
+                let approx = s.quantile(q).unwrap();
+                prop_assert!((approx - exact).abs() <= 0.01 * exact.abs() * (1.0 + 1e-12));
+            }
+        }
+
+        mod moments_sketch {
+            use super::*;
+            use crate::quantiles::MomentsSketch;
+
+            commutative_monoid_properties!(
+                || vec(-1e2..1e2, 0..50).prop_map(MomentsSketch::<6>::from_iter)
+            );
+            semigroup_assign_properties!(
+                || vec(-1e2..1e2, 0..50).prop_map(MomentsSketch::<6>::from_iter)
+            );
+
+            #[test]
+            fn degenerate() {
+                let empty = MomentsSketch::<4>::default();
+                assert_eq!((empty.quantile(0.5), empty.mean()), (None, None));
+                let one = MomentsSketch::<4>::new(3.0) + 3.0;
+                assert_eq!(
+                    (one.quantiles(&[0.0, 1.0]), one.power_sum(2)),
+                    (Some(vec![3.0, 3.0]), 18.0)
+                );
+            }
+
+            #[test]
+            fn uniform() {
+                let xs: Vec<f64> = (0..10_000).map(|i| 10.0 + i as f64 / 1_000.0).collect();
+                let s = xs
+                    .chunks(100)
+                    .map(|c| c.iter().collect::<MomentsSketch>())
+                    .fold(MomentsSketch::zero(), |a, b| Semigroup::op(&a, &b));
+                let qs = [0.01, 0.1, 0.5, 0.9, 0.99];
+                for (q, x) in qs.iter().zip(s.quantiles(&qs).unwrap()) {
+                    assert!((x - (10.0 + 10.0 * q)).abs() < 0.1, "{} {}", q, x);
+                }
+            }
+
+            #[test]
+            fn gaussian() {
+                // Evenly spaced standard normal quantiles stand in for a sample.
+                let xs: Vec<f64> = (1..10_000)
+                    .map(|i| crate::special::probit(i as f64 / 10_000.0))
+                    .collect();
+                let s: MomentsSketch = xs.iter().collect();
+                let qs = [0.05, 0.25, 0.5, 0.75, 0.95];
+                for (q, x) in qs.iter().zip(s.quantiles(&qs).unwrap()) {
+                    assert!((x - crate::special::probit(*q)).abs() < 0.05, "{} {}", q, x);
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn quantiles_are_monotone(xs in vec(-1e3..1e3, 1..100)) {
+                    let s: MomentsSketch<8> = xs.iter().collect();
+                    let qs: Vec<f64> = (0..=20).map(|i| i as f64 / 20.0).collect();
+                    let est = s.quantiles(&qs).unwrap();
+                    prop_assert!(est.windows(2).all(|w| w[0] <= w[1]));
+                    prop_assert!(est[0] >= s.min() && est[20] <= s.max());
+                }
+            }
+        }
+    }
+
+    mod cardinality {
+        use super::*;
+        use crate::cardinality::HyperLogLog;
+        use prop::collection::vec;
+
+        mod hyperloglog {
+            use super::*;
+            use std::collections::HashMap;
+
+            // Small registers, so that both representations turn up.
+            fn arb() -> impl Strategy<Value = HyperLogLog<6>> {
+                vec(0..1_000u32, 0..40).prop_map(|xs| xs.into_iter().collect())
+            }
+
+            semilattice_properties!(arb);
+            semigroup_assign_properties!(arb);
+
+            #[test]
+            fn sparse_until_promoted() {
+                let mut s = HyperLogLog::<10>::new();
+                assert!(s.is_empty() && s.is_sparse());
+                assert_eq!(s.estimate(), 0.0);
+                s.extend(0..100u32);
+                s.extend(0..100u32);
+                assert!(s.is_sparse());
+                assert_eq!(s.size_bytes(), 400);
+                assert!((s.estimate() - 100.0).abs() < 1.0);
+                s.extend(100..1_000u32);
+                assert!(!s.is_sparse());
+                assert_eq!(s.size_bytes(), 1 << 10);
+            }
+
+            #[test]
+            fn merge_promotes_transparently() {
+                let small: HyperLogLog<8> = (0..20u32).collect();
+                let large: HyperLogLog<8> = (10..5_000u32).collect();
+                assert!(small.is_sparse() && !large.is_sparse());
+                let whole: HyperLogLog<8> = (0..5_000u32).collect();
+                assert_eq!(Semigroup::op(&small, &large), whole);
+                assert_eq!(Semigroup::op(&large, &small), whole);
+                // Two sparse halves whose union overflows.
+                let a: HyperLogLog<8> = (0..60u32).collect();
+                let b: HyperLogLog<8> = (60..120u32).collect();
+                assert!(a.is_sparse() && b.is_sparse());
+                let ab = Semigroup::op(&a, &b);
+                assert!(!ab.is_sparse());
+                assert_eq!(ab, (0..120u32).collect());
+            }
+
+            #[test]
+            fn accurate_when_dense() {
+                let s = (0..100)
+                    .map(|i| (i * 10_000..(i + 1) * 10_000).collect::<HyperLogLog>())
+                    .fold(HyperLogLog::zero(), |a, b| Semigroup::op(&a, &b));
+                let error = (s.estimate() / 1e6 - 1.0).abs();
+                assert!(
+                    error < 3.0 * HyperLogLog::<14>::standard_error(),
+                    "{}",
+                    error
+                );
+            }
+
+            #[test]
+            fn one_sketch_per_key() {
+                // Distinct users per page, from two shards; most pages are rare and stay sparse.
+                let shard = |users: std::ops::Range<u32>| -> HashMap<&str, HyperLogLog> {
+                    users
+                        .map(|u| (if u % 50 == 0 { "home" } else { "rare" }, u))
+                        .fold(HashMap::new(), |mut m, (page, u)| {
+                            *m.entry(page).or_default() += u;
+                            m
+                        })
+                };
+                let visits = Semigroup::op(&shard(0..5_000), &shard(2_500..7_500));
+                assert!((visits["home"].estimate() - 150.0).abs() < 2.0);
+                assert!(visits["home"].size_bytes() < 1 << 10);
+                assert!((visits["rare"].estimate() / 7_350.0 - 1.0).abs() < 0.03);
+            }
+
+            proptest! {
+                #[test]
+                fn exact_while_sparse(xs in vec(any::<u64>(), 0..200)) {
+                    let s: HyperLogLog = xs.iter().collect();
+                    let distinct = xs.iter().collect::<std::collections::HashSet<_>>().len() as f64;
+                    prop_assert!(s.is_sparse());
+                    prop_assert!((s.estimate() - distinct).abs() <= 1.0 + 0.01 * distinct);
+                }
+            }
+        }
+
+        mod kmv {
+            use super::*;
+            use crate::cardinality::Kmv;
+
+            fn arb() -> impl Strategy<Value = Kmv<16>> {
+                vec(0..100u32, 0..40).prop_map(|xs| xs.into_iter().collect())
+            }
+
+            semilattice_properties!(arb);
+            semigroup_assign_properties!(arb);
+
+            #[test]
+            fn exact_below_k() {
+                let s: Kmv = (0..500u32).chain(0..500).collect();
+                assert_eq!((s.len(), s.estimate()), (500, 500.0));
+                assert!(Kmv::<8>::new().is_empty());
+                assert_eq!(Kmv::<8>::new().jaccard(&Kmv::new()), None);
+            }
+
+            #[test]
+            fn cardinality_and_similarity() {
+                // Two overlapping sets, each sketched in shards.
+                let sketch = |range: std::ops::Range<u32>| {
+                    range
+                        .step_by(1_000)
+                        .map(|i| (i..i + 1_000).collect::<Kmv>())
+                        .fold(Kmv::zero(), |a, b| Semigroup::op(&a, &b))
+                };
+                let a = sketch(0..60_000);
+                let b = sketch(20_000..100_000);
+                assert!((a.estimate() / 60_000.0 - 1.0).abs() < 0.1);
+                let union = Semigroup::op(&a, &b);
+                assert!((union.estimate() / 100_000.0 - 1.0).abs() < 0.1);
+                let j = a.jaccard(&b).unwrap();
+                assert!((j - 0.4).abs() < 0.05, "{}", j);
+                assert!((a.intersection(&b) / 40_000.0 - 1.0).abs() < 0.15);
+                assert_eq!(a.jaccard(&a), Some(1.0));
+                assert_eq!(sketch(0..1_000).jaccard(&sketch(1_000..2_000)), Some(0.0));
             }
         }
-        impl CommutativeMonoid for Max {}
 
-        commutative_monoid_properties!(|| any::<f64>().prop_map(Max));
+        mod theta {
+            use super::*;
+            use crate::cardinality::ThetaSketch;
+
+            fn arb() -> impl Strategy<Value = ThetaSketch<16>> {
+                vec(0..100u32, 0..40).prop_map(|xs| xs.into_iter().collect())
+            }
+
+            semilattice_properties!(arb);
+            semigroup_assign_properties!(arb);
 
-        proptest! {
             #[test]
-            fn map_shuffle_reduce_sort_of(xs in vec(any::<f64>(), 0..1000)) {
-                let map_reduce = xs.iter().cloned().map(Max).fold(Monoid::zero(), |x, y| Semigroup::op(&x, &y));
-                let map_shuffle_reduce = {
-                    let mut ys = xs.into_iter().map(Max).collect::<Vec<_>>();
-                    ys.shuffle(&mut thread_rng());
-                    ys
-                }.par_chunks(4)
-                    .map(|c| c.iter().fold(Monoid::zero(), |x, &y| Semigroup::op(&x, &y)))
-                    .reduce(Monoid::zero, |x, y| Semigroup::op(&x, &y));
-                prop_assert_eq!(map_reduce, map_shuffle_reduce);
+            fn exact_up_to_k() {
+                let a: ThetaSketch<1_000> = (0..600u32).collect();
+                let b: ThetaSketch<1_000> = (400..1_000u32).collect();
+                assert_eq!((a.theta(), a.estimate()), (1.0, 600.0));
+                assert_eq!(Semigroup::op(&a, &b).estimate(), 1_000.0);
+                assert_eq!(a.intersection(&b).estimate(), 200.0);
+                assert_eq!(a.difference(&b).estimate(), 400.0);
+                assert!(ThetaSketch::<8>::new().is_empty());
+            }
+
+            #[test]
+            fn set_expressions() {
+                // Users seen on each of three days, sketched hour by hour.
+                let day = |range: std::ops::Range<u32>| {
+                    range
+                        .step_by(10_000)
+                        .map(|i| (i..i + 10_000).collect::<ThetaSketch>())
+                        .fold(ThetaSketch::zero(), |a, b| Semigroup::op(&a, &b))
+                };
+                let (mon, tue, wed) = (
+                    day(0..200_000),
+                    day(100_000..300_000),
+                    day(150_000..250_000),
+                );
+                let close = |est: f64, truth: f64, tol: f64| {
+                    assert!((est / truth - 1.0).abs() < tol, "{} {}", est, truth)
+                };
+                close(mon.estimate(), 200_000.0, 0.05);
+                close(Semigroup::op(&mon, &tue).estimate(), 300_000.0, 0.05);
+                close(mon.intersection(&tue).estimate(), 100_000.0, 0.1);
+                close(mon.difference(&tue).estimate(), 100_000.0, 0.1);
+                // (Mon ∩ Tue) \ Wed = [100k, 150k)
+                close(
+                    mon.intersection(&tue).difference(&wed).estimate(),
+                    50_000.0,
+                    0.15,
+                );
+                // (Mon \ Tue) ∪ (Tue ∩ Wed) = [0, 100k) ∪ [150k, 250k)
+                let expr = Semigroup::op(&mon.difference(&tue), &tue.intersection(&wed));
+                close(expr.estimate(), 200_000.0, 0.1);
+                assert!(mon.intersection(&day(500_000..600_000)).is_empty());
             }
         }
     }
 
-    mod any {
+    mod frequency {
         use super::*;
+        use prop::collection::vec;
+
+        mod count_sketch {
+            use super::*;
+            use crate::frequency::CountSketch;
 
-        #[derive(Clone, Debug, PartialEq)]
-        pub struct Any(pub bool);
-        impl Semigroup for Any {
-            fn op(&Any(x): &Self, &Any(y): &Self) -> Self {
-                Any(x || y)
+            fn arb() -> impl Strategy<Value = CountSketch<3, 8>> {
+                vec((0..20u8, -5..5i64), 0..20).prop_map(|xs| {
+                    let mut s = CountSketch::default();
+                    for (t, n) in xs {
+                        s.add_count(&t, n);
+                    }
+                    s
+                })
             }
-        }
-        impl Monoid for Any {
-            fn zero() -> Self {
-                Any(false)
+
+            commutative_monoid_properties!(arb);
+            semigroup_assign_properties!(arb);
+            mod group {
+                use super::*;
+                group_properties!(arb);
             }
-        }
-        impl CommutativeMonoid for Any {}
 
-        commutative_monoid_properties!(|| any::<bool>().prop_map(Any));
-    }
+            #[test]
+            fn exact_without_collisions() {
+                let s: CountSketch = ["a", "b", "a", "c", "a"].into_iter().collect();
+                assert_eq!((s.estimate("a"), s.estimate("b")), (3.0, 1.0));
+                assert_eq!(s.estimate("z"), 0.0);
+                let even: CountSketch<4, 1024> = ["a", "a"].into_iter().collect();
+                assert_eq!(even.estimate("a"), 2.0);
+                assert_eq!(CountSketch::<5, 64>::new("a").estimate("a"), 1.0);
+            }
 
-    mod all {
-        use super::*;
+            #[test]
+            fn zipf_frequencies_from_shards() {
+                // Item i occurs ⌊10⁴ / i⌋ times, spread across ten shards.
+                let shard = |k: u32| -> CountSketch<5, 256> {
+                    let mut s = CountSketch::default();
+                    for i in 1..=1_000u32 {
+                        let n = 10_000 / i;
+                        s.add_count(&i, i64::from(n / 10 + u32::from(k < n % 10)));
+                    }
+                    s
+                };
+                let s = (0..10)
+                    .map(shard)
+                    .fold(CountSketch::zero(), |a, b| Semigroup::op(&a, &b));
+                // ‖f‖₂ ≈ 10⁴ π / √6, so errors should be a few hundred at most.
+                let errors: Vec<f64> = (1..=1_000u32)
+                    .map(|i| s.estimate(&i) - f64::from(10_000 / i))
+                    .collect();
+                assert!(errors.iter().all(|e| e.abs() < 400.0));
+                let bias = errors.iter().sum::<f64>() / errors.len() as f64;
+                assert!(bias.abs() < 20.0, "{}", bias);
+            }
 
-        #[derive(Debug, PartialEq)]
-        struct All(bool);
-        impl Semigroup for All {
-            fn op(&All(x): &Self, &All(y): &Self) -> Self {
-                All(x && y)
+            #[test]
+            fn counts_can_be_removed() {
+                let all: CountSketch = (0..100u32).collect();
+                let evens: CountSketch = (0..100u32).step_by(2).collect();
+                let odds = Semigroup::op(&all, &evens.invert());
+                assert_eq!(odds, (1..100u32).step_by(2).collect());
+                assert_eq!((odds.estimate(&3u32), odds.estimate(&4u32)), (1.0, 0.0));
             }
         }
-        impl Monoid for All {
-            fn zero() -> Self {
-                All(true)
+
+        mod misra_gries {
+            use super::*;
+            use crate::frequency::MisraGries;
+            use rand::seq::SliceRandom;
+            use rand::thread_rng;
+
+            // With no more distinct items than counters, nothing is ever pruned and the laws hold exactly.
+            fn exact() -> impl Strategy<Value = MisraGries<u8, 8>> {
+                vec(0..8u8, 0..50).prop_map(MisraGries::from_iter)
+            }
+
+            commutative_monoid_properties!(exact);
+            semigroup_assign_properties!(exact);
+            op_owned_properties!(exact);
+
+            #[test]
+            fn classic_example() {
+                let s: MisraGries<char, 2> = "abacabadabacaba".chars().collect();
+                assert_eq!((s.count(), s.slack()), (15, 3));
+                assert_eq!(s.top_k(1), vec![(&'a', 5..=8)]);
+                assert!(s.bounds(&'a').contains(&8));
+                assert_eq!(s.bounds(&'z'), 0..=3);
+            }
+
+            #[test]
+            fn top_n_per_partition_merged_globally() {
+                // Item i occurs ⌊2000 / i⌋ times, shuffled across partitions merged in a random order.
+                let mut items: Vec<u32> = (1..=500)
+                    .flat_map(|i| std::iter::repeat_n(i, 2_000 / i as usize))
+                    .collect();
+                items.shuffle(&mut thread_rng());
+                let mut parts: Vec<MisraGries<u32, 20>> = items
+                    .chunks(997)
+                    .map(|c| c.iter().copied().collect())
+                    .collect();
+                parts.shuffle(&mut thread_rng());
+                let s = parts
+                    .into_iter()
+                    .fold(MisraGries::zero(), Semigroup::op_owned);
+                assert_eq!(s.count(), items.len() as u64);
+                for i in 1..=500u32 {
+                    assert!(s.bounds(&i).contains(&(2_000 / i).into()), "{}", i);
+                }
+                // Anything above n / (K + 1) must be present, and the leader is far enough ahead to be sure.
+                let kept: Vec<u32> = s.top_k(20).into_iter().map(|(&t, _)| t).collect();
+                for i in 1..=500u32 {
+                    if u64::from(2_000 / i) > s.count() / 21 {
+                        assert!(kept.contains(&i), "{}", i);
+                    }
+                }
+                assert_eq!(*s.top_k(1)[0].0, 1);
+            }
+
+            proptest! {
+                #[test]
+                fn bounds_hold_under_any_merge(xs in vec(0..30u8, 0..300), splits in vec(0..300usize, 0..10)) {
+                    let mut cuts: Vec<usize> = splits.into_iter().map(|s| s.min(xs.len())).collect();
+                    cuts.extend([0, xs.len()]);
+                    cuts.sort_unstable();
+                    let s = cuts
+                        .windows(2)
+                        .map(|w| xs[w[0]..w[1]].iter().copied().collect::<MisraGries<u8, 4>>())
+                        .fold(MisraGries::zero(), Semigroup::op_owned);
+                    for t in 0..30u8 {
+                        let count = xs.iter().filter(|&&x| x == t).count() as u64;
+                        prop_assert!(s.bounds(&t).contains(&count));
+                    }
+                    prop_assert!(s.top_k(10).len() <= 4);
+                }
             }
         }
-        impl CommutativeMonoid for All {}
 
-        commutative_monoid_properties!(|| any::<bool>().prop_map(All));
-    }
+        mod ams {
+            use super::*;
+            use crate::frequency::AmsSketch;
 
-    // Boolean blindness :-(
-    // Other options are worse.
-    // I want feature(adt_const_params)!
-    #[derive(Debug, PartialEq)]
-    struct TaggedU64<const ADDING: bool>(u64);
-    impl Semigroup for TaggedU64<true> {
-        fn op(&TaggedU64(x): &Self, &TaggedU64(y): &Self) -> Self {
-            TaggedU64(x.wrapping_add(y))
+            fn arb() -> impl Strategy<Value = AmsSketch<3, 4>> {
+                vec((0..20u8, -5..5i64), 0..20).prop_map(|xs| {
+                    let mut s = AmsSketch::default();
+                    for (t, n) in xs {
+                        s.add_count(&t, n);
+                    }
+                    s
+                })
+            }
+
+            commutative_monoid_properties!(arb);
+            semigroup_assign_properties!(arb);
+            mod group {
+                use super::*;
+                group_properties!(arb);
+            }
+
+            #[test]
+            fn single_item_is_exact() {
+                let mut s = AmsSketch::<5, 64>::new("a");
+                s.add_count("a", 6);
+                assert_eq!(s.estimate(), 49.0);
+                assert_eq!(AmsSketch::<4, 8>::zero().estimate(), 0.0);
+            }
+
+            #[test]
+            fn skew_and_join_size() {
+                // Uniform over 1000 items vs. Zipfian, each 10 occurrences per item on average, in shards.
+                let sketch = |freq: &dyn Fn(u32) -> u32| {
+                    (1..=1_000u32)
+                        .map(|i| {
+                            let mut s = AmsSketch::<7, 1_024>::default();
+                            s.add_count(&i, freq(i).into());
+                            s
+                        })
+                        .fold(AmsSketch::zero(), Semigroup::op_owned)
+                };
+                let uniform = sketch(&|_| 10);
+                let zipf = sketch(&|i| 1_336 / i);
+                let f2 = |freq: &dyn Fn(u32) -> u32| {
+                    (1..=1_000u32)
+                        .map(|i| f64::from(freq(i)).powi(2))
+                        .sum::<f64>()
+                };
+                let close = |est: f64, truth: f64| {
+                    assert!((est / truth - 1.0).abs() < 0.3, "{} {}", est, truth)
+                };
+                close(uniform.estimate(), f2(&|_| 10));
+                close(zipf.estimate(), f2(&|i| 1_336 / i));
+                assert!(zipf.estimate() > 10.0 * uniform.estimate());
+                let join: f64 = (1..=1_000u32).map(|i| f64::from(10 * (1_336 / i))).sum();
+                close(zipf.join_size(&uniform), join);
+            }
         }
     }
-    impl Semigroup for TaggedU64<false> {
-        fn op(&TaggedU64(x): &Self, &TaggedU64(y): &Self) -> Self {
-            TaggedU64(x.wrapping_mul(y))
+
+    mod naive_bayes {
+        use super::*;
+        use crate::naive_bayes::GaussianNaiveBayes;
+        use prop::collection::vec;
+        use rayon::prelude::*;
+
+        fn arb() -> impl Strategy<Value = GaussianNaiveBayes<bool, 2>> {
+            vec((any::<bool>(), [-1e2..1e2, -1e2..1e2]), 0..50)
+                .prop_map(GaussianNaiveBayes::from_iter)
         }
-    }
-    impl Monoid for TaggedU64<true> {
-        fn zero() -> Self {
-            TaggedU64(0)
+
+        monoid_properties!(arb);
+        op_owned_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        /// Two well-separated clusters of points on a grid.
+        fn clusters() -> Vec<(char, [f64; 2])> {
+            (0..400)
+                .map(|i| {
+                    let (dx, dy) = ((i % 20) as f64 / 10.0 - 1.0, (i / 20) as f64 / 10.0 - 1.0);
+                    if i % 2 == 0 {
+                        ('a', [dx, dy])
+                    } else {
+                        ('b', [5.0 + dx, 5.0 + 2.0 * dy])
+                    }
+                })
+                .collect()
         }
-    }
-    impl Monoid for TaggedU64<false> {
-        fn zero() -> Self {
-            TaggedU64(1)
+
+        #[test]
+        fn separates_clusters() {
+            let m: GaussianNaiveBayes<char, 2> = fold_map(clusters().into_iter(), |(k, x)| {
+                GaussianNaiveBayes::new(k, x)
+            });
+            assert_eq!(
+                (m.count(), m.class_count(&'a'), m.class_count(&'z')),
+                (400.0, 200.0, 0.0)
+            );
+            assert_eq!(m.predict(&[0.2, -0.1]), Some(&'a'));
+            assert_eq!(m.predict(&[4.0, 6.0]), Some(&'b'));
+            let proba = m.predict_proba(&[2.5, 2.5]);
+            assert!((proba.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-12);
+            let far = m.predict_proba(&[100.0, 100.0]);
+            assert!(far.iter().all(|(_, p)| p.is_finite()));
+            assert_eq!(
+                GaussianNaiveBayes::<char, 2>::default().predict(&[0.0, 0.0]),
+                None
+            );
         }
-    }
 
-    mod u64_add {
-        use super::*;
-        monoid_properties!(|| any::<u64>().prop_map(TaggedU64::<true>));
-    }
+        #[test]
+        fn parallel_training_agrees() {
+            let data = clusters();
+            let serial: GaussianNaiveBayes<char, 2> = data.iter().copied().collect();
+            let parallel = data
+                .par_iter()
+                .map(|&(k, x)| GaussianNaiveBayes::new(k, x))
+                .reduce(GaussianNaiveBayes::zero, Semigroup::op_owned);
+            assert_eq!(serial, parallel);
+            assert!((serial.features(&'b').unwrap()[0].mean() - 5.0).abs() < 1e-12);
+        }
 
-    mod u64_mul {
-        use super::*;
-        monoid_properties!(|| any::<u64>().prop_map(TaggedU64::<false>));
+        #[test]
+        fn constant_features() {
+            // Zero variance within each class mustn't produce NaNs.
+            let m: GaussianNaiveBayes<u8, 1> =
+                [(0, [1.0]), (0, [1.0]), (1, [2.0])].into_iter().collect();
+            assert_eq!(m.predict(&[1.0]), Some(&0));
+            assert_eq!(m.predict(&[1.9]), Some(&1));
+        }
     }
 
-    mod string {
+    mod moments {
         use super::*;
+        use crate::moments::Moments;
+        use prop::collection::vec;
 
-        impl Semigroup for String {
-            fn op(x: &Self, y: &Self) -> Self {
-                format!("{}{}", x, y)
-            }
+        commutative_monoid_properties!(|| vec(-10.0..10.0, 0..100).prop_map(Moments::from_iter));
+        semigroup_assign_properties!(|| vec(-10.0..10.0, 0..100).prop_map(Moments::from_iter));
+
+        #[test]
+        fn known_shape() {
+            let m: Moments = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0].iter().collect();
+            assert_eq!(m.mean(), 4.125);
+            assert!((m.skewness() - 0.265_055_412_269_857_35).abs() < 1e-12);
+            assert!((m.kurtosis() + 1.666_001_075_283_850_8).abs() < 1e-12);
+            let symmetric: Moments = [-2.0, -1.0, 0.0, 1.0, 2.0].iter().collect();
+            assert_eq!(symmetric.skewness(), 0.0);
         }
-        impl Monoid for String {
-            fn zero() -> Self {
-                "".to_string()
+
+        proptest! {
+            #[test]
+            fn merging_matches_two_pass(xs in vec(-10.0..10.0, 2..100), k in 0usize..100) {
+                let (l, r) = xs.split_at(k.min(xs.len()));
+                let merged = Semigroup::op(&l.iter().collect::<Moments>(), &r.iter().collect());
+                let n = xs.len() as f64;
+                let mean = xs.iter().sum::<f64>() / n;
+                let central = |p| xs.iter().map(|x| (x - mean).powi(p)).sum::<f64>();
+                prop_assume!(central(2) > 1e-6);
+                prop_assert!((merged.mean() - mean).abs() < 1e-9);
+                prop_assert!((merged.variance() - central(2) / (n - 1.0)).abs() < 1e-9);
+                prop_assert!((merged.skewness() - n.sqrt() * central(3) / central(2).powf(1.5)).abs() < 1e-9);
+                prop_assert!((merged.kurtosis() - (n * central(4) / central(2).powi(2) - 3.0)).abs() < 1e-9);
             }
         }
-
-        monoid_properties!(any::<String>);
     }
 
-    mod vec {
+    mod similarity {
         use super::*;
+        use crate::similarity::MinHash;
+        use prop::collection::vec;
 
-        impl<T: PartialEq + Copy> Semigroup for Vec<T> {
-            fn op(xs: &Self, ys: &Self) -> Self {
-                let mut zs = Vec::with_capacity(xs.len() + ys.len());
-                for z in xs.iter().chain(ys.iter()) {
-                    zs.push(*z);
-                }
-                zs
-            }
+        fn arb() -> impl Strategy<Value = MinHash<16>> {
+            vec(0..100u32, 0..20).prop_map(MinHash::from_iter)
         }
-        impl<T: PartialEq + Copy> Monoid for Vec<T> {
-            fn zero() -> Self {
-                Vec::new()
-            }
+
+        semilattice_properties!(arb);
+        semigroup_assign_properties!(arb);
+
+        #[test]
+        fn jaccard_of_overlapping_sets() {
+            // |A ∩ B| = 1000 and |A ∪ B| = 3000, each signed in shards.
+            let sign = |range: std::ops::Range<u32>| {
+                range
+                    .step_by(100)
+                    .map(|i| (i..i + 100).collect::<MinHash<512>>())
+                    .fold(MinHash::zero(), |a, b| Semigroup::op(&a, &b))
+            };
+            let (a, b) = (sign(0..2_000), sign(1_000..3_000));
+            let j = a.jaccard(&b).unwrap();
+            assert!((j - 1.0 / 3.0).abs() < 0.07, "{}", j);
+            assert_eq!(a.jaccard(&a), Some(1.0));
+            assert_eq!(sign(0..100).jaccard(&sign(100..200)), Some(0.0));
+            assert_eq!(MinHash::<8>::zero().jaccard(&MinHash::zero()), None);
+            assert_eq!(Semigroup::op(&a, &b), sign(0..3_000));
         }
 
-        monoid_properties!(any::<Vec<u8>>);
+        #[test]
+        fn banding_finds_near_duplicates() {
+            let doc = |words: &str| -> MinHash<128> { words.split(' ').collect() };
+            let original =
+                doc("the quick brown fox jumps over the lazy dog and keeps on running far away");
+            let edited =
+                doc("the quick brown fox jumps over the lazy cat and keeps on running far away");
+            let other =
+                doc("lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor");
+            let shares = |x: &MinHash<128>, y: &MinHash<128>| {
+                x.bands(4).iter().zip(y.bands(4)).any(|(a, b)| *a == b)
+            };
+            assert!(shares(&original, &edited));
+            assert!(!shares(&original, &other));
+            assert_eq!(original.bands(4).len(), 32);
+            let p = MinHash::<128>::candidate_probability(0.8, 4);
+            assert!((p - (1.0 - (1.0 - 0.8f64.powi(4)).powi(32))).abs() < 1e-12);
+        }
+
+        #[test]
+        #[should_panic(expected = "The number of rows per band must divide the signature length.")]
+        fn uneven_bands() {
+            MinHash::<128>::new("x").bands(5);
+        }
     }
 
-    mod gaussian {
+    mod summary {
         use super::*;
-        use crate::gaussian::Gaussian;
+        use crate::summary::Summary;
         use prop::collection::vec;
-        use rayon::prelude::*;
-        use std::iter::FromIterator;
 
-        monoid_properties!(|| vec(-1e3..1e3, 0..1000).prop_map(Gaussian::from_iter));
+        monoid_properties!(|| vec(-1e3..1e3, 0..100).prop_map(Summary::from_iter));
+        semigroup_assign_properties!(|| vec(-1e3..1e3, 0..100).prop_map(Summary::from_iter));
+
+        #[test]
+        fn describes_data() {
+            let s: Summary = [3.0, 1.0, 4.0, 1.0, 5.0].iter().collect();
+            assert_eq!((s.count(), s.sum(), s.mean()), (5.0, 14.0, 2.8));
+            assert_eq!((s.min(), s.max(), s.range()), (1.0, 5.0, 4.0));
+            assert!((s.variance() - 3.2).abs() < 1e-12);
+            let empty = Summary::default();
+            assert_eq!(
+                (empty.count(), empty.sum(), empty.try_variance()),
+                (0.0, 0.0, None)
+            );
+            assert_eq!(
+                (empty.min(), empty.max()),
+                (f64::INFINITY, f64::NEG_INFINITY)
+            );
+        }
 
         proptest! {
             #[test]
-            fn homomorphisms_and_associativity_are_cool(xs in vec(-1e3..1e3, 0..1000)) {
-                let from_iter = xs.iter().collect();
-                let by_hand = xs.iter().fold(Gaussian::default(), |g, &x| g + x);
-                let mapped = xs.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2));
-                let mapped_chunks = xs
-                    .chunks(4)
-                    .map(|c| c.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2)))
-                    .fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2));
-                let mapped_par_chunks = xs
-                    .par_chunks(4)
-                    .map(|c| c.iter().map(|&x| Gaussian::new(x)).fold(Monoid::zero(), |g1, g2| Semigroup::op(&g1, &g2)))
-                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
-                let sharper_par = xs
-                    .par_chunks(4)
-                    .map(|c| {let mut g = Gaussian::default(); c.iter().for_each(|&x| g += x); g})
-                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
-                let par_from_iter = xs
-                    .par_chunks(4)
-                    .map(|c| c.iter().collect())
-                    .reduce(Monoid::zero, |g1, g2| Semigroup::op(&g1, &g2));
-                for w in [from_iter, by_hand, mapped, mapped_chunks, mapped_par_chunks, sharper_par, par_from_iter].windows(2) {
-                    prop_assert_eq!(w[0], w[1]);
-                }
+            fn sharded_summaries_agree(xs in vec(-1e3..1e3, 0..100), k in 0usize..100) {
+                let (l, r) = xs.split_at(k.min(xs.len()));
+                let merged = Semigroup::op(&l.iter().collect::<Summary>(), &r.iter().collect());
+                prop_assert_eq!(merged, xs.iter().collect());
+                prop_assert_eq!(merged.min(), xs.iter().copied().fold(f64::INFINITY, f64::min));
             }
         }
     }
 
     mod tuples {
-        use super::{any::Any, max::Max, *};
+        use super::{max::Max, *};
+        use crate::newtypes::Any;
         monoid_properties!(|| any::<(f64, bool)>().prop_map(|(x, b)| (Max(x), Any(b))));
+        mod wide {
+            use super::*;
+            use crate::newtypes::{Max, Min, Sum};
+            commutative_monoid_properties!(|| any::<(u32, u64, i32, u8)>()
+                .prop_map(|(a, b, c, d)| (Sum(a as u64), Sum(b >> 8), Max(c), Min(d))));
+            semigroup_assign_properties!(|| any::<(u32, u64, i32, u8)>()
+                .prop_map(|(a, b, c, d)| (Sum(a as u64), Sum(b >> 8), Max(c), Min(d))));
+        }
+        mod twelve {
+            use super::*;
+            use crate::newtypes::All;
+            semilattice_properties!(|| any::<u16>().prop_map(|n| {
+                let b = |i: u16| n >> i & 1 == 1;
+                (
+                    Any(b(0)),
+                    All(b(1)),
+                    Any(b(2)),
+                    All(b(3)),
+                    Any(b(4)),
+                    All(b(5)),
+                    Any(b(6)),
+                    All(b(7)),
+                    Any(b(8)),
+                    All(b(9)),
+                    Any(b(10)),
+                    All(b(11)),
+                )
+            }));
+        }
+        #[test]
+        fn single_pass_summary() {
+            use crate::newtypes::{Max, Min, Sum};
+            let xs = [3i64, -1, 4, 1, -5, 9, 2, 6];
+            let (count, total, lo, hi) =
+                fold_map(xs.iter(), |&x| (Sum(1u64), Sum(x), Min(x), Max(x)));
+            assert_eq!((count.0, total.0, lo.0, hi.0), (8, 19, -5, 9));
+        }
+        #[test]
+        fn idempotence_requires_every_component() {
+            use crate::newtypes::{Max, Sum};
+            const { assert!(<(Any, Max<u8>, (Any,))>::IDEMPOTENT) };
+            const { assert!(!<(Any, Sum<u8>)>::IDEMPOTENT) };
+        }
+    }
+
+    mod arrays {
+        use super::*;
+        use crate::newtypes::{Max, Sum};
+
+        group_properties!(|| any::<[i32; 8]>().prop_map(|xs| xs.map(|x| Sum(x as i64))));
+        semigroup_assign_properties!(any::<[String; 3]>);
+        op_owned_properties!(any::<[String; 3]>);
+
+        mod semilattice {
+            use super::*;
+            semilattice_properties!(|| any::<[u8; 4]>().prop_map(|xs| xs.map(Max)));
+        }
+
+        proptest! {
+            #[test]
+            fn histograms(xs in any::<Vec<u8>>()) {
+                let buckets: [Sum<u64>; 4] = fold_map(xs.iter(), |&x| {
+                    let mut h = [Sum(0); 4];
+                    h[x as usize % 4] = Sum(1);
+                    h
+                });
+                for (i, Sum(n)) in buckets.into_iter().enumerate() {
+                    prop_assert_eq!(n, xs.iter().filter(|&&x| x as usize % 4 == i).count() as u64);
+                }
+            }
+        }
     }
 
     mod options {
         use super::{max::Max, *};
         monoid_properties!(|| any::<Option<f64>>().prop_map(|o| o.map(Max)));
+
+        mod assign {
+            use super::*;
+            semigroup_assign_properties!(any::<Option<(String, Vec<u8>)>>);
+        }
+
+        mod owned {
+            use super::*;
+            op_owned_properties!(any::<Option<(String, Vec<u8>)>>);
+        }
     }
 
     mod hashmap {
@@ -221,6 +4163,16 @@ mod tests {
 
         monoid_properties!(|| hm(any::<char>(), any::<f64>().prop_map(Max), 0..100));
 
+        mod assign {
+            use super::*;
+            semigroup_assign_properties!(|| hm(any::<char>(), any::<f64>().prop_map(Max), 0..100));
+        }
+
+        mod owned {
+            use super::*;
+            op_owned_properties!(|| hm(any::<char>(), any::<(String, Vec<u8>)>(), 0..20));
+        }
+
         mod nested {
             use super::*;
             monoid_properties!(|| hm(
@@ -235,7 +4187,8 @@ mod tests {
         }
 
         mod composite {
-            use super::{any::Any, max::Max, *};
+            use super::{max::Max, *};
+            use crate::newtypes::Any;
             monoid_properties!(|| hm(
                 any::<char>(),
                 hm(
@@ -252,6 +4205,115 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "indexmap")]
+    mod indexmap {
+        use super::{max::Max, *};
+        use ::indexmap::IndexMap;
+        use prop::collection::vec;
+
+        fn im<V: std::fmt::Debug>(
+            v: impl Strategy<Value = V>,
+        ) -> impl Strategy<Value = IndexMap<u8, V>> {
+            vec((0u8..16, v), 0..20).prop_map(|kvs| kvs.into_iter().collect())
+        }
+
+        monoid_properties!(|| im(any::<f64>().prop_map(Max)));
+        semigroup_assign_properties!(|| im(any::<f64>().prop_map(Max)));
+
+        mod owned {
+            use super::*;
+            op_owned_properties!(|| im(any::<(String, Vec<u8>)>()));
+        }
+
+        proptest! {
+            #[test]
+            fn keys_keep_first_seen_order(x in im(any::<String>()), y in im(any::<String>())) {
+                let mut expected = x.keys().copied().collect::<Vec<_>>();
+                expected.extend(y.keys().filter(|k| !x.contains_key(*k)));
+                let z = IndexMap::op(&x, &y);
+                prop_assert_eq!(z.keys().copied().collect::<Vec<_>>(), expected.clone());
+                prop_assert_eq!(IndexMap::op_owned(x, y).keys().copied().collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    mod btreemap {
+        use super::{max::Max, *};
+        use prop::collection::btree_map as bm;
+
+        monoid_properties!(|| bm(any::<char>(), any::<f64>().prop_map(Max), 0..100));
+        semigroup_assign_properties!(|| bm(any::<char>(), any::<f64>().prop_map(Max), 0..100));
+
+        mod owned {
+            use super::*;
+            op_owned_properties!(|| bm(0u8..16, any::<(String, Vec<u8>)>(), 0..20));
+        }
+
+        mod nested {
+            use super::*;
+            monoid_properties!(|| bm(0u8..4, bm(0u8..4, any::<f64>().prop_map(Max), 0..4), 0..4));
+        }
+    }
+
+    mod sets {
+        use super::*;
+        use crate::newtypes::Intersection;
+        use prop::collection::{btree_set, hash_set};
+
+        mod hash {
+            use super::*;
+            semilattice_properties!(|| hash_set(0u8..32, 0..16));
+            semigroup_assign_properties!(|| hash_set(0u8..32, 0..16));
+            op_owned_properties!(|| hash_set(0u8..32, 0..16));
+
+            mod monus {
+                use super::*;
+                monus_properties!(|| hash_set(0u8..32, 0..16));
+            }
+
+            mod lattice {
+                use super::*;
+                lattice_properties!(|| hash_set(0u8..32, 0..16));
+            }
+
+            mod intersection {
+                use super::*;
+                band_properties!(|| hash_set(0u8..32, 0..16).prop_map(Intersection));
+                semigroup_assign_properties!(|| hash_set(0u8..32, 0..16).prop_map(Intersection));
+            }
+        }
+
+        mod btree {
+            use super::*;
+            semilattice_properties!(|| btree_set(any::<String>(), 0..8));
+            semigroup_assign_properties!(|| btree_set(any::<String>(), 0..8));
+            op_owned_properties!(|| btree_set(any::<String>(), 0..8));
+
+            mod monus {
+                use super::*;
+                monus_properties!(|| btree_set(0u8..32, 0..16));
+            }
+
+            mod lattice {
+                use super::*;
+                lattice_properties!(|| btree_set(0u8..32, 0..16));
+            }
+
+            mod intersection {
+                use super::*;
+                band_properties!(|| btree_set(0u8..32, 0..16).prop_map(Intersection));
+                semigroup_assign_properties!(|| btree_set(0u8..32, 0..16).prop_map(Intersection));
+
+                mod adjoined {
+                    use super::*;
+                    monoid_properties!(|| prop::option::of(
+                        btree_set(0u8..32, 0..16).prop_map(Intersection)
+                    ));
+                }
+            }
+        }
+    }
+
     mod minplus {
         use super::*;
         use std::ops::{Add, Mul};
@@ -307,11 +4369,33 @@ mod tests {
             }
         }
 
-        semiring_properties!(|| any::<Option<f64>>().prop_map(|o| {
+        impl<T: Real> CommutativeSemiring for MinPlus<T> {}
+
+        commutative_semiring_properties!(|| any::<Option<f64>>().prop_map(|o| {
             match o {
                 None => MinPlus::Infinity,
                 Some(x) => MinPlus::Finite(x),
             }
         }));
+
+        // Shortest paths only make sense without negative cycles, so we only handle nonnegative weights.
+        impl<T: Real> StarSemiring for MinPlus<T> {
+            fn star(&x: &Self) -> Self {
+                if let MinPlus::Finite(a) = x {
+                    assert!(a >= Zero::zero(), "Negative weights have no closure.");
+                }
+                Semiring::one()
+            }
+        }
+
+        mod star {
+            use super::*;
+            star_semiring_properties!(|| any::<Option<u16>>().prop_map(|o| {
+                match o {
+                    None => MinPlus::Infinity,
+                    Some(x) => MinPlus::Finite(x as f64),
+                }
+            }));
+        }
     }
 }
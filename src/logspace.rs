@@ -0,0 +1,139 @@
+//! Sums of probabilities kept in log space
+//!
+//! Multiplying many small probabilities underflows, so they're usually carried as logarithms; adding them
+//! back up then needs `ln(eˣ + eʸ)`, which overflows or underflows if computed directly.  Shifting by the
+//! larger argument first,
+//!
+//! ```text
+//! ln(eˣ + eʸ) = max(x, y) + ln(1 + e^-|x - y|)
+//! ```
+//!
+//! keeps every intermediate in range, so [`LogSumExp`] can merge totals from parallel shards whatever their
+//! magnitude.  Together with ordinary addition as multiplication this is the log semiring, the smooth
+//! counterpart of max-plus.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{
+    CommutativeMonoid, CommutativeSemiring, Monoid, Semigroup, SemigroupAssign, Semiring,
+};
+
+/// The logarithm of a sum of exponentials, i.e. a log-probability under addition of probabilities
+///
+/// The empty sum is `ln 0 = −∞`.  NaN poisons everything it's merged with.
+#[derive(Clone, Copy, Debug)]
+pub struct LogSumExp(f64);
+
+/// Infinities must match exactly; finite values are compared approximately.
+impl PartialEq for LogSumExp {
+    fn eq(&self, other: &LogSumExp) -> bool {
+        self.0 == other.0 || close(self.0, other.0) || (self.0.is_nan() && other.0.is_nan())
+    }
+}
+
+/// `ln(eˣ + eʸ)`, shifting by the larger argument so nothing overflows.
+fn log_add_exp(x: f64, y: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return f64::NAN;
+    }
+    let (hi, lo) = if x >= y { (x, y) } else { (y, x) };
+    if lo == f64::NEG_INFINITY || hi == f64::INFINITY {
+        hi
+    } else {
+        hi + (lo - hi).exp().ln_1p()
+    }
+}
+
+impl LogSumExp {
+    /// Construct from a single log-probability (or any logarithm).
+    pub fn new(log_x: f64) -> LogSumExp {
+        LogSumExp(log_x)
+    }
+    /// Construct from a probability (or any non-negative number) by taking its logarithm.
+    pub fn from_prob(p: f64) -> LogSumExp {
+        LogSumExp(p.ln())
+    }
+    /// The logarithm of the total.
+    pub fn ln(&self) -> f64 {
+        self.0
+    }
+    /// The total itself, which may over- or underflow.
+    pub fn exp(&self) -> f64 {
+        self.0.exp()
+    }
+}
+
+/// Default to the empty sum.
+impl Default for LogSumExp {
+    fn default() -> Self {
+        LogSumExp(f64::NEG_INFINITY)
+    }
+}
+
+/// We can add a new log-probability.
+impl Add<f64> for LogSumExp {
+    type Output = Self;
+    fn add(mut self, log_x: f64) -> Self::Output {
+        self += log_x;
+        self
+    }
+}
+
+/// We can add a new log-probability.
+impl AddAssign<f64> for LogSumExp {
+    fn add_assign(&mut self, log_x: f64) {
+        self.0 = log_add_exp(self.0, log_x);
+    }
+}
+
+/// Accumulate the log-probabilities one at a time.
+impl FromIterator<f64> for LogSumExp {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut s = LogSumExp::default();
+        for x in iter {
+            s += x
+        }
+        s
+    }
+}
+
+/// Accumulate the log-probabilities one at a time.
+impl<'a> FromIterator<&'a f64> for LogSumExp {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the underlying probabilities, shifted by the larger log.
+impl Semigroup for LogSumExp {
+    fn op(x: &Self, y: &Self) -> Self {
+        LogSumExp(log_add_exp(x.0, y.0))
+    }
+}
+
+/// `ln 0 = −∞`.
+impl Monoid for LogSumExp {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Add in place.
+impl SemigroupAssign for LogSumExp {}
+
+/// Addition of probabilities is commutative.
+impl CommutativeMonoid for LogSumExp {}
+
+/// Multiplying probabilities adds their logs.
+impl Semiring for LogSumExp {
+    fn mul(x: &Self, y: &Self) -> Self {
+        LogSumExp(x.0 + y.0)
+    }
+    fn one() -> Self {
+        LogSumExp(0.0)
+    }
+}
+
+/// Multiplication of probabilities is commutative.
+impl CommutativeSemiring for LogSumExp {}
@@ -0,0 +1,263 @@
+//! Averages that need less machinery than [`Gaussian`](crate::gaussian::Gaussian)
+//!
+//! Each keeps an integer count alongside a (compensated) running total, and merging adds both, so the
+//! merged average is weighted by how much data each side saw.  [`GeometricMean`] and [`HarmonicMean`]
+//! total a transform of the data (logarithms and reciprocals, respectively), which only makes sense for
+//! positive inputs: zeros are counted separately and pull either mean to zero, while negative or NaN
+//! inputs leave it undefined.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::compensated::CompensatedSum;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Add counts, panicking rather than silently wrapping.
+fn add_counts(x: u64, y: u64) -> u64 {
+    x.checked_add(y).expect("count overflowed u64")
+}
+
+/// The arithmetic mean, as a count and a compensated sum
+///
+/// Merging panics if the combined count would overflow `u64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Mean {
+    count: u64,
+    sum: CompensatedSum,
+}
+
+impl Mean {
+    /// Construct from a single data point.
+    pub fn new(x: f64) -> Mean {
+        Mean {
+            count: 1,
+            sum: CompensatedSum::new(x),
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// The (compensated) total.
+    pub fn sum(&self) -> f64 {
+        self.sum.value()
+    }
+    /// The mean, or `None` if no data has been seen.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum.value() / self.count as f64)
+    }
+}
+
+/// We can add a new data point.
+impl Add<f64> for Mean {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point.
+impl AddAssign<f64> for Mean {
+    fn add_assign(&mut self, x: f64) {
+        self.count = add_counts(self.count, 1);
+        self.sum += x;
+    }
+}
+
+/// Accumulate the points one at a time.
+impl FromIterator<f64> for Mean {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut m = Mean::default();
+        for x in iter {
+            m += x
+        }
+        m
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a> FromIterator<&'a f64> for Mean {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the counts and the sums.
+impl Semigroup for Mean {
+    fn op(x: &Self, y: &Self) -> Self {
+        Mean {
+            count: add_counts(x.count, y.count),
+            sum: Semigroup::op(&x.sum, &y.sum),
+        }
+    }
+}
+
+/// Add the counts and the sums in place.
+impl SemigroupAssign for Mean {
+    fn op_assign(&mut self, other: &Self) {
+        self.count = add_counts(self.count, other.count);
+        self.sum.op_assign(&other.sum);
+    }
+}
+
+/// No data.
+impl Monoid for Mean {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Addition is commutative.
+impl CommutativeMonoid for Mean {}
+
+/// Counts and a transformed total of the positive inputs, shared by the geometric and harmonic means
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PositiveTotal {
+    /// Every input
+    count: u64,
+    /// Inputs equal to zero
+    zeros: u64,
+    /// Negative or NaN inputs
+    invalid: u64,
+    /// Total of the transformed positive inputs
+    sum: CompensatedSum,
+}
+
+impl PositiveTotal {
+    /// Tally `x`, adding `f(x)` to the total if `x` is positive.
+    fn observe(&mut self, x: f64, f: fn(f64) -> f64) {
+        self.count = add_counts(self.count, 1);
+        if x > 0.0 {
+            self.sum += f(x);
+        } else if x == 0.0 {
+            self.zeros += 1;
+        } else {
+            self.invalid += 1;
+        }
+    }
+    /// The mean computed by `from_total` from the count and total, if it's defined.
+    fn mean(&self, from_total: impl Fn(f64, f64) -> f64) -> Option<f64> {
+        if self.count == 0 || self.invalid > 0 {
+            None
+        } else if self.zeros > 0 {
+            Some(0.0)
+        } else {
+            Some(from_total(self.count as f64, self.sum.value()))
+        }
+    }
+    /// Add the counts and totals.
+    fn merge(&mut self, other: &Self) {
+        self.count = add_counts(self.count, other.count);
+        self.zeros += other.zeros;
+        self.invalid += other.invalid;
+        self.sum.op_assign(&other.sum);
+    }
+}
+
+/// Instances shared by means that total a transform of positive inputs.
+macro_rules! positive_mean_instances {
+    ($t:ident, $f:expr) => {
+        impl $t {
+            /// Construct from a single data point.
+            pub fn new(x: f64) -> $t {
+                let mut m = $t::default();
+                m += x;
+                m
+            }
+            /// The number of data points seen, including zeros and invalid ones.
+            pub fn count(&self) -> u64 {
+                self.0.count
+            }
+        }
+
+        /// We can add a new data point.
+        impl Add<f64> for $t {
+            type Output = Self;
+            fn add(mut self, x: f64) -> Self::Output {
+                self += x;
+                self
+            }
+        }
+
+        /// We can add a new data point.
+        impl AddAssign<f64> for $t {
+            fn add_assign(&mut self, x: f64) {
+                self.0.observe(x, $f);
+            }
+        }
+
+        /// Accumulate the points one at a time.
+        impl FromIterator<f64> for $t {
+            fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+                let mut m = $t::default();
+                for x in iter {
+                    m += x
+                }
+                m
+            }
+        }
+
+        /// Accumulate the points one at a time.
+        impl<'a> FromIterator<&'a f64> for $t {
+            fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+                iter.into_iter().copied().collect()
+            }
+        }
+
+        /// Add the counts and the totals.
+        impl Semigroup for $t {
+            fn op(x: &Self, y: &Self) -> Self {
+                let mut z = *x;
+                z.op_assign(y);
+                z
+            }
+        }
+
+        /// Add the counts and the totals in place.
+        impl SemigroupAssign for $t {
+            fn op_assign(&mut self, other: &Self) {
+                self.0.merge(&other.0);
+            }
+        }
+
+        /// No data.
+        impl Monoid for $t {
+            fn zero() -> Self {
+                Self::default()
+            }
+        }
+
+        /// Addition is commutative.
+        impl CommutativeMonoid for $t {}
+    };
+}
+
+/// The geometric mean, `(x₁ ⋯ xₙ)^(1/n)`, as a count and a sum of logarithms
+///
+/// This is `Some(0.0)` if any input was zero, and `None` if any was negative or NaN.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GeometricMean(PositiveTotal);
+
+positive_mean_instances!(GeometricMean, f64::ln);
+
+impl GeometricMean {
+    /// The geometric mean, or `None` if there's no data or it's undefined.
+    pub fn mean(&self) -> Option<f64> {
+        self.0.mean(|n, logs| (logs / n).exp())
+    }
+}
+
+/// The harmonic mean, `n / (1/x₁ + ⋯ + 1/xₙ)`, as a count and a sum of reciprocals
+///
+/// This is `Some(0.0)` if any input was zero, and `None` if any was negative or NaN.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HarmonicMean(PositiveTotal);
+
+positive_mean_instances!(HarmonicMean, f64::recip);
+
+impl HarmonicMean {
+    /// The harmonic mean, or `None` if there's no data or it's undefined.
+    pub fn mean(&self) -> Option<f64> {
+        self.0.mean(|n, reciprocals| n / reciprocals)
+    }
+}
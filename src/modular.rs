@@ -0,0 +1,220 @@
+//! Integers modulo a compile-time constant
+//!
+//! Residues form a commutative ring under addition and multiplication mod `N`, and a field when `N` is
+//! prime.  Because [`Mod`] implements the usual arithmetic operators as well as [`Semiring`], it plugs into
+//! [`Product`] for fast modular exponentiation via [`power_monoid`], and into anything generic over
+//! semirings.
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_traits::{One, Zero};
+
+use crate::newtypes::Product;
+use crate::traits::{
+    power_monoid, CancellativeMonoid, CommutativeMonoid, CommutativeSemiring, Field, Group, Monoid,
+    Ring, Semigroup, SemigroupAssign, Semiring,
+};
+
+/// `a * b mod m` without overflow.
+const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// `b^e mod m` by repeated squaring.
+const fn pow_mod(mut b: u64, mut e: u64, m: u64) -> u64 {
+    let mut acc = 1 % m;
+    b %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            acc = mul_mod(acc, b, m);
+        }
+        b = mul_mod(b, b, m);
+        e >>= 1;
+    }
+    acc
+}
+
+/// Deterministic Miller–Rabin; these bases suffice for every `u64`.
+const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < BASES.len() {
+        if n.is_multiple_of(BASES[i]) {
+            return n == BASES[i];
+        }
+        i += 1;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut i = 0;
+    while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, n);
+        let mut r = 1;
+        let mut witness = x != 1 && x != n - 1;
+        while witness && r < s {
+            x = mul_mod(x, x, n);
+            witness = x != n - 1;
+            r += 1;
+        }
+        if witness {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// An integer modulo `N`, always stored in canonical form `0..N`
+///
+/// `N` must be positive; [`Field::reciprocal`] additionally requires it to be prime, which is checked at
+/// compile time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mod<const N: u64>(u64);
+
+impl<const N: u64> Mod<N> {
+    /// Whether `N` is prime, i.e. whether this is a field.
+    pub const IS_FIELD: bool = is_prime(N);
+
+    /// The residue of `x`.
+    pub const fn new(x: u64) -> Self {
+        const { assert!(N > 0, "the modulus must be positive") };
+        Mod(x % N)
+    }
+    /// The canonical representative, in `0..N`.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+    /// `self` to the power `e`, by repeated squaring.
+    pub fn pow(self, e: u64) -> Self {
+        power_monoid(Product(self), e).0
+    }
+}
+
+impl<const N: u64> From<u64> for Mod<N> {
+    fn from(x: u64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<const N: u64> Add for Mod<N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let (s, overflow) = self.0.overflowing_add(other.0);
+        Mod(if overflow || s >= N {
+            s.wrapping_sub(N)
+        } else {
+            s
+        })
+    }
+}
+
+impl<const N: u64> Neg for Mod<N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Mod(if self.0 == 0 { 0 } else { N - self.0 })
+    }
+}
+
+impl<const N: u64> Sub for Mod<N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: u64> Mul for Mod<N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Mod(mul_mod(self.0, other.0, N))
+    }
+}
+
+impl<const N: u64> AddAssign for Mod<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: u64> SubAssign for Mod<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: u64> MulAssign for Mod<N> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const N: u64> Zero for Mod<N> {
+    fn zero() -> Self {
+        Mod(0)
+    }
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const N: u64> One for Mod<N> {
+    fn one() -> Self {
+        Mod(1 % N)
+    }
+}
+
+/// Addition mod `N`.
+impl<const N: u64> Semigroup for Mod<N> {
+    fn op(&x: &Self, &y: &Self) -> Self {
+        x + y
+    }
+}
+
+/// Zero is the additive identity.
+impl<const N: u64> Monoid for Mod<N> {
+    fn zero() -> Self {
+        Mod(0)
+    }
+}
+
+/// Add in place.
+impl<const N: u64> SemigroupAssign for Mod<N> {}
+
+/// Addition is commutative.
+impl<const N: u64> CommutativeMonoid for Mod<N> {}
+
+/// Addition mod `N` can always be undone.
+impl<const N: u64> CancellativeMonoid for Mod<N> {}
+
+/// Negation mod `N`.
+impl<const N: u64> Group for Mod<N> {
+    fn invert(&self) -> Self {
+        -*self
+    }
+}
+
+/// Multiplication mod `N`.
+impl<const N: u64> Semiring for Mod<N> {
+    fn mul(&x: &Self, &y: &Self) -> Self {
+        x * y
+    }
+    fn one() -> Self {
+        Mod(1 % N)
+    }
+}
+
+/// Multiplication is commutative.
+impl<const N: u64> CommutativeSemiring for Mod<N> {}
+
+/// The integers mod `N` are a ring.
+impl<const N: u64> Ring for Mod<N> {}
+
+/// The integers mod a prime are a field; using this with composite `N` fails to compile.
+impl<const N: u64> Field for Mod<N> {
+    // Fermat's little theorem: x⁻¹ = x^(p - 2)
+    fn reciprocal(&self) -> Self {
+        const { assert!(is_prime(N), "the modulus must be prime") };
+        self.pow(N - 2)
+    }
+}
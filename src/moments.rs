@@ -0,0 +1,149 @@
+//! Running central moments up to the fourth, for shape statistics that survive merging
+//!
+//! [`Gaussian`](crate::gaussian::Gaussian) only keeps the first two moments; [`Moments`] adds the third and
+//! fourth, combined with the pairwise update formulas from [Pébay (2008)](https://www.osti.gov/biblio/1028931),
+//! so that skewness and kurtosis can be read off of summaries merged from many shards.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Count, mean, and the second through fourth central moment sums of a stream of floats
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Moments {
+    /// Count of datapoints (stored as a float for convenience)
+    n: f64,
+    /// Mean
+    m1: f64,
+    /// Sum of squared deviations from the mean
+    m2: f64,
+    /// Sum of cubed deviations from the mean
+    m3: f64,
+    /// Sum of fourth powers of deviations from the mean
+    m4: f64,
+}
+
+impl PartialEq for Moments {
+    fn eq(&self, other: &Moments) -> bool {
+        (self.n == other.n)
+            && close(self.m1, other.m1)
+            && close(self.m2, other.m2)
+            && close(self.m3, other.m3)
+            && close(self.m4, other.m4)
+    }
+}
+
+impl Moments {
+    /// Construct from a single data point.
+    pub fn new(x: f64) -> Moments {
+        Moments {
+            n: 1.0,
+            m1: x,
+            ..Default::default()
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> f64 {
+        self.n
+    }
+    /// The mean.
+    pub fn mean(&self) -> f64 {
+        self.m1
+    }
+    /// The (sample) variance.
+    pub fn variance(&self) -> f64 {
+        assert!(self.n > 1.0, "Variance requires more than 1 sample.");
+        self.m2 / (self.n - 1.0)
+    }
+    /// The (population) skewness, `g₁`; NaN if every point is the same.
+    pub fn skewness(&self) -> f64 {
+        self.n.sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+    /// The (population) excess kurtosis, `g₂`, which is zero for a normal distribution; NaN if every point is
+    /// the same.
+    pub fn kurtosis(&self) -> f64 {
+        self.n * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+}
+
+/// We can add a new data point.
+impl Add<f64> for Moments {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point.
+impl AddAssign<f64> for Moments {
+    fn add_assign(&mut self, x: f64) {
+        self.op_assign(&Moments::new(x));
+    }
+}
+
+/// Accumulate the points one at a time.
+impl FromIterator<f64> for Moments {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut m = Self::default();
+        for x in iter {
+            m += x
+        }
+        m
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a> FromIterator<&'a f64> for Moments {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Join together two sets of moments.
+impl Semigroup for Moments {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// Join another set of moments into this one.
+impl SemigroupAssign for Moments {
+    fn op_assign(&mut self, other: &Self) {
+        let (na, nb) = (self.n, other.n);
+        let n = na + nb;
+        if n == 0.0 {
+            *self = Self::default();
+            return;
+        }
+        let d = other.m1 - self.m1;
+        let (d_n, d2) = (d / n, d * d);
+        let m4 = self.m4
+            + other.m4
+            + d2 * d2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * d_n * d_n * (na * na * other.m2 + nb * nb * self.m2)
+            + 4.0 * d_n * (na * other.m3 - nb * self.m3);
+        let m3 = self.m3
+            + other.m3
+            + d2 * d * na * nb * (na - nb) / (n * n)
+            + 3.0 * d_n * (na * other.m2 - nb * self.m2);
+        self.m2 += other.m2 + d2 * na * nb / n;
+        self.m1 += d_n * nb;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.n = n;
+    }
+}
+
+/// The "empty distribution."
+impl Monoid for Moments {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl CommutativeMonoid for Moments {}
@@ -0,0 +1,187 @@
+//! The `D`-dimensional extension of [`Gaussian`](crate::gaussian::Gaussian)
+//!
+//! Keeps the count, mean vector, and co-moment matrix (sums of products of deviations), merged with the
+//! pairwise formula of [Chan et al.](https://doi.org/10.1007/978-3-642-51461-6_3), from which the
+//! covariance, correlation, and density follow.
+use std::f64::consts::PI;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Running mean and covariance of points in `D` dimensions
+#[derive(Clone, Copy, Debug)]
+pub struct MultivariateGaussian<const D: usize> {
+    /// Count of datapoints (stored as a float for convenience)
+    n: f64,
+    /// Mean vector
+    mean: [f64; D],
+    /// Sums of products of deviations from the mean
+    comoment: [[f64; D]; D],
+}
+
+impl<const D: usize> PartialEq for MultivariateGaussian<D> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.n == other.n)
+            && (0..D).all(|i| {
+                close(self.mean[i], other.mean[i])
+                    && (0..D).all(|j| close(self.comoment[i][j], other.comoment[i][j]))
+            })
+    }
+}
+
+impl<const D: usize> Default for MultivariateGaussian<D> {
+    fn default() -> Self {
+        MultivariateGaussian {
+            n: 0.0,
+            mean: [0.0; D],
+            comoment: [[0.0; D]; D],
+        }
+    }
+}
+
+impl<const D: usize> MultivariateGaussian<D> {
+    /// Construct from a single data point.
+    pub fn new(x: [f64; D]) -> Self {
+        MultivariateGaussian {
+            n: 1.0,
+            mean: x,
+            comoment: [[0.0; D]; D],
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> f64 {
+        self.n
+    }
+    /// The mean vector.
+    pub fn mean(&self) -> [f64; D] {
+        self.mean
+    }
+    /// The (sample) covariance matrix.
+    pub fn covariance(&self) -> [[f64; D]; D] {
+        assert!(self.n > 1.0, "Covariance requires more than 1 sample.");
+        self.comoment.map(|row| row.map(|c| c / (self.n - 1.0)))
+    }
+    /// The correlation matrix; entries involving a constant coordinate are NaN.
+    pub fn correlation(&self) -> [[f64; D]; D] {
+        let c = &self.comoment;
+        std::array::from_fn(|i| std::array::from_fn(|j| c[i][j] / (c[i][i] * c[j][j]).sqrt()))
+    }
+    /// Probability Density Function; the covariance must be positive definite.
+    pub fn pdf(&self, x: &[f64; D]) -> f64 {
+        self.log_pdf(x).exp()
+    }
+    /// Natural logarithm of the [`pdf`](MultivariateGaussian::pdf).
+    pub fn log_pdf(&self, x: &[f64; D]) -> f64 {
+        let l = cholesky(&self.covariance()).expect("Covariance must be positive definite.");
+        // Solve L z = x - μ, so that the Mahalanobis distance is |z|².
+        let mut z = [0.0; D];
+        for i in 0..D {
+            z[i] = ((x[i] - self.mean[i]) - (0..i).map(|k| l[i][k] * z[k]).sum::<f64>()) / l[i][i];
+        }
+        let log_det = 2.0 * (0..D).map(|i| l[i][i].ln()).sum::<f64>();
+        let mahalanobis = z.iter().map(|z| z * z).sum::<f64>();
+        -0.5 * (D as f64 * (2.0 * PI).ln() + log_det + mahalanobis)
+    }
+}
+
+/// The lower-triangular `L` with `L Lᵀ = a`, or `None` if `a` isn't positive definite.
+fn cholesky<const D: usize>(a: &[[f64; D]; D]) -> Option<[[f64; D]; D]> {
+    let mut l = [[0.0; D]; D];
+    for i in 0..D {
+        for j in 0..=i {
+            let s = a[i][j] - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+            if i == j {
+                if s <= 0.0 {
+                    return None;
+                }
+                l[i][i] = s.sqrt();
+            } else {
+                l[i][j] = s / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// We can add a new data point to a multivariate Gaussian distribution.
+impl<const D: usize> Add<[f64; D]> for MultivariateGaussian<D> {
+    type Output = Self;
+    fn add(mut self, x: [f64; D]) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point to a multivariate Gaussian distribution.
+impl<const D: usize> AddAssign<[f64; D]> for MultivariateGaussian<D> {
+    fn add_assign(&mut self, x: [f64; D]) {
+        self.n += 1.0;
+        let d: [f64; D] = std::array::from_fn(|i| x[i] - self.mean[i]);
+        let w = (self.n - 1.0) / self.n;
+        for i in 0..D {
+            self.mean[i] += d[i] / self.n;
+            for j in 0..D {
+                self.comoment[i][j] += d[i] * d[j] * w;
+            }
+        }
+    }
+}
+
+/// Accumulate the points one at a time into a new multivariate Gaussian distribution.
+impl<const D: usize> FromIterator<[f64; D]> for MultivariateGaussian<D> {
+    fn from_iter<I: IntoIterator<Item = [f64; D]>>(iter: I) -> Self {
+        let mut g = Self::default();
+        for x in iter {
+            g += x
+        }
+        g
+    }
+}
+
+/// Accumulate the points one at a time into a new multivariate Gaussian distribution.
+impl<'a, const D: usize> FromIterator<&'a [f64; D]> for MultivariateGaussian<D> {
+    fn from_iter<I: IntoIterator<Item = &'a [f64; D]>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Join together two multivariate gaussian distributions.
+impl<const D: usize> Semigroup for MultivariateGaussian<D> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// Join another multivariate gaussian distribution into this one.
+impl<const D: usize> SemigroupAssign for MultivariateGaussian<D> {
+    fn op_assign(&mut self, other: &Self) {
+        let n = self.n + other.n;
+        if n == 0.0 {
+            *self = Self::default();
+            return;
+        }
+        let d: [f64; D] = std::array::from_fn(|i| other.mean[i] - self.mean[i]);
+        let w = self.n * other.n / n;
+        for i in 0..D {
+            self.mean[i] += d[i] * (other.n / n);
+            for j in 0..D {
+                self.comoment[i][j] += other.comoment[i][j] + d[i] * d[j] * w;
+            }
+        }
+        self.n = n;
+    }
+}
+
+/// The "empty distribution."
+impl<const D: usize> Monoid for MultivariateGaussian<D> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merging is symmetric in its arguments.
+impl<const D: usize> CommutativeMonoid for MultivariateGaussian<D> {}
@@ -0,0 +1,157 @@
+//! Gaussian naive Bayes classification, trained as a monoid
+//!
+//! Following HLearn's lineage, the model is nothing but a [`Gaussian`] per class and feature, so training
+//! on separate shards and merging the models gives exactly the model trained on all the data at once:
+//! fit with [`fold_map`](crate::traits::fold_map), a parallel reduce, or incrementally as data arrives.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+use crate::gaussian::Gaussian;
+use crate::logspace::LogSumExp;
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// A naive Bayes classifier over `D` real-valued features, with labels of type `K`
+///
+/// Each class's features are modeled as independent Gaussians.  As in scikit-learn, a tiny multiple of
+/// the largest feature variance is added to every variance, so a feature that happens to be constant
+/// within a class doesn't rule out everything else.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianNaiveBayes<K: Eq + Hash, const D: usize> {
+    /// Per-feature summaries of each class's training points
+    classes: HashMap<K, [Gaussian; D]>,
+}
+
+impl<K: Eq + Hash, const D: usize> Default for GaussianNaiveBayes<K, D> {
+    fn default() -> Self {
+        const { assert!(D > 0, "a classifier needs at least one feature") };
+        GaussianNaiveBayes {
+            classes: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, const D: usize> GaussianNaiveBayes<K, D> {
+    /// Construct from a single training point `x` labeled `class`.
+    pub fn new(class: K, x: [f64; D]) -> Self {
+        let mut m = Self::default();
+        m.observe(class, x);
+        m
+    }
+    /// Train on one more point `x` labeled `class`.
+    pub fn observe(&mut self, class: K, x: [f64; D]) {
+        let gs = self
+            .classes
+            .entry(class)
+            .or_insert_with(|| [Gaussian::default(); D]);
+        for (g, x) in gs.iter_mut().zip(x) {
+            *g += x;
+        }
+    }
+    /// The labels seen in training, in arbitrary order.
+    pub fn classes(&self) -> impl Iterator<Item = &K> + '_ {
+        self.classes.keys()
+    }
+    /// The number of training points labeled `class`.
+    pub fn class_count(&self, class: &K) -> f64 {
+        self.classes.get(class).map_or(0.0, |gs| gs[0].count())
+    }
+    /// The number of training points.
+    pub fn count(&self) -> f64 {
+        self.classes.values().map(|gs| gs[0].count()).sum()
+    }
+    /// The summaries of each feature among training points labeled `class`.
+    pub fn features(&self, class: &K) -> Option<&[Gaussian; D]> {
+        self.classes.get(class)
+    }
+    /// The log of the joint probability density of `x` and each class, up to a constant.
+    fn log_joint(&self, x: &[f64; D]) -> Vec<(&K, f64)> {
+        let largest = self
+            .classes
+            .values()
+            .flatten()
+            .map(|g| g.population_variance())
+            .fold(0.0, f64::max);
+        let epsilon = 1e-9 * if largest > 0.0 { largest } else { 1.0 };
+        let n = self.count();
+        self.classes
+            .iter()
+            .map(|(k, gs)| {
+                let prior = (gs[0].count() / n).ln();
+                let likelihood: f64 = gs
+                    .iter()
+                    .zip(x)
+                    .map(|(g, &x)| {
+                        let v = g.population_variance() + epsilon;
+                        -0.5 * ((2.0 * PI * v).ln() + (x - g.mean()).powi(2) / v)
+                    })
+                    .sum();
+                (k, prior + likelihood)
+            })
+            .collect()
+    }
+    /// The most probable class for `x`, or `None` if the model hasn't been trained.
+    pub fn predict(&self, x: &[f64; D]) -> Option<&K> {
+        self.log_joint(x)
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(k, _)| k)
+    }
+    /// The posterior probability of each class given `x`, in arbitrary order.
+    pub fn predict_proba(&self, x: &[f64; D]) -> Vec<(&K, f64)> {
+        let joint = self.log_joint(x);
+        let evidence: LogSumExp = joint.iter().map(|&(_, l)| l).collect();
+        joint
+            .into_iter()
+            .map(|(k, l)| (k, (l - evidence.ln()).exp()))
+            .collect()
+    }
+}
+
+/// Train on the points one at a time.
+impl<K: Eq + Hash, const D: usize> FromIterator<(K, [f64; D])> for GaussianNaiveBayes<K, D> {
+    fn from_iter<I: IntoIterator<Item = (K, [f64; D])>>(iter: I) -> Self {
+        let mut m = Self::default();
+        m.extend(iter);
+        m
+    }
+}
+
+/// Train on the points one at a time.
+impl<K: Eq + Hash, const D: usize> Extend<(K, [f64; D])> for GaussianNaiveBayes<K, D> {
+    fn extend<I: IntoIterator<Item = (K, [f64; D])>>(&mut self, iter: I) {
+        for (k, x) in iter {
+            self.observe(k, x)
+        }
+    }
+}
+
+/// Merge the summaries class by class and feature by feature.
+impl<K: Eq + Hash + Clone, const D: usize> Semigroup for GaussianNaiveBayes<K, D> {
+    fn op(x: &Self, y: &Self) -> Self {
+        GaussianNaiveBayes {
+            classes: Semigroup::op(&x.classes, &y.classes),
+        }
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        GaussianNaiveBayes {
+            classes: Semigroup::op_owned(x.classes, y.classes),
+        }
+    }
+}
+
+/// Merge in place.
+impl<K: Eq + Hash + Clone, const D: usize> SemigroupAssign for GaussianNaiveBayes<K, D> {
+    fn op_assign(&mut self, other: &Self) {
+        self.classes.op_assign(&other.classes);
+    }
+}
+
+/// The untrained model.
+impl<K: Eq + Hash + Clone, const D: usize> Monoid for GaussianNaiveBayes<K, D> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
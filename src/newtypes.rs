@@ -0,0 +1,1006 @@
+//! Newtype wrappers picking out a particular monoid on an existing type
+//!
+//! Many types carry more than one lawful monoid (integers under `+` or `*`, say); wrapping a value says which
+//! one we mean.
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Neg,
+};
+
+use num_traits::{
+    Bounded, CheckedAdd, CheckedMul, One, PrimInt, Saturating, Unsigned, WrappingAdd, WrappingMul,
+    WrappingNeg, Zero,
+};
+
+use crate::traits::{
+    Band, CancellativeMonoid, CommutativeMonoid, CommutativeSemiring, Group, Monoid, Monus,
+    OrderedMonoid, Ring, Semigroup, SemigroupAssign, Semilattice, Semiring, StarSemiring,
+};
+
+/// Numbers under addition
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sum<T>(pub T);
+
+/// Add the wrapped values.
+impl<T: Clone + Zero> Semigroup for Sum<T> {
+    fn op(Sum(x): &Self, Sum(y): &Self) -> Self {
+        Sum(x.clone() + y.clone())
+    }
+    fn op_owned(Sum(x): Self, Sum(y): Self) -> Self {
+        Sum(x + y)
+    }
+}
+
+/// Zero is the additive identity.
+impl<T: Clone + Zero> Monoid for Sum<T> {
+    fn zero() -> Self {
+        Sum(T::zero())
+    }
+}
+
+/// Add in place.
+impl<T: Clone + Zero> SemigroupAssign for Sum<T> {}
+
+/// Addition is commutative.
+impl<T: Clone + Zero> CommutativeMonoid for Sum<T> {}
+
+/// Negation gives additive inverses.
+impl<T: Clone + Zero + Neg<Output = T>> Group for Sum<T> {
+    fn invert(&self) -> Self {
+        Sum(-self.0.clone())
+    }
+}
+
+/// Unsigned numbers are the prototypical monus.
+impl<T: Clone + Zero + Unsigned + Saturating> Monus for Sum<T> {
+    fn monus(Sum(x): &Self, Sum(y): &Self) -> Self {
+        Sum(x.clone().saturating_sub(y.clone()))
+    }
+}
+
+/// Adding unsigned numbers can be undone.
+impl<T: Clone + Zero + Unsigned> CancellativeMonoid for Sum<T> {}
+
+/// Adding unsigned numbers only makes things bigger.
+impl<T: Clone + Zero + Unsigned + PartialOrd> OrderedMonoid for Sum<T> {}
+
+/// Numbers under multiplication
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+/// Multiply the wrapped values.
+impl<T: Clone + One> Semigroup for Product<T> {
+    fn op(Product(x): &Self, Product(y): &Self) -> Self {
+        Product(x.clone() * y.clone())
+    }
+    fn op_owned(Product(x): Self, Product(y): Self) -> Self {
+        Product(x * y)
+    }
+}
+
+/// One is the multiplicative identity.
+impl<T: Clone + One> Monoid for Product<T> {
+    fn zero() -> Self {
+        Product(T::one())
+    }
+}
+
+/// Multiply in place.
+impl<T: Clone + One> SemigroupAssign for Product<T> {}
+
+/// Multiplication is commutative.
+impl<T: Clone + One> CommutativeMonoid for Product<T> {}
+
+/// Integers under addition modulo their width
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WrappingSum<T>(pub T);
+
+/// Add the wrapped values, wrapping around on overflow.
+impl<T: WrappingAdd + Zero> Semigroup for WrappingSum<T> {
+    fn op(WrappingSum(x): &Self, WrappingSum(y): &Self) -> Self {
+        WrappingSum(x.wrapping_add(y))
+    }
+}
+
+/// Zero is the additive identity.
+impl<T: WrappingAdd + Zero> Monoid for WrappingSum<T> {
+    fn zero() -> Self {
+        WrappingSum(T::zero())
+    }
+}
+
+/// Add in place.
+impl<T: WrappingAdd + Zero> SemigroupAssign for WrappingSum<T> {}
+
+/// Addition is commutative.
+impl<T: WrappingAdd + Zero> CommutativeMonoid for WrappingSum<T> {}
+
+/// Wrapping negation gives additive inverses, even for unsigned types.
+impl<T: WrappingAdd + WrappingNeg + Zero> Group for WrappingSum<T> {
+    fn invert(&self) -> Self {
+        WrappingSum(self.0.wrapping_neg())
+    }
+}
+
+/// Wrapping addition can always be undone.
+impl<T: WrappingAdd + Zero> CancellativeMonoid for WrappingSum<T> {}
+
+/// Integers under multiplication modulo their width
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WrappingProduct<T>(pub T);
+
+/// Multiply the wrapped values, wrapping around on overflow.
+impl<T: WrappingMul + One> Semigroup for WrappingProduct<T> {
+    fn op(WrappingProduct(x): &Self, WrappingProduct(y): &Self) -> Self {
+        WrappingProduct(x.wrapping_mul(y))
+    }
+}
+
+/// One is the multiplicative identity.
+impl<T: WrappingMul + One> Monoid for WrappingProduct<T> {
+    fn zero() -> Self {
+        WrappingProduct(T::one())
+    }
+}
+
+/// Multiply in place.
+impl<T: WrappingMul + One> SemigroupAssign for WrappingProduct<T> {}
+
+/// Multiplication is commutative.
+impl<T: WrappingMul + One> CommutativeMonoid for WrappingProduct<T> {}
+
+/// Unsigned integers under addition, with overflow recorded as `None` (which then absorbs everything)
+///
+/// Signed integers are excluded since whether an intermediate result overflows depends on the grouping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedSum<T>(pub Option<T>);
+
+impl<T> CheckedSum<T> {
+    /// Wrap a (not yet overflowed) value.
+    pub fn new(x: T) -> Self {
+        CheckedSum(Some(x))
+    }
+}
+
+/// Add the wrapped values, unless either has already overflowed.
+impl<T: CheckedAdd + Zero + Unsigned> Semigroup for CheckedSum<T> {
+    fn op(CheckedSum(x): &Self, CheckedSum(y): &Self) -> Self {
+        match (x, y) {
+            (Some(a), Some(b)) => CheckedSum(a.checked_add(b)),
+            _ => CheckedSum(None),
+        }
+    }
+}
+
+/// Zero is the additive identity.
+impl<T: CheckedAdd + Zero + Unsigned> Monoid for CheckedSum<T> {
+    fn zero() -> Self {
+        CheckedSum(Some(T::zero()))
+    }
+}
+
+/// Add in place.
+impl<T: CheckedAdd + Zero + Unsigned> SemigroupAssign for CheckedSum<T> {}
+
+/// Mathematical addition is commutative, so overflow happens regardless of order.
+impl<T: CheckedAdd + Zero + Unsigned> CommutativeMonoid for CheckedSum<T> {}
+
+/// Unsigned integers under multiplication, with overflow recorded as `None` (which then absorbs everything
+/// but zero)
+///
+/// Signed integers are excluded since whether an intermediate result overflows depends on the grouping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedProduct<T>(pub Option<T>);
+
+impl<T> CheckedProduct<T> {
+    /// Wrap a (not yet overflowed) value.
+    pub fn new(x: T) -> Self {
+        CheckedProduct(Some(x))
+    }
+}
+
+/// Multiply the wrapped values, unless either has already overflowed.
+impl<T: CheckedMul + One + Unsigned> Semigroup for CheckedProduct<T> {
+    fn op(CheckedProduct(x): &Self, CheckedProduct(y): &Self) -> Self {
+        match (x, y) {
+            (Some(a), Some(b)) => CheckedProduct(a.checked_mul(b)),
+            (Some(a), None) | (None, Some(a)) if a.is_zero() => CheckedProduct(Some(T::zero())),
+            _ => CheckedProduct(None),
+        }
+    }
+}
+
+/// One is the multiplicative identity.
+impl<T: CheckedMul + One + Unsigned> Monoid for CheckedProduct<T> {
+    fn zero() -> Self {
+        CheckedProduct(Some(T::one()))
+    }
+}
+
+/// Multiply in place.
+impl<T: CheckedMul + One + Unsigned> SemigroupAssign for CheckedProduct<T> {}
+
+/// Mathematical multiplication is commutative, so overflow happens regardless of order.
+impl<T: CheckedMul + One + Unsigned> CommutativeMonoid for CheckedProduct<T> {}
+
+/// Totally ordered values under taking the larger
+///
+/// This is a monoid for [`Bounded`] types; otherwise, `Option<Max<T>>` adjoins an identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Max<T>(pub T);
+
+/// Keep the larger value (the right one, in case of ties).
+impl<T: Ord + Clone> Semigroup for Max<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x.max(y)
+    }
+}
+
+/// The smallest value is the identity.
+impl<T: Ord + Clone + Bounded> Monoid for Max<T> {
+    fn zero() -> Self {
+        Max(T::min_value())
+    }
+}
+
+/// Compare in place.
+impl<T: Ord + Clone> SemigroupAssign for Max<T> {}
+
+/// Maxima can be taken in any order.
+impl<T: Ord + Clone + Bounded> CommutativeMonoid for Max<T> {}
+
+/// Taking the maximum twice changes nothing.
+impl<T: Ord + Clone> Band for Max<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: Ord + Clone + Bounded> Semilattice for Max<T> {}
+
+/// Larger inputs give larger maxima.
+impl<T: Ord + Clone + Bounded> OrderedMonoid for Max<T> {}
+
+/// Anything at most `y` is already accounted for by `y`.
+impl<T: Ord + Clone + Bounded> Monus for Max<T> {
+    fn monus(x: &Self, y: &Self) -> Self {
+        if x <= y {
+            Self::zero()
+        } else {
+            x.clone()
+        }
+    }
+}
+
+/// Totally ordered values under taking the smaller
+///
+/// This is a monoid for [`Bounded`] types; otherwise, `Option<Min<T>>` adjoins an identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Min<T>(pub T);
+
+/// Keep the smaller value (the left one, in case of ties).
+impl<T: Ord + Clone> Semigroup for Min<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x.min(y)
+    }
+}
+
+/// The largest value is the identity.
+impl<T: Ord + Clone + Bounded> Monoid for Min<T> {
+    fn zero() -> Self {
+        Min(T::max_value())
+    }
+}
+
+/// Compare in place.
+impl<T: Ord + Clone> SemigroupAssign for Min<T> {}
+
+/// Minima can be taken in any order.
+impl<T: Ord + Clone + Bounded> CommutativeMonoid for Min<T> {}
+
+/// Taking the minimum twice changes nothing.
+impl<T: Ord + Clone> Band for Min<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: Ord + Clone + Bounded> Semilattice for Min<T> {}
+
+/// Larger inputs give larger minima.
+impl<T: Ord + Clone + Bounded> OrderedMonoid for Min<T> {}
+
+/// The largest key seen along with its payload, with `None` as the identity
+///
+/// Ties keep the earlier (left) entry, so the result is deterministic for any fixed order of reduction,
+/// including rayon's.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArgMax<K, V>(pub Option<(K, V)>);
+
+impl<K, V> ArgMax<K, V> {
+    /// Wrap a key and its payload.
+    pub fn new(key: K, value: V) -> Self {
+        ArgMax(Some((key, value)))
+    }
+    /// The largest key, if any.
+    pub fn key(&self) -> Option<&K> {
+        self.0.as_ref().map(|(k, _)| k)
+    }
+    /// The payload of the largest key, if any.
+    pub fn value(&self) -> Option<&V> {
+        self.0.as_ref().map(|(_, v)| v)
+    }
+}
+
+/// Keep the entry with the larger key (the left one, in case of ties).
+impl<K: Ord + Clone, V: Clone> Semigroup for ArgMax<K, V> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        match (&x.0, &y.0) {
+            (Some((kx, _)), Some((ky, _))) if ky > kx => y,
+            (None, _) => y,
+            _ => x,
+        }
+    }
+}
+
+/// Nothing seen yet.
+impl<K: Ord + Clone, V: Clone> Monoid for ArgMax<K, V> {
+    fn zero() -> Self {
+        ArgMax(None)
+    }
+}
+
+/// Compare in place.
+impl<K: Ord + Clone, V: Clone> SemigroupAssign for ArgMax<K, V> {
+    fn op_assign(&mut self, other: &Self) {
+        if let Some((ky, _)) = &other.0 {
+            if self.key().is_none_or(|kx| ky > kx) {
+                *self = other.clone();
+            }
+        }
+    }
+}
+
+/// Taking the maximum twice changes nothing.
+impl<K: Ord + Clone, V: Clone> Band for ArgMax<K, V> {}
+
+/// The smallest key seen along with its payload, with `None` as the identity
+///
+/// Ties keep the earlier (left) entry, so the result is deterministic for any fixed order of reduction,
+/// including rayon's.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArgMin<K, V>(pub Option<(K, V)>);
+
+impl<K, V> ArgMin<K, V> {
+    /// Wrap a key and its payload.
+    pub fn new(key: K, value: V) -> Self {
+        ArgMin(Some((key, value)))
+    }
+    /// The smallest key, if any.
+    pub fn key(&self) -> Option<&K> {
+        self.0.as_ref().map(|(k, _)| k)
+    }
+    /// The payload of the smallest key, if any.
+    pub fn value(&self) -> Option<&V> {
+        self.0.as_ref().map(|(_, v)| v)
+    }
+}
+
+/// Keep the entry with the smaller key (the left one, in case of ties).
+impl<K: Ord + Clone, V: Clone> Semigroup for ArgMin<K, V> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        match (&x.0, &y.0) {
+            (Some((kx, _)), Some((ky, _))) if ky < kx => y,
+            (None, _) => y,
+            _ => x,
+        }
+    }
+}
+
+/// Nothing seen yet.
+impl<K: Ord + Clone, V: Clone> Monoid for ArgMin<K, V> {
+    fn zero() -> Self {
+        ArgMin(None)
+    }
+}
+
+/// Compare in place.
+impl<K: Ord + Clone, V: Clone> SemigroupAssign for ArgMin<K, V> {
+    fn op_assign(&mut self, other: &Self) {
+        if let Some((ky, _)) = &other.0 {
+            if self.key().is_none_or(|kx| ky < kx) {
+                *self = other.clone();
+            }
+        }
+    }
+}
+
+/// Taking the minimum twice changes nothing.
+impl<K: Ord + Clone, V: Clone> Band for ArgMin<K, V> {}
+
+/// The first non-empty value seen, with `None` as the identity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct First<T>(pub Option<T>);
+
+impl<T> First<T> {
+    /// Wrap a present value.
+    pub fn new(x: T) -> Self {
+        First(Some(x))
+    }
+}
+
+/// Keep the left value unless it's missing.
+impl<T: Clone> Semigroup for First<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        if x.0.is_some() {
+            x.clone()
+        } else {
+            y.clone()
+        }
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        First(x.0.or(y.0))
+    }
+}
+
+/// Nothing seen yet.
+impl<T: Clone> Monoid for First<T> {
+    fn zero() -> Self {
+        First(None)
+    }
+}
+
+/// Only clone the right value if we need it.
+impl<T: Clone> SemigroupAssign for First<T> {
+    fn op_assign(&mut self, other: &Self) {
+        if self.0.is_none() {
+            self.0.clone_from(&other.0);
+        }
+    }
+}
+
+/// Seeing the same thing twice changes nothing.
+impl<T: Clone> Band for First<T> {}
+
+/// The last non-empty value seen, with `None` as the identity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Last<T>(pub Option<T>);
+
+impl<T> Last<T> {
+    /// Wrap a present value.
+    pub fn new(x: T) -> Self {
+        Last(Some(x))
+    }
+}
+
+/// Keep the right value unless it's missing.
+impl<T: Clone> Semigroup for Last<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        if y.0.is_some() {
+            y.clone()
+        } else {
+            x.clone()
+        }
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        Last(y.0.or(x.0))
+    }
+}
+
+/// Nothing seen yet.
+impl<T: Clone> Monoid for Last<T> {
+    fn zero() -> Self {
+        Last(None)
+    }
+}
+
+/// Only clone the right value if it's there.
+impl<T: Clone> SemigroupAssign for Last<T> {
+    fn op_assign(&mut self, other: &Self) {
+        if other.0.is_some() {
+            self.0.clone_from(&other.0);
+        }
+    }
+}
+
+/// Seeing the same thing twice changes nothing.
+impl<T: Clone> Band for Last<T> {}
+
+/// The earliest of some timestamps (or any other ordered values), with `None` as the identity
+///
+/// Unlike [`Min`], this needs no [`Bounded`] instance, so it works for `SystemTime` and `Instant`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Earliest<T>(pub Option<T>);
+
+impl<T> Earliest<T> {
+    /// Wrap a present value.
+    pub fn new(x: T) -> Self {
+        Earliest(Some(x))
+    }
+}
+
+/// Keep the smaller value (the left one, in case of ties), ignoring missing ones.
+impl<T: Ord + Clone> Semigroup for Earliest<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        match (x.0, y.0) {
+            (Some(a), Some(b)) => Earliest(Some(a.min(b))),
+            (a, b) => Earliest(a.or(b)),
+        }
+    }
+}
+
+/// Nothing seen yet.
+impl<T: Ord + Clone> Monoid for Earliest<T> {
+    fn zero() -> Self {
+        Earliest(None)
+    }
+}
+
+/// Compare in place.
+impl<T: Ord + Clone> SemigroupAssign for Earliest<T> {}
+
+/// Minima can be taken in any order.
+impl<T: Ord + Clone> CommutativeMonoid for Earliest<T> {}
+
+/// Seeing the same thing twice changes nothing.
+impl<T: Ord + Clone> Band for Earliest<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: Ord + Clone> Semilattice for Earliest<T> {}
+
+/// The latest of some timestamps (or any other ordered values), with `None` as the identity
+///
+/// Unlike [`Max`], this needs no [`Bounded`] instance, so it works for `SystemTime` and `Instant`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Latest<T>(pub Option<T>);
+
+impl<T> Latest<T> {
+    /// Wrap a present value.
+    pub fn new(x: T) -> Self {
+        Latest(Some(x))
+    }
+}
+
+/// Keep the larger value (the right one, in case of ties); `None` sorts below everything.
+impl<T: Ord + Clone> Semigroup for Latest<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        Latest(x.0.max(y.0))
+    }
+}
+
+/// Nothing seen yet.
+impl<T: Ord + Clone> Monoid for Latest<T> {
+    fn zero() -> Self {
+        Latest(None)
+    }
+}
+
+/// Compare in place.
+impl<T: Ord + Clone> SemigroupAssign for Latest<T> {}
+
+/// Maxima can be taken in any order.
+impl<T: Ord + Clone> CommutativeMonoid for Latest<T> {}
+
+/// Seeing the same thing twice changes nothing.
+impl<T: Ord + Clone> Band for Latest<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: Ord + Clone> Semilattice for Latest<T> {}
+
+/// Booleans under disjunction: is any of them true?
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Any(pub bool);
+
+/// Logical or.
+impl Semigroup for Any {
+    const IDEMPOTENT: bool = true;
+    fn op(&Any(x): &Self, &Any(y): &Self) -> Self {
+        Any(x || y)
+    }
+}
+
+/// Nothing is true of the empty collection.
+impl Monoid for Any {
+    fn zero() -> Self {
+        Any(false)
+    }
+}
+
+/// Or in place.
+impl SemigroupAssign for Any {}
+
+/// Disjunction is commutative.
+impl CommutativeMonoid for Any {}
+
+/// Disjunction is idempotent.
+impl Band for Any {}
+
+/// Disjunction is the join of the two-element lattice.
+impl Semilattice for Any {}
+
+/// The boolean semiring, e.g. for reachability.
+impl Semiring for Any {
+    fn mul(&Any(x): &Self, &Any(y): &Self) -> Self {
+        Any(x && y)
+    }
+    fn one() -> Self {
+        Any(true)
+    }
+}
+
+/// Conjunction is commutative.
+impl CommutativeSemiring for Any {}
+
+/// Everything reaches itself, so the closure of anything is true.
+impl StarSemiring for Any {
+    fn star(_: &Self) -> Self {
+        Any(true)
+    }
+}
+
+/// Booleans under conjunction: are all of them true?
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct All(pub bool);
+
+/// Logical and.
+impl Semigroup for All {
+    const IDEMPOTENT: bool = true;
+    fn op(&All(x): &Self, &All(y): &Self) -> Self {
+        All(x && y)
+    }
+}
+
+/// Everything is true of the empty collection.
+impl Monoid for All {
+    fn zero() -> Self {
+        All(true)
+    }
+}
+
+/// And in place.
+impl SemigroupAssign for All {}
+
+/// Conjunction is commutative.
+impl CommutativeMonoid for All {}
+
+/// Conjunction is idempotent.
+impl Band for All {}
+
+/// Conjunction is the meet of the two-element lattice.
+impl Semilattice for All {}
+
+/// What float extrema do with NaN
+pub trait NanPolicy {
+    /// Whether a NaN anywhere makes the result NaN (rather than being skipped)
+    const PROPAGATE: bool;
+}
+
+/// Treat NaN as missing data: it's skipped by [`MaxF64`] & [`MinF64`].
+#[derive(Clone, Copy, Debug)]
+pub enum IgnoreNan {}
+
+impl NanPolicy for IgnoreNan {
+    const PROPAGATE: bool = false;
+}
+
+/// Treat NaN as poison: once seen, every extremum is NaN.
+#[derive(Clone, Copy, Debug)]
+pub enum PropagateNan {}
+
+impl NanPolicy for PropagateNan {
+    const PROPAGATE: bool = true;
+}
+
+/// Floats under taking the larger, with an explicit [`NanPolicy`]
+///
+/// Unlike `f64::max`, which silently drops NaN on one side but not the other, this is a lawful monoid: under
+/// [`IgnoreNan`], NaN is stored as the identity (−∞), while under [`PropagateNan`] it absorbs everything.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxF64<P = IgnoreNan>(f64, PhantomData<P>);
+
+impl<P: NanPolicy> MaxF64<P> {
+    /// Wrap a value, normalizing NaN according to the policy.
+    pub fn new(x: f64) -> Self {
+        if x.is_nan() && !P::PROPAGATE {
+            MaxF64(f64::NEG_INFINITY, PhantomData)
+        } else {
+            MaxF64(x, PhantomData)
+        }
+    }
+    /// The wrapped value.
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+}
+
+/// NaN is equal to itself here, so that propagated NaNs compare sensibly.
+impl<P> PartialEq for MaxF64<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 || (self.0.is_nan() && other.0.is_nan())
+    }
+}
+impl<P> Eq for MaxF64<P> {}
+
+/// Keep the larger value, or NaN when propagating.
+impl<P: NanPolicy> Semigroup for MaxF64<P> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        if x.0.is_nan() || y.0.is_nan() {
+            MaxF64(f64::NAN, PhantomData)
+        } else {
+            MaxF64(x.0.max(y.0), PhantomData)
+        }
+    }
+}
+
+/// Negative infinity is below everything.
+impl<P: NanPolicy> Monoid for MaxF64<P> {
+    fn zero() -> Self {
+        MaxF64(f64::NEG_INFINITY, PhantomData)
+    }
+}
+
+/// Compare in place.
+impl<P: NanPolicy> SemigroupAssign for MaxF64<P> {}
+
+/// Maxima can be taken in any order.
+impl<P: NanPolicy> CommutativeMonoid for MaxF64<P> {}
+
+/// Taking the maximum twice changes nothing.
+impl<P: NanPolicy> Band for MaxF64<P> {}
+
+/// Floats (plus poison) are totally ordered.
+impl<P: NanPolicy> Semilattice for MaxF64<P> {}
+
+/// Floats under taking the smaller, with an explicit [`NanPolicy`]
+///
+/// Unlike `f64::min`, which silently drops NaN on one side but not the other, this is a lawful monoid: under
+/// [`IgnoreNan`], NaN is stored as the identity (+∞), while under [`PropagateNan`] it absorbs everything.
+#[derive(Clone, Copy, Debug)]
+pub struct MinF64<P = IgnoreNan>(f64, PhantomData<P>);
+
+impl<P: NanPolicy> MinF64<P> {
+    /// Wrap a value, normalizing NaN according to the policy.
+    pub fn new(x: f64) -> Self {
+        if x.is_nan() && !P::PROPAGATE {
+            MinF64(f64::INFINITY, PhantomData)
+        } else {
+            MinF64(x, PhantomData)
+        }
+    }
+    /// The wrapped value.
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+}
+
+/// NaN is equal to itself here, so that propagated NaNs compare sensibly.
+impl<P> PartialEq for MinF64<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 || (self.0.is_nan() && other.0.is_nan())
+    }
+}
+impl<P> Eq for MinF64<P> {}
+
+/// Keep the smaller value, or NaN when propagating.
+impl<P: NanPolicy> Semigroup for MinF64<P> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        if x.0.is_nan() || y.0.is_nan() {
+            MinF64(f64::NAN, PhantomData)
+        } else {
+            MinF64(x.0.min(y.0), PhantomData)
+        }
+    }
+}
+
+/// Infinity is above everything.
+impl<P: NanPolicy> Monoid for MinF64<P> {
+    fn zero() -> Self {
+        MinF64(f64::INFINITY, PhantomData)
+    }
+}
+
+/// Compare in place.
+impl<P: NanPolicy> SemigroupAssign for MinF64<P> {}
+
+/// Minima can be taken in any order.
+impl<P: NanPolicy> CommutativeMonoid for MinF64<P> {}
+
+/// Taking the minimum twice changes nothing.
+impl<P: NanPolicy> Band for MinF64<P> {}
+
+/// Floats (plus poison) are totally ordered.
+impl<P: NanPolicy> Semilattice for MinF64<P> {}
+
+/// Integers under bitwise or
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitOrM<T>(pub T);
+
+/// Set any bit set on either side.
+impl<T: PrimInt> Semigroup for BitOrM<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(&BitOrM(x): &Self, &BitOrM(y): &Self) -> Self {
+        BitOrM(x | y)
+    }
+}
+
+/// No bits set.
+impl<T: PrimInt> Monoid for BitOrM<T> {
+    fn zero() -> Self {
+        BitOrM(T::zero())
+    }
+}
+
+/// Or in place.
+impl<T: PrimInt> SemigroupAssign for BitOrM<T> {}
+
+/// Bitwise or is commutative.
+impl<T: PrimInt> CommutativeMonoid for BitOrM<T> {}
+
+/// Bitwise or is idempotent.
+impl<T: PrimInt> Band for BitOrM<T> {}
+
+/// Bitwise or is set union.
+impl<T: PrimInt> Semilattice for BitOrM<T> {}
+
+/// Integers under bitwise and
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitAndM<T>(pub T);
+
+/// Keep only the bits set on both sides.
+impl<T: PrimInt> Semigroup for BitAndM<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(&BitAndM(x): &Self, &BitAndM(y): &Self) -> Self {
+        BitAndM(x & y)
+    }
+}
+
+/// All bits set.
+impl<T: PrimInt> Monoid for BitAndM<T> {
+    fn zero() -> Self {
+        BitAndM(!T::zero())
+    }
+}
+
+/// And in place.
+impl<T: PrimInt> SemigroupAssign for BitAndM<T> {}
+
+/// Bitwise and is commutative.
+impl<T: PrimInt> CommutativeMonoid for BitAndM<T> {}
+
+/// Bitwise and is idempotent.
+impl<T: PrimInt> Band for BitAndM<T> {}
+
+/// Bitwise and is set intersection.
+impl<T: PrimInt> Semilattice for BitAndM<T> {}
+
+/// Integers under bitwise exclusive or
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitXorM<T>(pub T);
+
+/// Flip the bits set on the right.
+impl<T: PrimInt> Semigroup for BitXorM<T> {
+    fn op(&BitXorM(x): &Self, &BitXorM(y): &Self) -> Self {
+        BitXorM(x ^ y)
+    }
+}
+
+/// No bits set.
+impl<T: PrimInt> Monoid for BitXorM<T> {
+    fn zero() -> Self {
+        BitXorM(T::zero())
+    }
+}
+
+/// Xor in place.
+impl<T: PrimInt> SemigroupAssign for BitXorM<T> {}
+
+/// Bitwise xor is commutative.
+impl<T: PrimInt> CommutativeMonoid for BitXorM<T> {}
+
+/// Everything is its own inverse.
+impl<T: PrimInt> Group for BitXorM<T> {
+    fn invert(&self) -> Self {
+        *self
+    }
+}
+
+/// Xor can always be undone.
+impl<T: PrimInt> CancellativeMonoid for BitXorM<T> {}
+
+/// Xor and and make bit vectors into a ring (a power of the two-element field).
+impl<T: PrimInt> Semiring for BitXorM<T> {
+    fn mul(&BitXorM(x): &Self, &BitXorM(y): &Self) -> Self {
+        BitXorM(x & y)
+    }
+    fn one() -> Self {
+        BitXorM(!T::zero())
+    }
+}
+
+/// Bitwise and is commutative.
+impl<T: PrimInt> CommutativeSemiring for BitXorM<T> {}
+
+/// Xor is a group, so this is a ring.
+impl<T: PrimInt> Ring for BitXorM<T> {}
+
+/// Sets under intersection
+///
+/// There's no universal set to act as the identity, so this is only a semigroup; use
+/// `Option<Intersection<S>>` for a monoid.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Intersection<S>(pub S);
+
+/// Keep the elements common to both sides.
+impl<T: Clone + Eq + Hash> Semigroup for Intersection<HashSet<T>> {
+    const IDEMPOTENT: bool = true;
+    fn op(Intersection(x): &Self, Intersection(y): &Self) -> Self {
+        Intersection(x.intersection(y).cloned().collect())
+    }
+}
+
+/// Remove the elements missing from the right.
+impl<T: Clone + Eq + Hash> SemigroupAssign for Intersection<HashSet<T>> {
+    fn op_assign(&mut self, Intersection(other): &Self) {
+        self.0.retain(|t| other.contains(t));
+    }
+}
+
+/// Intersection is idempotent.
+impl<T: Clone + Eq + Hash> Band for Intersection<HashSet<T>> {}
+
+/// Keep the elements common to both sides.
+impl<T: Clone + Ord> Semigroup for Intersection<BTreeSet<T>> {
+    const IDEMPOTENT: bool = true;
+    fn op(Intersection(x): &Self, Intersection(y): &Self) -> Self {
+        Intersection(x.intersection(y).cloned().collect())
+    }
+}
+
+/// Remove the elements missing from the right.
+impl<T: Clone + Ord> SemigroupAssign for Intersection<BTreeSet<T>> {
+    fn op_assign(&mut self, Intersection(other): &Self) {
+        self.0.retain(|t| other.contains(t));
+    }
+}
+
+/// Intersection is idempotent.
+impl<T: Clone + Ord> Band for Intersection<BTreeSet<T>> {}
+
+/// Sets under symmetric difference, a group in which every set is its own inverse; see
+/// [`BitSet`](crate::collections::BitSet)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymmetricDifference<S>(pub S);
+
+#[cfg(feature = "ordered-float")]
+mod total;
+#[cfg(feature = "ordered-float")]
+pub use total::{TotalFloat, TotalMax, TotalMin};
+
+#[cfg(feature = "ndarray")]
+mod sum_array;
+#[cfg(feature = "ndarray")]
+pub use sum_array::SumArray;
@@ -0,0 +1,54 @@
+//! Element-wise sums of `ndarray` arrays
+use ndarray::{Array, Dimension};
+use num_traits::Zero;
+
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Arrays under element-wise addition, with NumPy-style broadcasting
+///
+/// Shapes are matched from the trailing axis backwards, and along each axis the lengths must agree or one of
+/// them must be 1, in which case that side is repeated; the result takes the larger length.  So a `1×n` row
+/// adds to every row of an `m×n` array.  Combining incompatible shapes panics.
+///
+/// The identity is a single zero broadcast to whatever shape it meets: shape `[1, 1, …]` for a fixed
+/// dimensionality, or a 0-dimensional scalar for `IxDyn`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumArray<A, D: Dimension>(pub Array<A, D>);
+
+/// Add element-wise, broadcasting as needed.
+impl<A: Clone + Zero, D: Dimension> Semigroup for SumArray<A, D> {
+    fn op(SumArray(x): &Self, SumArray(y): &Self) -> Self {
+        SumArray(x + y)
+    }
+    fn op_owned(SumArray(x): Self, SumArray(y): Self) -> Self {
+        if x.shape() == y.shape() {
+            SumArray(x + y)
+        } else {
+            SumArray(&x + &y)
+        }
+    }
+}
+
+/// A single zero, broadcast as needed.
+impl<A: Clone + Zero, D: Dimension> Monoid for SumArray<A, D> {
+    fn zero() -> Self {
+        let mut dim = D::zeros(D::NDIM.unwrap_or(0));
+        dim.slice_mut().iter_mut().for_each(|d| *d = 1);
+        SumArray(Array::from_elem(dim, A::zero()))
+    }
+}
+
+/// Add in place when the right side broadcasts to the left's shape, reallocating otherwise.
+impl<A: Clone + Zero, D: Dimension> SemigroupAssign for SumArray<A, D> {
+    fn op_assign(&mut self, other: &Self) {
+        if other.0.broadcast(self.0.raw_dim()).is_some() {
+            self.0
+                .zip_mut_with(&other.0, |a, b| *a = a.clone() + b.clone());
+        } else {
+            *self = Self::op(self, other);
+        }
+    }
+}
+
+/// Addition is commutative.
+impl<A: Clone + Zero, D: Dimension> CommutativeMonoid for SumArray<A, D> {}
@@ -0,0 +1,120 @@
+//! Extrema of floats given a total order by the `ordered-float` crate
+use ordered_float::{FloatCore, NotNan, OrderedFloat};
+
+use crate::traits::{
+    Band, CommutativeMonoid, Monoid, Monus, OrderedMonoid, Semigroup, SemigroupAssign, Semilattice,
+};
+
+/// A totally ordered float type, along with its least and greatest elements
+///
+/// These differ from [`num_traits::Bounded`], which gives the largest *finite* values and so can't serve as
+/// identities for [`TotalMax`] and [`TotalMin`] once infinities are in play.
+pub trait TotalFloat: Ord + Clone {
+    /// The least element: `-∞`
+    fn least() -> Self;
+    /// The greatest element: `+∞`, or NaN for [`OrderedFloat`], which sorts it above everything
+    fn greatest() -> Self;
+}
+
+impl<F: FloatCore> TotalFloat for OrderedFloat<F> {
+    fn least() -> Self {
+        OrderedFloat(F::neg_infinity())
+    }
+    fn greatest() -> Self {
+        OrderedFloat(F::nan())
+    }
+}
+
+impl<F: FloatCore> TotalFloat for NotNan<F> {
+    fn least() -> Self {
+        NotNan::new(F::neg_infinity()).expect("-∞ isn't NaN")
+    }
+    fn greatest() -> Self {
+        NotNan::new(F::infinity()).expect("∞ isn't NaN")
+    }
+}
+
+/// Totally ordered floats under taking the larger, with `-∞` as the identity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TotalMax<T>(pub T);
+
+/// Keep the larger value (the right one, in case of ties).
+impl<T: TotalFloat> Semigroup for TotalMax<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x.max(y)
+    }
+}
+
+/// Negative infinity is below everything.
+impl<T: TotalFloat> Monoid for TotalMax<T> {
+    fn zero() -> Self {
+        TotalMax(T::least())
+    }
+}
+
+/// Compare in place.
+impl<T: TotalFloat> SemigroupAssign for TotalMax<T> {}
+
+/// Maxima can be taken in any order.
+impl<T: TotalFloat> CommutativeMonoid for TotalMax<T> {}
+
+/// Taking the maximum twice changes nothing.
+impl<T: TotalFloat> Band for TotalMax<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: TotalFloat> Semilattice for TotalMax<T> {}
+
+/// Larger inputs give larger maxima.
+impl<T: TotalFloat> OrderedMonoid for TotalMax<T> {}
+
+/// Anything at most `y` is already accounted for by `y`.
+impl<T: TotalFloat> Monus for TotalMax<T> {
+    fn monus(x: &Self, y: &Self) -> Self {
+        if x <= y {
+            Self::zero()
+        } else {
+            x.clone()
+        }
+    }
+}
+
+/// Totally ordered floats under taking the smaller, with the greatest element as the identity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TotalMin<T>(pub T);
+
+/// Keep the smaller value (the left one, in case of ties).
+impl<T: TotalFloat> Semigroup for TotalMin<T> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        Self::op_owned(x.clone(), y.clone())
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        x.min(y)
+    }
+}
+
+/// The greatest element is above everything.
+impl<T: TotalFloat> Monoid for TotalMin<T> {
+    fn zero() -> Self {
+        TotalMin(T::greatest())
+    }
+}
+
+/// Compare in place.
+impl<T: TotalFloat> SemigroupAssign for TotalMin<T> {}
+
+/// Minima can be taken in any order.
+impl<T: TotalFloat> CommutativeMonoid for TotalMin<T> {}
+
+/// Taking the minimum twice changes nothing.
+impl<T: TotalFloat> Band for TotalMin<T> {}
+
+/// Totally ordered sets are lattices.
+impl<T: TotalFloat> Semilattice for TotalMin<T> {}
+
+/// Larger inputs give larger minima.
+impl<T: TotalFloat> OrderedMonoid for TotalMin<T> {}
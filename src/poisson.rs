@@ -0,0 +1,173 @@
+//! The Poisson distribution, fitted by maximum likelihood
+//!
+//! Each data point is the number of events seen in one unit interval.  The sufficient statistics are the
+//! number of intervals and the total number of events, both integers, so summaries merge exactly.
+
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::distribution::Distribution;
+use crate::special;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Parameterized Poisson distribution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Poisson {
+    /// Number of intervals observed
+    n: u64,
+    /// Total number of events over all intervals
+    total: u64,
+}
+
+impl Poisson {
+    /// Construct from a single interval, in which `k` events occurred.
+    pub fn new(k: u64) -> Self {
+        Poisson { n: 1, total: k }
+    }
+    /// The number of intervals observed.
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+    /// The total number of events.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+    /// The maximum-likelihood rate: the mean number of events per interval.
+    pub fn lambda(&self) -> f64 {
+        assert!(self.n > 0, "Rate requires at least 1 sample.");
+        self.total as f64 / self.n as f64
+    }
+    /// The mean of this distribution, which is [`lambda`](Poisson::lambda).
+    pub fn mean(&self) -> f64 {
+        self.lambda()
+    }
+    /// The variance of this distribution, which is also [`lambda`](Poisson::lambda).
+    pub fn variance(&self) -> f64 {
+        self.lambda()
+    }
+    /// Probability Mass Function.
+    pub fn pmf(&self, k: u64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+    /// Natural logarithm of the [`pmf`](Poisson::pmf), which doesn't underflow in the tails.
+    pub fn log_pmf(&self, k: u64) -> f64 {
+        let l = self.lambda();
+        if l == 0.0 {
+            return if k == 0 { 0.0 } else { f64::NEG_INFINITY };
+        }
+        let k = k as f64;
+        k * l.ln() - l - libm::lgamma(k + 1.0)
+    }
+    /// Cumulative Distribution Function: the probability of at most `k` events.
+    pub fn cdf(&self, k: u64) -> f64 {
+        special::gamma_q(k as f64 + 1.0, self.lambda())
+    }
+}
+
+/// We can add a new interval's count.
+impl Add<u64> for Poisson {
+    type Output = Self;
+    fn add(mut self, k: u64) -> Self::Output {
+        self += k;
+        self
+    }
+}
+
+/// We can add a new interval's count.
+impl AddAssign<u64> for Poisson {
+    fn add_assign(&mut self, k: u64) {
+        self.n += 1;
+        self.total += k;
+    }
+}
+
+/// Accumulate the counts one at a time.
+impl FromIterator<u64> for Poisson {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut p = Self::default();
+        p.extend(iter);
+        p
+    }
+}
+
+/// Accumulate the counts one at a time.
+impl<'a> FromIterator<&'a u64> for Poisson {
+    fn from_iter<I: IntoIterator<Item = &'a u64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the counts one at a time.
+impl Extend<u64> for Poisson {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for k in iter {
+            *self += k
+        }
+    }
+}
+
+/// Draw samples from the fitted distribution, by bisecting the CDF.
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<u64> for Poisson {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let u = rng.gen::<f64>();
+        let (mut lo, mut hi) = (0, (self.lambda() + 10.0 * self.lambda().sqrt()) as u64 + 10);
+        while self.cdf(hi) <= u {
+            hi *= 2;
+        }
+        // Find the smallest k with cdf(k) > u.
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.cdf(mid) > u {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo
+    }
+}
+
+/// The maximum-likelihood fit.
+impl Distribution for Poisson {
+    type Value = u64;
+    type Real = f64;
+    fn mean(&self) -> f64 {
+        self.mean()
+    }
+    fn variance(&self) -> f64 {
+        self.variance()
+    }
+    fn pdf(&self, k: u64) -> f64 {
+        self.pmf(k)
+    }
+    fn log_pdf(&self, k: u64) -> f64 {
+        self.log_pmf(k)
+    }
+    fn cdf(&self, k: u64) -> f64 {
+        self.cdf(k)
+    }
+}
+
+/// Add the interval and event counts.
+impl Semigroup for Poisson {
+    fn op(x: &Self, y: &Self) -> Self {
+        Poisson {
+            n: x.n + y.n,
+            total: x.total + y.total,
+        }
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for Poisson {}
+
+/// No intervals observed.
+impl Monoid for Poisson {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Addition is commutative.
+impl CommutativeMonoid for Poisson {}
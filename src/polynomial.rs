@@ -0,0 +1,122 @@
+//! Polynomials with coefficients in a semiring
+//!
+//! Over the counting semiring a product of polynomials enumerates combinations (the coefficient of `xᵏ` counts
+//! the ways to reach total `k`); over other semirings the same convolution tracks provenance, reachability,
+//! and so on.
+use crate::traits::{
+    CommutativeMonoid, CommutativeSemiring, Group, Monoid, Ring, Semigroup, SemigroupAssign,
+    Semiring,
+};
+
+/// A polynomial `c₀ + c₁x + c₂x² + …`, stored as its coefficients from lowest degree up
+///
+/// Trailing zero coefficients are trimmed, so equality is structural and the zero polynomial has no
+/// coefficients at all.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Poly<R>(Vec<R>);
+
+impl<R: Semiring + Clone + PartialEq> Poly<R> {
+    /// The polynomial with the given coefficients, lowest degree first.
+    pub fn new(mut coeffs: Vec<R>) -> Self {
+        let zero = R::zero();
+        while coeffs.last() == Some(&zero) {
+            coeffs.pop();
+        }
+        Poly(coeffs)
+    }
+    /// The constant polynomial `c`.
+    pub fn constant(c: R) -> Self {
+        Self::new(vec![c])
+    }
+    /// The monomial `c·xᵏ`.
+    pub fn monomial(c: R, k: usize) -> Self {
+        let mut coeffs = vec![R::zero(); k];
+        coeffs.push(c);
+        Self::new(coeffs)
+    }
+    /// The coefficients, lowest degree first, without trailing zeros.
+    pub fn coeffs(&self) -> &[R] {
+        &self.0
+    }
+    /// The coefficient of `xᵏ`.
+    pub fn coeff(&self, k: usize) -> R {
+        self.0.get(k).cloned().unwrap_or_else(R::zero)
+    }
+    /// The highest power with a nonzero coefficient, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.0.len().checked_sub(1)
+    }
+    /// Evaluate at `x` by Horner's rule.
+    pub fn eval(&self, x: &R) -> R {
+        self.0
+            .iter()
+            .rev()
+            .fold(R::zero(), |acc, c| R::op(&R::mul(&acc, x), c))
+    }
+}
+
+/// Add coefficients of like powers.
+impl<R: Semiring + Clone + PartialEq> Semigroup for Poly<R> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The zero polynomial.
+impl<R: Semiring + Clone + PartialEq> Monoid for Poly<R> {
+    fn zero() -> Self {
+        Poly(Vec::new())
+    }
+}
+
+/// Add coefficients in place.
+impl<R: Semiring + Clone + PartialEq> SemigroupAssign for Poly<R> {
+    fn op_assign(&mut self, Poly(ys): &Self) {
+        let n = self.0.len().min(ys.len());
+        for (a, b) in self.0.iter_mut().zip(ys) {
+            *a = R::op(a, b);
+        }
+        self.0.extend_from_slice(&ys[n..]);
+        let zero = R::zero();
+        while self.0.last() == Some(&zero) {
+            self.0.pop();
+        }
+    }
+}
+
+/// Addition is commutative.
+impl<R: Semiring + Clone + PartialEq> CommutativeMonoid for Poly<R> {}
+
+/// Negate every coefficient.
+impl<R: Ring + Clone + PartialEq> Group for Poly<R> {
+    fn invert(&self) -> Self {
+        Poly(self.0.iter().map(R::invert).collect())
+    }
+}
+
+/// Convolution of coefficients, i.e. the usual product of polynomials.
+impl<R: Semiring + Clone + PartialEq> Semiring for Poly<R> {
+    fn mul(Poly(xs): &Self, Poly(ys): &Self) -> Self {
+        if xs.is_empty() || ys.is_empty() {
+            return Poly(Vec::new());
+        }
+        let mut zs = vec![R::zero(); xs.len() + ys.len() - 1];
+        for (i, x) in xs.iter().enumerate() {
+            for (j, y) in ys.iter().enumerate() {
+                zs[i + j] = R::op(&zs[i + j], &R::mul(x, y));
+            }
+        }
+        Self::new(zs)
+    }
+    fn one() -> Self {
+        Self::constant(R::one())
+    }
+}
+
+/// Polynomials over a commutative semiring commute.
+impl<R: CommutativeSemiring + Clone + PartialEq> CommutativeSemiring for Poly<R> {}
+
+/// Polynomials over a ring form a ring.
+impl<R: Ring + Clone + PartialEq> Ring for Poly<R> {}
@@ -1,4 +1,15 @@
 //! Macros for generating property tests for the required properties
+use crate::traits::{Monoid, Semiring};
+
+/// The identity of whichever monoid `x` belongs to, to help type inference along
+pub(crate) fn zero_like<M: Monoid>(_: &M) -> M {
+    M::zero()
+}
+
+/// The multiplicative identity of whichever semiring `x` belongs to, to help type inference along
+pub(crate) fn one_like<R: Semiring>(_: &R) -> R {
+    R::one()
+}
 
 macro_rules! semigroup_properties {
     ($arb:expr) => {
@@ -54,6 +65,184 @@ macro_rules! commutative_monoid_properties {
 
 pub(crate) use commutative_monoid_properties;
 
+macro_rules! op_owned_properties {
+    ($arb:expr) => {
+        proptest! {
+            #[test]
+            fn op_owned_agrees_with_op(x in $arb(), y in $arb()) {
+                let z = Semigroup::op(&x, &y);
+                prop_assert_eq!(Semigroup::op_owned(x, y), z);
+            }
+        }
+    };
+}
+
+pub(crate) use op_owned_properties;
+
+macro_rules! semigroup_assign_properties {
+    ($arb:expr) => {
+        proptest! {
+            #[test]
+            fn op_assign_agrees_with_op(x in $arb(), y in $arb()) {
+                let z = Semigroup::op(&x, &y);
+                let mut x = x;
+                x.op_assign(&y);
+                prop_assert_eq!(x, z);
+            }
+        }
+    };
+}
+
+pub(crate) use semigroup_assign_properties;
+
+macro_rules! band_properties {
+    ($arb:expr) => {
+        mod semigroup_properties {
+            use super::*;
+            semigroup_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn idempotency(x in $arb()) {
+                fn flagged<S: Semigroup>(_: &S) -> bool {
+                    S::IDEMPOTENT
+                }
+                prop_assert!(flagged(&x));
+                prop_assert_eq!(Semigroup::op(&x, &x), x);
+            }
+        }
+    };
+}
+
+pub(crate) use band_properties;
+
+macro_rules! semilattice_properties {
+    ($arb:expr) => {
+        mod commutative_monoid_properties {
+            use super::*;
+            commutative_monoid_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn idempotency(x in $arb()) {
+                fn flagged<S: Semigroup>(_: &S) -> bool {
+                    S::IDEMPOTENT
+                }
+                prop_assert!(flagged(&x));
+                prop_assert_eq!(Semigroup::op(&x, &x), x);
+            }
+        }
+    };
+}
+
+pub(crate) use semilattice_properties;
+
+macro_rules! cancellative_monoid_properties {
+    ($arb:expr) => {
+        mod monoid_properties {
+            use super::*;
+            monoid_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn left_cancellation(x in $arb(), y in $arb(), z in $arb()) {
+                if Semigroup::op(&x, &y) == Semigroup::op(&x, &z) {
+                    prop_assert_eq!(y, z);
+                }
+            }
+            #[test]
+            fn right_cancellation(x in $arb(), y in $arb(), z in $arb()) {
+                if Semigroup::op(&y, &x) == Semigroup::op(&z, &x) {
+                    prop_assert_eq!(y, z);
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use cancellative_monoid_properties;
+
+macro_rules! monus_properties {
+    ($arb:expr) => {
+        mod commutative_monoid_properties {
+            use super::*;
+            commutative_monoid_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn symmetric_completion(x in $arb(), y in $arb()) {
+                prop_assert_eq!(
+                    Semigroup::op(&x, &Monus::monus(&y, &x)),
+                    Semigroup::op(&y, &Monus::monus(&x, &y))
+                );
+            }
+            #[test]
+            fn successive_subtraction(x in $arb(), y in $arb(), z in $arb()) {
+                prop_assert_eq!(
+                    Monus::monus(&Monus::monus(&x, &y), &z),
+                    Monus::monus(&x, &Semigroup::op(&y, &z))
+                );
+            }
+            #[test]
+            fn self_subtraction(x in $arb()) {
+                prop_assert_eq!(Monus::monus(&x, &x), zero_like(&x));
+            }
+            #[test]
+            fn zero_subtraction(x in $arb()) {
+                prop_assert_eq!(Monus::monus(&zero_like(&x), &x), zero_like(&x));
+            }
+        }
+    };
+}
+
+pub(crate) use monus_properties;
+
+macro_rules! ordered_monoid_properties {
+    ($arb:expr) => {
+        mod monoid_properties {
+            use super::*;
+            monoid_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn left_monotonicity(x in $arb(), y in $arb(), z in $arb()) {
+                if x <= y {
+                    prop_assert!(Semigroup::op(&x, &z) <= Semigroup::op(&y, &z));
+                }
+            }
+            #[test]
+            fn right_monotonicity(x in $arb(), y in $arb(), z in $arb()) {
+                if x <= y {
+                    prop_assert!(Semigroup::op(&z, &x) <= Semigroup::op(&z, &y));
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use ordered_monoid_properties;
+
+macro_rules! group_properties {
+    ($arb:expr) => {
+        mod monoid_properties {
+            use super::*;
+            monoid_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn left_inverse(x in $arb()) {
+                prop_assert_eq!(Semigroup::op(&x.invert(), &x), zero_like(&x));
+            }
+            #[test]
+            fn right_inverse(x in $arb()) {
+                prop_assert_eq!(Semigroup::op(&x, &x.invert()), zero_like(&x));
+            }
+        }
+    };
+}
+
+pub(crate) use group_properties;
+
 macro_rules! semiring_properties {
     ($arb: expr) => {
         mod commutative_monoid_properties {
@@ -63,11 +252,11 @@ macro_rules! semiring_properties {
         proptest! {
             #[test]
             fn left_annihilation(x in $arb()) {
-                prop_assert_eq!(Semiring::mul(&Monoid::zero(), &x), Monoid::zero());
+                prop_assert_eq!(Semiring::mul(&zero_like(&x), &x), zero_like(&x));
             }
             #[test]
             fn right_annihilation(x in $arb()) {
-                prop_assert_eq!(Semiring::mul(&x, &Monoid::zero()), Monoid::zero());
+                prop_assert_eq!(Semiring::mul(&x, &zero_like(&x)), zero_like(&x));
             }
             #[test]
             fn left_one(x in $arb()) {
@@ -101,3 +290,248 @@ macro_rules! semiring_properties {
 }
 
 pub(crate) use semiring_properties;
+
+macro_rules! commutative_semiring_properties {
+    ($arb: expr) => {
+        mod semiring_properties {
+            use super::*;
+            semiring_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn multiplicative_commutativity(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Semiring::mul(&x, &y), Semiring::mul(&y, &x));
+            }
+        }
+    };
+}
+
+pub(crate) use commutative_semiring_properties;
+
+macro_rules! star_semiring_properties {
+    ($arb: expr) => {
+        mod semiring_properties {
+            use super::*;
+            semiring_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn left_unfolding(x in $arb()) {
+                prop_assert_eq!(
+                    StarSemiring::star(&x),
+                    Semigroup::op(&Semiring::one(), &Semiring::mul(&x, &StarSemiring::star(&x)))
+                );
+            }
+            #[test]
+            fn right_unfolding(x in $arb()) {
+                prop_assert_eq!(
+                    StarSemiring::star(&x),
+                    Semigroup::op(&Semiring::one(), &Semiring::mul(&StarSemiring::star(&x), &x))
+                );
+            }
+        }
+    };
+}
+
+pub(crate) use star_semiring_properties;
+
+macro_rules! ring_properties {
+    ($arb: expr) => {
+        mod semiring_properties {
+            use super::*;
+            semiring_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn left_negation(x in $arb()) {
+                prop_assert_eq!(Semigroup::op(&x.invert(), &x), zero_like(&x));
+            }
+            #[test]
+            fn right_negation(x in $arb()) {
+                prop_assert_eq!(Semigroup::op(&x, &x.invert()), zero_like(&x));
+            }
+        }
+    };
+}
+
+pub(crate) use ring_properties;
+
+macro_rules! field_properties {
+    ($arb: expr) => {
+        mod ring_properties {
+            use super::*;
+            ring_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn multiplicative_commutativity(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Semiring::mul(&x, &y), Semiring::mul(&y, &x));
+            }
+            #[test]
+            fn left_reciprocal(x in $arb()) {
+                prop_assume!(x != zero_like(&x));
+                prop_assert_eq!(Semiring::mul(&x.reciprocal(), &x), one_like(&x));
+            }
+            #[test]
+            fn right_reciprocal(x in $arb()) {
+                prop_assume!(x != zero_like(&x));
+                prop_assert_eq!(Semiring::mul(&x, &x.reciprocal()), one_like(&x));
+            }
+        }
+    };
+}
+
+pub(crate) use field_properties;
+
+macro_rules! monoid_action_properties {
+    ($arb_m:expr, $arb_x:expr) => {
+        proptest! {
+            #[test]
+            fn identity_action(m in $arb_m(), x in $arb_x()) {
+                prop_assert_eq!(zero_like(&m).act(&x), x);
+            }
+            #[test]
+            fn compatibility(m in $arb_m(), n in $arb_m(), x in $arb_x()) {
+                prop_assert_eq!(Semigroup::op(&m, &n).act(&x), m.act(&n.act(&x)));
+            }
+        }
+    };
+}
+
+pub(crate) use monoid_action_properties;
+
+macro_rules! homomorphism_properties {
+    ($hom:ty, $arb:expr) => {
+        proptest! {
+            #[test]
+            fn preserves_op(x in $arb(), y in $arb()) {
+                prop_assert_eq!(
+                    <$hom as MonoidHom<_, _>>::apply(&Semigroup::op(&x, &y)),
+                    Semigroup::op(&<$hom as MonoidHom<_, _>>::apply(&x), &<$hom as MonoidHom<_, _>>::apply(&y))
+                );
+            }
+            #[test]
+            fn preserves_zero(x in $arb()) {
+                let fz = <$hom as MonoidHom<_, _>>::apply(&zero_like(&x));
+                prop_assert_eq!(&fz, &zero_like(&fz));
+            }
+        }
+    };
+}
+
+pub(crate) use homomorphism_properties;
+
+macro_rules! lattice_properties {
+    ($arb: expr) => {
+        proptest! {
+            #[test]
+            fn join_associativity(x in $arb(), y in $arb(), z in $arb()) {
+                prop_assert_eq!(
+                    Lattice::join(&x, &Lattice::join(&y, &z)),
+                    Lattice::join(&Lattice::join(&x, &y), &z)
+                );
+            }
+            #[test]
+            fn meet_associativity(x in $arb(), y in $arb(), z in $arb()) {
+                prop_assert_eq!(
+                    Lattice::meet(&x, &Lattice::meet(&y, &z)),
+                    Lattice::meet(&Lattice::meet(&x, &y), &z)
+                );
+            }
+            #[test]
+            fn join_commutativity(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Lattice::join(&x, &y), Lattice::join(&y, &x));
+            }
+            #[test]
+            fn meet_commutativity(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Lattice::meet(&x, &y), Lattice::meet(&y, &x));
+            }
+            #[test]
+            fn join_idempotency(x in $arb()) {
+                prop_assert_eq!(Lattice::join(&x, &x), x);
+            }
+            #[test]
+            fn meet_idempotency(x in $arb()) {
+                prop_assert_eq!(Lattice::meet(&x, &x), x);
+            }
+            #[test]
+            fn join_absorption(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Lattice::join(&x, &Lattice::meet(&x, &y)), x);
+            }
+            #[test]
+            fn meet_absorption(x in $arb(), y in $arb()) {
+                prop_assert_eq!(Lattice::meet(&x, &Lattice::join(&x, &y)), x);
+            }
+        }
+    };
+}
+
+pub(crate) use lattice_properties;
+
+macro_rules! bounded_lattice_properties {
+    ($arb: expr) => {
+        mod lattice_properties {
+            use super::*;
+            lattice_properties!($arb);
+        }
+        proptest! {
+            #[test]
+            fn bottom_is_join_identity(x in $arb()) {
+                prop_assert_eq!(Lattice::join(&BoundedLattice::bottom(), &x), x);
+            }
+            #[test]
+            fn top_is_meet_identity(x in $arb()) {
+                prop_assert_eq!(Lattice::meet(&BoundedLattice::top(), &x), x);
+            }
+            #[test]
+            fn top_absorbs_join(x in $arb()) {
+                prop_assert_eq!(Lattice::join(&BoundedLattice::top(), &x), BoundedLattice::top());
+            }
+            #[test]
+            fn bottom_absorbs_meet(x in $arb()) {
+                prop_assert_eq!(Lattice::meet(&BoundedLattice::bottom(), &x), BoundedLattice::bottom());
+            }
+        }
+    };
+}
+
+pub(crate) use bounded_lattice_properties;
+
+macro_rules! semimodule_properties {
+    ($arb_r: expr, $arb_x: expr) => {
+        proptest! {
+            #[test]
+            fn left_distribution(r in $arb_r(), x in $arb_x(), y in $arb_x()) {
+                prop_assert_eq!(
+                    Semimodule::scale(&r, &Semigroup::op(&x, &y)),
+                    Semigroup::op(&Semimodule::scale(&r, &x), &Semimodule::scale(&r, &y))
+                );
+            }
+            #[test]
+            fn right_distribution(r in $arb_r(), s in $arb_r(), x in $arb_x()) {
+                prop_assert_eq!(
+                    Semimodule::scale(&Semigroup::op(&r, &s), &x),
+                    Semigroup::op(&Semimodule::scale(&r, &x), &Semimodule::scale(&s, &x))
+                );
+            }
+            #[test]
+            fn compatibility(r in $arb_r(), s in $arb_r(), x in $arb_x()) {
+                prop_assert_eq!(
+                    Semimodule::scale(&Semiring::mul(&r, &s), &x),
+                    Semimodule::scale(&r, &Semimodule::scale(&s, &x))
+                );
+            }
+            #[test]
+            fn one_scales_trivially(r in $arb_r(), x in $arb_x()) {
+                prop_assert_eq!(Semimodule::scale(&one_like(&r), &x), x);
+            }
+            #[test]
+            fn zero_annihilates(r in $arb_r(), x in $arb_x()) {
+                prop_assert_eq!(Semimodule::scale(&zero_like(&r), &x), zero_like(&x));
+                prop_assert_eq!(Semimodule::scale(&r, &zero_like(&x)), zero_like(&x));
+            }
+        }
+    };
+}
+
+pub(crate) use semimodule_properties;
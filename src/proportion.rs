@@ -0,0 +1,184 @@
+//! The proportion of successes in a series of yes/no trials
+//!
+//! Successes and trials are counted exactly, so summaries from separate shards (the arms of an A/B test,
+//! say, split across servers) merge without loss; see
+//! [`two_proportion_z_test`](crate::stats::tests::two_proportion_z_test) for comparing two of them.
+
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::distribution::Distribution;
+use crate::special;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Counts of successes out of trials, i.e. a fitted Bernoulli or binomial distribution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Proportion {
+    /// Number of successful trials
+    successes: u64,
+    /// Number of trials
+    trials: u64,
+}
+
+impl Proportion {
+    /// Construct from a single trial.
+    pub fn new(success: bool) -> Self {
+        Proportion {
+            successes: u64::from(success),
+            trials: 1,
+        }
+    }
+    /// Construct from counts; there can't be more successes than trials.
+    pub fn of(successes: u64, trials: u64) -> Self {
+        assert!(successes <= trials, "Successes can't exceed trials.");
+        Proportion { successes, trials }
+    }
+    /// The number of successes.
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+    /// The number of failures.
+    pub fn failures(&self) -> u64 {
+        self.trials - self.successes
+    }
+    /// The number of trials.
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+    /// The observed proportion of successes, the maximum-likelihood estimate of the success probability.
+    pub fn p_hat(&self) -> f64 {
+        assert!(self.trials > 0, "Proportion requires at least 1 trial.");
+        self.successes as f64 / self.trials as f64
+    }
+    /// Standard error of [`p_hat`](Proportion::p_hat).
+    pub fn std_error(&self) -> f64 {
+        let p = self.p_hat();
+        (p * (1.0 - p) / self.trials as f64).sqrt()
+    }
+    /// Wilson score interval for the success probability at the given `level`, e.g. `0.95`; unlike the
+    /// textbook normal approximation, it behaves well near 0 and 1 and for few trials.
+    pub fn wilson_interval(&self, level: f64) -> (f64, f64) {
+        let (p, n) = (self.p_hat(), self.trials as f64);
+        let z = special::probit((1.0 + level) / 2.0);
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half_width = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        // The bounds contain the estimate exactly, but rounding could push one past it when it's 0 or 1.
+        (
+            (center - half_width).clamp(0.0, p),
+            (center + half_width).clamp(p, 1.0),
+        )
+    }
+    /// Jeffreys interval for the success probability at the given `level`, e.g. `0.95`: the equal-tailed
+    /// credible interval under the Jeffreys prior `Beta(½, ½)`.
+    pub fn jeffreys_interval(&self, level: f64) -> (f64, f64) {
+        assert!(self.trials > 0, "Proportion requires at least 1 trial.");
+        let a = self.successes as f64 + 0.5;
+        let b = self.failures() as f64 + 0.5;
+        let tail = (1.0 - level) / 2.0;
+        let lo = if self.successes == 0 {
+            0.0
+        } else {
+            special::beta_quantile(tail, a, b)
+        };
+        let hi = if self.successes == self.trials {
+            1.0
+        } else {
+            special::beta_quantile(1.0 - tail, a, b)
+        };
+        (lo, hi)
+    }
+}
+
+/// Draw trials from the fitted Bernoulli distribution; this needs at least one trial.
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<bool> for Proportion {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        rng.gen_bool(self.p_hat())
+    }
+}
+
+/// The Bernoulli distribution with the observed success probability, ordering failure before success.
+impl Distribution for Proportion {
+    type Value = bool;
+    type Real = f64;
+    fn mean(&self) -> f64 {
+        self.p_hat()
+    }
+    fn variance(&self) -> f64 {
+        let p = self.p_hat();
+        p * (1.0 - p)
+    }
+    fn pdf(&self, success: bool) -> f64 {
+        let p = self.p_hat();
+        if success {
+            p
+        } else {
+            1.0 - p
+        }
+    }
+    fn cdf(&self, success: bool) -> f64 {
+        if success {
+            1.0
+        } else {
+            1.0 - self.p_hat()
+        }
+    }
+}
+
+/// We can add the outcome of a new trial.
+impl Add<bool> for Proportion {
+    type Output = Self;
+    fn add(mut self, success: bool) -> Self::Output {
+        self += success;
+        self
+    }
+}
+
+/// We can add the outcome of a new trial.
+impl AddAssign<bool> for Proportion {
+    fn add_assign(&mut self, success: bool) {
+        self.op_assign(&Self::new(success));
+    }
+}
+
+/// Count the outcomes one at a time.
+impl FromIterator<bool> for Proportion {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut p = Self::default();
+        p.extend(iter);
+        p
+    }
+}
+
+/// Count the outcomes one at a time.
+impl Extend<bool> for Proportion {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for success in iter {
+            *self += success
+        }
+    }
+}
+
+/// Add the success and trial counts.
+impl Semigroup for Proportion {
+    fn op(x: &Self, y: &Self) -> Self {
+        Proportion {
+            successes: x.successes + y.successes,
+            trials: x.trials + y.trials,
+        }
+    }
+}
+
+/// Merge in place.
+impl SemigroupAssign for Proportion {}
+
+/// No trials.
+impl Monoid for Proportion {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Addition is commutative.
+impl CommutativeMonoid for Proportion {}
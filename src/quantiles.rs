@@ -0,0 +1,595 @@
+//! Mergeable sketches for estimating quantiles of large or distributed data
+//!
+//! [`DdSketch`] follows Masson, Rim & Lee, [DDSketch: A Fast and Fully-Mergeable Quantile Sketch with
+//! Relative-Error Guarantees](https://www.vldb.org/pvldb/vol12/p2195-masson.pdf).  Values are bucketed on
+//! a logarithmic scale fine enough that any value in a bucket is within a relative distance `alpha` of the
+//! bucket's representative, so every quantile estimate, however far out in the tail, carries the same
+//! relative guarantee.  Buckets depend only on `alpha`, so merging sketches adds counts and loses nothing.
+//!
+//! [`MomentsSketch`] follows Gan et al., [Moment-Based Quantile Sketches for Efficient High Cardinality
+//! Aggregation Queries](https://www.vldb.org/pvldb/vol11/p1647-gan.pdf).  It keeps nothing but the extrema
+//! and the first few power sums, so merging is vector addition and the sketch is tiny; quantiles come from
+//! the maximum-entropy density matching those moments, which is smooth and usually close, though without
+//! any worst-case guarantee.
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::special::close;
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// A quantile sketch with relative error at most `alpha`
+///
+/// Storage is a bucket per occupied power of `γ = (1 + alpha) / (1 − alpha)`, which grows with the logarithm
+/// of the range of the data rather than with its size.  NaN is ignored.
+#[derive(Clone, Debug)]
+pub struct DdSketch {
+    /// Relative accuracy
+    alpha: f64,
+    /// `ln γ`, the width of a bucket on a log scale
+    ln_gamma: f64,
+    /// Counts of positive values by bucket
+    positive: BTreeMap<i64, u64>,
+    /// Counts of negative values by the bucket of their magnitude
+    negative: BTreeMap<i64, u64>,
+    /// Count of zeros
+    zeros: u64,
+    /// Total count
+    count: u64,
+    /// Smallest value seen, or +∞
+    min: f64,
+    /// Largest value seen, or −∞
+    max: f64,
+}
+
+/// Any two empty sketches are equal; otherwise, accuracies and counts must match exactly.
+impl PartialEq for DdSketch {
+    fn eq(&self, other: &DdSketch) -> bool {
+        (self.is_empty() && other.is_empty())
+            || (self.alpha == other.alpha
+                && self.positive == other.positive
+                && self.negative == other.negative
+                && self.zeros == other.zeros
+                && self.count == other.count
+                && self.min == other.min
+                && self.max == other.max)
+    }
+}
+
+impl DdSketch {
+    /// An empty sketch with relative accuracy `alpha`, which must lie strictly between 0 and 1.
+    pub fn new(alpha: f64) -> DdSketch {
+        assert!(
+            alpha > 0.0 && alpha < 1.0,
+            "Relative accuracy must lie strictly between 0 and 1."
+        );
+        DdSketch {
+            alpha,
+            ln_gamma: ((1.0 + alpha) / (1.0 - alpha)).ln(),
+            ..Self::zero()
+        }
+    }
+    /// Construct from a single data point with relative accuracy `alpha`.
+    pub fn of(x: f64, alpha: f64) -> DdSketch {
+        let mut s = Self::new(alpha);
+        s.insert(x);
+        s
+    }
+    /// Add a data point.
+    ///
+    /// Panics on the identity [`zero`](Monoid::zero), which has no accuracy to bucket the point with; start
+    /// from [`new`](DdSketch::new) instead.
+    pub fn insert(&mut self, x: f64) {
+        assert!(
+            !self.alpha.is_nan(),
+            "Can't insert without an accuracy; start from DdSketch::new."
+        );
+        if x.is_nan() {
+            return;
+        }
+        if x > 0.0 {
+            *self.positive.entry(self.key(x)).or_insert(0) += 1;
+        } else if x < 0.0 {
+            *self.negative.entry(self.key(-x)).or_insert(0) += 1;
+        } else {
+            self.zeros += 1;
+        }
+        self.count += 1;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+    /// The relative accuracy.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Whether no data has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The smallest value seen, or +∞ if there are none.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+    /// The largest value seen, or −∞ if there are none.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+    /// The number of occupied buckets.
+    pub fn buckets(&self) -> usize {
+        self.positive.len() + self.negative.len() + usize::from(self.zeros > 0)
+    }
+    /// An estimate of the `q`-quantile (the value of rank `⌊q (n − 1)⌋` among `n`, counting from zero) within
+    /// a relative distance `alpha` of the truth, or `None` if there's no data.  The minimum and maximum are
+    /// reported exactly.
+    ///
+    /// Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        assert!((0.0..=1.0).contains(&q), "Quantiles must lie in [0, 1].");
+        if self.is_empty() {
+            return None;
+        }
+        let rank = (q * (self.count - 1) as f64).floor() as u64;
+        if rank == 0 {
+            return Some(self.min);
+        } else if rank == self.count - 1 {
+            return Some(self.max);
+        }
+        let negatives = self
+            .negative
+            .iter()
+            .rev()
+            .map(|(&k, &n)| (-self.value(k), n));
+        let zeros = std::iter::once((0.0, self.zeros));
+        let positives = self.positive.iter().map(|(&k, &n)| (self.value(k), n));
+        let mut seen = 0;
+        for (x, n) in negatives.chain(zeros).chain(positives) {
+            seen += n;
+            if seen > rank {
+                return Some(x.clamp(self.min, self.max));
+            }
+        }
+        unreachable!("the counts add up to the total")
+    }
+    /// An estimate of the median, within a relative distance `alpha` of the truth.
+    pub fn median(&self) -> Option<f64> {
+        self.quantile(0.5)
+    }
+    /// The bucket of a positive value: `⌈log_γ x⌉`.
+    fn key(&self, x: f64) -> i64 {
+        (x.ln() / self.ln_gamma).ceil() as i64
+    }
+    /// The representative of bucket `k`, which is within a relative distance `alpha` of everything in
+    /// `(γ^(k-1), γ^k]`.
+    fn value(&self, k: i64) -> f64 {
+        (k as f64 * self.ln_gamma).exp() * (1.0 - self.alpha)
+    }
+}
+
+/// We can add a new data point.
+impl Add<f64> for DdSketch {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self.insert(x);
+        self
+    }
+}
+
+/// We can add a new data point.
+impl AddAssign<f64> for DdSketch {
+    fn add_assign(&mut self, x: f64) {
+        self.insert(x);
+    }
+}
+
+/// Add the points one at a time.
+impl Extend<f64> for DdSketch {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.insert(x)
+        }
+    }
+}
+
+/// Add the counts bucket by bucket.  Panics if both sides are non-empty with different accuracies.
+impl Semigroup for DdSketch {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = x.clone();
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty sketch, which merges with one of any accuracy and takes on that accuracy.
+impl Monoid for DdSketch {
+    fn zero() -> Self {
+        DdSketch {
+            alpha: f64::NAN,
+            ln_gamma: f64::NAN,
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+            zeros: 0,
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+/// Add the other side's counts in place.
+impl SemigroupAssign for DdSketch {
+    fn op_assign(&mut self, other: &Self) {
+        if other.is_empty() {
+            // Keep whichever accuracy is known, so folds starting from `zero` can still insert.
+            if self.alpha.is_nan() {
+                self.alpha = other.alpha;
+                self.ln_gamma = other.ln_gamma;
+            }
+            return;
+        } else if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        assert_eq!(
+            self.alpha, other.alpha,
+            "Can't merge sketches with different accuracies."
+        );
+        for (&k, &n) in &other.positive {
+            *self.positive.entry(k).or_insert(0) += n;
+        }
+        for (&k, &n) in &other.negative {
+            *self.negative.entry(k).or_insert(0) += n;
+        }
+        self.zeros += other.zeros;
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+/// Counts can be added in any order.
+impl CommutativeMonoid for DdSketch {}
+
+/// The extrema and power sums `Σ xⁱ` for `i = 1..=K` of a dataset
+///
+/// Power sums are added naïvely, so they lose precision when the data sit far from zero relative to their
+/// spread, or span many orders of magnitude; when the highest moments become unreliable, the quantile
+/// estimate falls back to fewer of them.
+#[derive(Clone, Copy, Debug)]
+pub struct MomentsSketch<const K: usize = 10> {
+    /// Count of datapoints (stored as a float for convenience)
+    n: f64,
+    /// Smallest value seen, or +∞
+    min: f64,
+    /// Largest value seen, or −∞
+    max: f64,
+    /// `sums[i]` is the sum of the `i + 1`th powers
+    sums: [f64; K],
+}
+
+impl<const K: usize> PartialEq for MomentsSketch<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n
+            && self.min == other.min
+            && self.max == other.max
+            && self
+                .sums
+                .iter()
+                .zip(&other.sums)
+                .all(|(&x, &y)| close(x, y))
+    }
+}
+
+impl<const K: usize> Default for MomentsSketch<K> {
+    fn default() -> Self {
+        MomentsSketch {
+            n: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            sums: [0.0; K],
+        }
+    }
+}
+
+/// Cells in the grid on which the maximum-entropy density is fitted
+const GRID: usize = 1024;
+
+impl<const K: usize> MomentsSketch<K> {
+    /// Construct from a single data point.
+    pub fn new(x: f64) -> Self {
+        let mut sums = [0.0; K];
+        let mut p = 1.0;
+        for s in sums.iter_mut() {
+            p *= x;
+            *s = p;
+        }
+        MomentsSketch {
+            n: 1.0,
+            min: x,
+            max: x,
+            sums,
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> f64 {
+        self.n
+    }
+    /// The smallest value seen, or +∞ if there are none.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+    /// The largest value seen, or −∞ if there are none.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+    /// The sum of the `i`th powers, for `i` in `1..=K`.
+    pub fn power_sum(&self, i: usize) -> f64 {
+        assert!((1..=K).contains(&i), "Only powers 1 to K are tracked.");
+        self.sums[i - 1]
+    }
+    /// The mean, or `None` if there's no data.
+    pub fn mean(&self) -> Option<f64> {
+        (K > 0 && self.n > 0.0).then(|| self.sums[0] / self.n)
+    }
+    /// An estimate of the `q`-quantile, or `None` if there's no data.
+    ///
+    /// Each call fits the density afresh; use [`quantiles`](MomentsSketch::quantiles) to estimate several at
+    /// once.  Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.quantiles(&[q]).map(|v| v[0])
+    }
+    /// Estimates of several quantiles from a single fit, or `None` if there's no data.
+    ///
+    /// Panics if any `q` is outside `[0, 1]`.
+    pub fn quantiles(&self, qs: &[f64]) -> Option<Vec<f64>> {
+        for q in qs {
+            assert!((0.0..=1.0).contains(q), "Quantiles must lie in [0, 1].");
+        }
+        if self.n == 0.0 {
+            return None;
+        }
+        if self.min == self.max {
+            return Some(vec![self.min; qs.len()]);
+        }
+        let cdf = self.fitted_cdf();
+        let (c, r) = ((self.max + self.min) / 2.0, (self.max - self.min) / 2.0);
+        let h = 2.0 / GRID as f64;
+        let quantile = |q: f64| {
+            // cdf[g] is the mass below the right edge of cell g
+            let g = cdf.partition_point(|&p| p < q).min(GRID - 1);
+            let below = if g == 0 { 0.0 } else { cdf[g - 1] };
+            let t = if cdf[g] > below {
+                (q - below) / (cdf[g] - below)
+            } else {
+                0.0
+            };
+            let u = -1.0 + (g as f64 + t.clamp(0.0, 1.0)) * h;
+            (c + r * u).clamp(self.min, self.max)
+        };
+        Some(qs.iter().map(|&q| quantile(q)).collect())
+    }
+    /// The estimated median.
+    pub fn median(&self) -> Option<f64> {
+        self.quantile(0.5)
+    }
+    /// The cumulative mass at the right edge of each grid cell over `[min, max]`, under the maximum-entropy
+    /// density matching as many moments as can be fitted.
+    fn fitted_cdf(&self) -> Vec<f64> {
+        let moments = self.chebyshev_moments();
+        let density = (0..=K)
+            .rev()
+            .find_map(|k| max_entropy(&moments[..=k]))
+            .unwrap_or_else(|| vec![1.0; GRID]);
+        let total: f64 = density.iter().sum();
+        density
+            .iter()
+            .scan(0.0, |acc, &f| {
+                *acc += f / total;
+                Some(*acc)
+            })
+            .collect()
+    }
+    /// `E[T_k(u)]` for `k = 0..=K`, where `u` rescales `[min, max]` to `[−1, 1]` and `T_k` are Chebyshev
+    /// polynomials.
+    fn chebyshev_moments(&self) -> Vec<f64> {
+        let (c, r) = ((self.max + self.min) / 2.0, (self.max - self.min) / 2.0);
+        // E[xʲ] / rʲ, then E[uⁱ] = Σⱼ C(i, j) (E[xʲ] / rʲ) (−c / r)^(i − j)
+        let raw: Vec<f64> = std::iter::once(1.0)
+            .chain((0..K).map(|j| self.sums[j] / self.n / r.powi(j as i32 + 1)))
+            .collect();
+        let shift = -c / r;
+        let mut powers = vec![0.0; K + 1];
+        let mut binomial = vec![1.0; K + 1];
+        for (i, power) in powers.iter_mut().enumerate() {
+            if i > 0 {
+                for j in (1..i).rev() {
+                    binomial[j] += binomial[j - 1];
+                }
+            }
+            *power = (0..=i)
+                .map(|j| binomial[j] * raw[j] * shift.powi((i - j) as i32))
+                .sum::<f64>()
+                .clamp(-1.0, 1.0);
+        }
+        // Power-basis coefficients of each Chebyshev polynomial, by T_{k+1} = 2u T_k − T_{k−1}.
+        let mut cheb = vec![vec![0.0; K + 1]; K + 1];
+        cheb[0][0] = 1.0;
+        if K > 0 {
+            cheb[1][1] = 1.0;
+        }
+        for k in 2..=K {
+            for i in 0..=K {
+                let up = if i > 0 { 2.0 * cheb[k - 1][i - 1] } else { 0.0 };
+                cheb[k][i] = up - cheb[k - 2][i];
+            }
+        }
+        cheb.iter()
+            .map(|t| t.iter().zip(&powers).map(|(a, m)| a * m).sum())
+            .collect()
+    }
+}
+
+/// The maximum-entropy density on `[−1, 1]` with the given Chebyshev moments, sampled at the midpoints of
+/// the grid, or `None` if Newton's method fails to match them.
+fn max_entropy(moments: &[f64]) -> Option<Vec<f64>> {
+    let k = moments.len();
+    let h = 2.0 / GRID as f64;
+    let basis: Vec<Vec<f64>> = (0..GRID)
+        .map(|g| {
+            let u = -1.0 + (g as f64 + 0.5) * h;
+            let mut t = vec![1.0; k];
+            if k > 1 {
+                t[1] = u;
+            }
+            for j in 2..k {
+                t[j] = 2.0 * u * t[j - 1] - t[j - 2];
+            }
+            t
+        })
+        .collect();
+    let density = |lambda: &[f64]| -> Vec<f64> {
+        basis
+            .iter()
+            .map(|t| t.iter().zip(lambda).map(|(a, b)| a * b).sum::<f64>().exp())
+            .collect()
+    };
+    // The convex dual: its gradient vanishes exactly when the density's moments match.
+    let dual = |lambda: &[f64], f: &[f64]| -> f64 {
+        h * f.iter().sum::<f64>() - lambda.iter().zip(moments).map(|(a, m)| a * m).sum::<f64>()
+    };
+    let mut lambda = vec![0.0; k];
+    lambda[0] = 0.5f64.ln();
+    let mut f = density(&lambda);
+    for _ in 0..50 {
+        let mut grad = vec![0.0; k];
+        let mut hess = vec![vec![0.0; k]; k];
+        for (t, &fg) in basis.iter().zip(&f) {
+            for i in 0..k {
+                grad[i] += h * t[i] * fg;
+                for j in 0..=i {
+                    hess[i][j] += h * t[i] * t[j] * fg;
+                }
+            }
+        }
+        for i in 0..k {
+            grad[i] -= moments[i];
+        }
+        if grad.iter().all(|g| g.abs() < 1e-9) {
+            return Some(f);
+        }
+        let step = solve_spd(&hess, &grad)?;
+        let current = dual(&lambda, &f);
+        let mut t = 1.0;
+        loop {
+            let candidate: Vec<f64> = lambda.iter().zip(&step).map(|(l, s)| l - t * s).collect();
+            let g = density(&candidate);
+            let value = dual(&candidate, &g);
+            if value.is_finite() && value <= current {
+                lambda = candidate;
+                f = g;
+                break;
+            }
+            t /= 2.0;
+            if t < 1e-10 {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+/// Solve `a x = b` for symmetric positive-definite `a`, of which only the lower triangle is read.
+fn solve_spd(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let k = b.len();
+    let mut l = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in 0..=i {
+            let s = a[i][j] - (0..j).map(|m| l[i][m] * l[j][m]).sum::<f64>();
+            if i == j {
+                if s <= 0.0 || !s.is_finite() {
+                    return None;
+                }
+                l[i][i] = s.sqrt();
+            } else {
+                l[i][j] = s / l[j][j];
+            }
+        }
+    }
+    let mut y = vec![0.0; k];
+    for i in 0..k {
+        y[i] = (b[i] - (0..i).map(|m| l[i][m] * y[m]).sum::<f64>()) / l[i][i];
+    }
+    let mut x = vec![0.0; k];
+    for i in (0..k).rev() {
+        x[i] = (y[i] - (i + 1..k).map(|m| l[m][i] * x[m]).sum::<f64>()) / l[i][i];
+    }
+    Some(x)
+}
+
+/// We can add a new data point.
+impl<const K: usize> Add<f64> for MomentsSketch<K> {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point.
+impl<const K: usize> AddAssign<f64> for MomentsSketch<K> {
+    fn add_assign(&mut self, x: f64) {
+        self.op_assign(&Self::new(x));
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<const K: usize> FromIterator<f64> for MomentsSketch<K> {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut s = Self::default();
+        for x in iter {
+            s += x
+        }
+        s
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a, const K: usize> FromIterator<&'a f64> for MomentsSketch<K> {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add the counts and power sums, and combine the extrema.
+impl<const K: usize> Semigroup for MomentsSketch<K> {
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The empty sketch.
+impl<const K: usize> Monoid for MomentsSketch<K> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merge in place.
+impl<const K: usize> SemigroupAssign for MomentsSketch<K> {
+    fn op_assign(&mut self, other: &Self) {
+        self.n += other.n;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        for (s, t) in self.sums.iter_mut().zip(&other.sums) {
+            *s += t;
+        }
+    }
+}
+
+/// Addition is commutative.
+impl<const K: usize> CommutativeMonoid for MomentsSketch<K> {}
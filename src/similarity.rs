@@ -0,0 +1,153 @@
+//! Mergeable signatures for estimating set similarity
+//!
+//! [`MinHash`] follows Broder, [On the resemblance and containment of
+//! documents](https://doi.org/10.1109/SEQUEN.1997.666900): under each of `N` hash functions, keep the
+//! smallest hash of any item in the set.  Two sets agree on a slot exactly when the item of the union with
+//! the smallest hash lies in both, which happens with probability equal to their Jaccard similarity.  The
+//! signature of a union is the element-wise min of the signatures, so they merge like any other semilattice.
+//!
+//! For near-duplicate detection among many sets, [`bands`](MinHash::bands) implements the banding trick of
+//! locality-sensitive hashing (see chapter 3 of Leskovec, Rajaraman & Ullman, [Mining of Massive
+//! Datasets](http://www.mmds.org/)): sets sharing any band key are candidates for comparison.
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::hashing;
+use crate::traits::{Band, CommutativeMonoid, Monoid, Semigroup, SemigroupAssign, Semilattice};
+
+/// A signature of `N` minimum hashes
+///
+/// Jaccard estimates have standard error `√(J (1 − J) / N)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MinHash<const N: usize = 128>([u64; N]);
+
+impl<const N: usize> Default for MinHash<N> {
+    fn default() -> Self {
+        const { assert!(N > 0, "a signature needs at least one hash") };
+        MinHash([u64::MAX; N])
+    }
+}
+
+impl<const N: usize> MinHash<N> {
+    /// Construct from a single item.
+    pub fn new<T: Hash + ?Sized>(t: &T) -> Self {
+        let mut s = Self::default();
+        s.insert(t);
+        s
+    }
+    /// Record an item.
+    pub fn insert<T: Hash + ?Sized>(&mut self, t: &T) {
+        let h = hashing::hash(t, 0);
+        for (i, m) in self.0.iter_mut().enumerate() {
+            *m = (*m).min(hashing::mix(h ^ hashing::mix(i as u64)));
+        }
+    }
+    /// The minimum hashes.
+    pub fn signature(&self) -> &[u64; N] {
+        &self.0
+    }
+    /// Whether no items have been seen.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&m| m == u64::MAX)
+    }
+    /// An estimate of the Jaccard similarity `|A ∩ B| / |A ∪ B|` of the two sets: the fraction of slots on
+    /// which the signatures agree.  `None` if both are empty.
+    pub fn jaccard(&self, other: &Self) -> Option<f64> {
+        if self.is_empty() && other.is_empty() {
+            return None;
+        }
+        let agree = self.0.iter().zip(&other.0).filter(|(a, b)| a == b).count();
+        Some(agree as f64 / N as f64)
+    }
+    /// Keys for locality-sensitive hashing: one hash per band of `rows` consecutive slots.
+    ///
+    /// Sets with similarity `s` share at least one key with probability `1 − (1 − s^rows)^(N / rows)`; see
+    /// [`candidate_probability`](MinHash::candidate_probability).  Panics unless `rows` divides `N`.
+    pub fn bands(&self, rows: usize) -> Vec<u64> {
+        assert!(
+            N.is_multiple_of(rows),
+            "The number of rows per band must divide the signature length."
+        );
+        self.0
+            .chunks(rows)
+            .enumerate()
+            .map(|(b, band)| hashing::hash(band, b as u64))
+            .collect()
+    }
+    /// The probability that sets with Jaccard similarity `s` share at least one of their
+    /// [`bands`](MinHash::bands) of `rows` slots.
+    pub fn candidate_probability(s: f64, rows: usize) -> f64 {
+        let bands = (N / rows) as i32;
+        1.0 - (1.0 - s.powi(rows as i32)).powi(bands)
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const N: usize> Add<T> for MinHash<N> {
+    type Output = Self;
+    fn add(mut self, t: T) -> Self::Output {
+        self.insert(&t);
+        self
+    }
+}
+
+/// We can add a new item.
+impl<T: Hash, const N: usize> AddAssign<T> for MinHash<N> {
+    fn add_assign(&mut self, t: T) {
+        self.insert(&t);
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const N: usize> Extend<T> for MinHash<N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+/// Add the items one at a time.
+impl<T: Hash, const N: usize> FromIterator<T> for MinHash<N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::default();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Element-wise min: the signature of the union.
+impl<const N: usize> Semigroup for MinHash<N> {
+    const IDEMPOTENT: bool = true;
+    fn op(x: &Self, y: &Self) -> Self {
+        let mut z = *x;
+        z.op_assign(y);
+        z
+    }
+}
+
+/// The signature of the empty set.
+impl<const N: usize> Monoid for MinHash<N> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Merge in place.
+impl<const N: usize> SemigroupAssign for MinHash<N> {
+    fn op_assign(&mut self, other: &Self) {
+        for (m, &n) in self.0.iter_mut().zip(&other.0) {
+            *m = (*m).min(n);
+        }
+    }
+}
+
+/// Min is commutative.
+impl<const N: usize> CommutativeMonoid for MinHash<N> {}
+
+/// Seeing the same items twice changes nothing.
+impl<const N: usize> Band for MinHash<N> {}
+
+/// Element-wise min is a meet.
+impl<const N: usize> Semilattice for MinHash<N> {}
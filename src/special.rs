@@ -0,0 +1,343 @@
+//! Special functions shared by the statistical summaries
+
+use std::f64::consts::{PI, SQRT_2};
+
+use num_traits::Float;
+
+/// Convert an `f64` constant to `T`.
+pub(crate) fn lit<T: Float>(x: f64) -> T {
+    T::from(x).expect("float constants are representable")
+}
+
+/// Convert `x` to `f64`, to call special functions.
+pub(crate) fn wide<T: Float>(x: T) -> f64 {
+    x.to_f64().expect("floats convert to f64")
+}
+
+/// Whether `x` is within `numpy.isclose`'s default tolerances of `y`.
+pub(crate) fn close<T: Float>(x: T, y: T) -> bool {
+    (x - y).abs() <= lit::<T>(1e-8) + lit::<T>(1e-5) * y.abs()
+}
+
+/// Evaluate the polynomial with coefficients `cs` (constant term first) at `x`.
+fn horner(cs: &[f64], x: f64) -> f64 {
+    cs.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+/// The standard normal quantile function, via Wichura's algorithm AS 241 (accurate to about 1e-16).
+///
+/// Returns ∓∞ at 0 and 1, and NaN outside `[0, 1]`.
+#[allow(clippy::excessive_precision)]
+pub(crate) fn probit(p: f64) -> f64 {
+    const A: [f64; 8] = [
+        3.387_132_872_796_366_608,
+        1.331_416_678_917_843_774_5e2,
+        1.971_590_950_306_551_442_7e3,
+        1.373_169_376_550_946_112_5e4,
+        4.592_195_393_154_987_145_7e4,
+        6.726_577_092_700_870_085_3e4,
+        3.343_057_558_358_812_810_5e4,
+        2.509_080_928_730_122_672_7e3,
+    ];
+    const B: [f64; 8] = [
+        1.0,
+        4.231_333_070_160_091_125_2e1,
+        6.871_870_074_920_579_083e2,
+        5.394_196_021_424_751_107_7e3,
+        2.121_379_430_158_659_586_7e4,
+        3.930_789_580_009_271_061e4,
+        2.872_908_573_572_194_267_4e4,
+        5.226_495_278_852_854_561e3,
+    ];
+    const C: [f64; 8] = [
+        1.423_437_110_749_683_577_34,
+        4.630_337_846_156_545_295_9,
+        5.769_497_221_460_691_405_5,
+        3.647_848_324_763_204_605_04,
+        1.270_458_252_452_368_382_58,
+        2.417_807_251_774_506_117_7e-1,
+        2.272_384_498_926_918_458_33e-2,
+        7.745_450_142_783_414_076_4e-4,
+    ];
+    const D: [f64; 8] = [
+        1.0,
+        2.053_191_626_637_758_821_87,
+        1.676_384_830_183_803_849_4,
+        6.897_673_349_851_000_045_5e-1,
+        1.481_039_764_274_800_745_9e-1,
+        1.519_866_656_361_645_719_66e-2,
+        5.475_938_084_995_344_946e-4,
+        1.050_750_071_644_416_843_24e-9,
+    ];
+    const E: [f64; 8] = [
+        6.657_904_643_501_103_777_2,
+        5.463_784_911_164_114_369_9,
+        1.784_826_539_917_291_335_8,
+        2.965_605_718_285_048_912_3e-1,
+        2.653_218_952_657_612_309_3e-2,
+        1.242_660_947_388_078_438_6e-3,
+        2.711_555_568_743_487_578_15e-5,
+        2.010_334_399_292_288_132_65e-7,
+    ];
+    const F: [f64; 8] = [
+        1.0,
+        5.998_322_065_558_879_376_9e-1,
+        1.369_298_809_227_358_053_1e-1,
+        1.487_536_129_085_061_485_25e-2,
+        7.868_691_311_456_132_591e-4,
+        1.846_318_317_510_054_681_8e-5,
+        1.421_511_758_316_445_888_7e-7,
+        2.044_263_103_389_939_785_64e-15,
+    ];
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    } else if p == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if p == 1.0 {
+        return f64::INFINITY;
+    }
+    let q = p - 0.5;
+    if q.abs() <= 0.425 {
+        let r = 0.180_625 - q * q;
+        return q * horner(&A, r) / horner(&B, r);
+    }
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let z = if r <= 5.0 {
+        horner(&C, r - 1.6) / horner(&D, r - 1.6)
+    } else {
+        horner(&E, r - 5.0) / horner(&F, r - 5.0)
+    };
+    if q < 0.0 {
+        -z
+    } else {
+        z
+    }
+}
+
+/// The logarithm of the standard normal CDF, accurate far into both tails.
+pub(crate) fn log_ndtr(z: f64) -> f64 {
+    if z > 6.0 {
+        // The CDF is within 1e-9 of one, so work with the (accurate) upper tail instead.
+        (-0.5 * libm::erfc(z / SQRT_2)).ln_1p()
+    } else if z > -20.0 {
+        (0.5 * libm::erfc(-z / SQRT_2)).ln()
+    } else {
+        // Asymptotic expansion of the Mills ratio; the first omitted term is below 1e-11 here.
+        let w = 1.0 / (z * z);
+        let series = horner(&[1.0, -1.0, 3.0, -15.0, 105.0, -945.0], w);
+        -0.5 * z * z - (-z).ln() - 0.5 * (2.0 * PI).ln() + series.ln()
+    }
+}
+
+/// Continued fraction for the regularized incomplete beta function, by the modified Lentz method.
+fn beta_cf(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let nudge = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let mut c = 1.0;
+    let mut d = 1.0 / nudge(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..=300 {
+        let m = f64::from(m);
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / nudge(1.0 + even * d);
+        c = nudge(1.0 + even / c);
+        h *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / nudge(1.0 + odd * d);
+        c = nudge(1.0 + odd / c);
+        h *= d * c;
+        if (d * c - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// The regularized incomplete beta function `I_x(a, b)`.
+pub(crate) fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    } else if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (libm::lgamma(a + b) - libm::lgamma(a) - libm::lgamma(b) + a * x.ln() + b * (-x).ln_1p())
+            .exp();
+    // The continued fraction converges quickly only on one side of the mean, so use symmetry on the other.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_cf(a, b, x) / a
+    } else {
+        1.0 - front * beta_cf(b, a, 1.0 - x) / b
+    }
+}
+
+/// The quantile function of the beta distribution with shape parameters `a` and `b`, by bisection.
+pub(crate) fn beta_quantile(p: f64, a: f64, b: f64) -> f64 {
+    let (mut lo, mut hi) = (0.0, 1.0);
+    loop {
+        let mid = 0.5 * (lo + hi);
+        if mid == lo || mid == hi {
+            return mid;
+        }
+        if beta_inc(a, b, mid) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+}
+
+/// Power series for the lower incomplete gamma function, scaled by `eˣ x⁻ᵃ Γ(a)`; converges quickly for
+/// `x < a + 1`.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let (mut term, mut sum, mut ap) = (1.0 / a, 1.0 / a, a);
+    for _ in 0..1000 {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * f64::EPSILON {
+            break;
+        }
+    }
+    sum
+}
+
+/// Continued fraction for the upper incomplete gamma function, by the modified Lentz method; converges
+/// quickly for `x > a + 1`, scaled like [`gamma_series`].
+fn gamma_cf(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let nudge = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / nudge(b);
+    let mut h = d;
+    for i in 1..=1000 {
+        let i = f64::from(i);
+        let an = -i * (i - a);
+        b += 2.0;
+        d = 1.0 / nudge(an * d + b);
+        c = nudge(b + an / c);
+        h *= d * c;
+        if (d * c - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// The regularized lower incomplete gamma function `P(a, x)`, accurate in the lower tail.
+pub(crate) fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    } else if x.is_infinite() {
+        return 1.0;
+    }
+    let front = (a * x.ln() - x - libm::lgamma(a)).exp();
+    if x < a + 1.0 {
+        front * gamma_series(a, x)
+    } else {
+        1.0 - front * gamma_cf(a, x)
+    }
+}
+
+/// The regularized upper incomplete gamma function `Q(a, x)`, accurate in the upper tail.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    } else if x.is_infinite() {
+        return 0.0;
+    }
+    let front = (a * x.ln() - x - libm::lgamma(a)).exp();
+    if x < a + 1.0 {
+        1.0 - front * gamma_series(a, x)
+    } else {
+        front * gamma_cf(a, x)
+    }
+}
+
+/// The digamma function `ψ(x) = Γ'(x) / Γ(x)`, for positive `x`.
+pub(crate) fn digamma(mut x: f64) -> f64 {
+    // Shift up with ψ(x) = ψ(x + 1) − 1/x until the asymptotic series is accurate.
+    let mut acc = 0.0;
+    while x < 10.0 {
+        acc -= 1.0 / x;
+        x += 1.0;
+    }
+    let w = 1.0 / (x * x);
+    let series = horner(
+        &[
+            0.0,
+            -1.0 / 12.0,
+            1.0 / 120.0,
+            -1.0 / 252.0,
+            1.0 / 240.0,
+            -1.0 / 132.0,
+        ],
+        w,
+    );
+    acc + x.ln() - 0.5 / x + series
+}
+
+/// The trigamma function `ψ'(x)`, for positive `x`.
+pub(crate) fn trigamma(mut x: f64) -> f64 {
+    // Shift up with ψ'(x) = ψ'(x + 1) + 1/x² until the asymptotic series is accurate.
+    let mut acc = 0.0;
+    while x < 10.0 {
+        acc += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let w = 1.0 / (x * x);
+    let series = horner(
+        &[1.0 / 6.0, -1.0 / 30.0, 1.0 / 42.0, -1.0 / 30.0, 5.0 / 66.0],
+        w,
+    );
+    acc + 1.0 / x + 0.5 * w + w / x * series
+}
+
+/// The CDF of Student's t distribution with `df` degrees of freedom, accurate in the lower tail.
+pub(crate) fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * beta_inc(0.5 * df, 0.5, df / (df + t * t));
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// The quantile function of Student's t distribution with `df` degrees of freedom, by bisection.
+///
+/// Returns ∓∞ at 0 and 1, and NaN outside `[0, 1]`.
+pub(crate) fn student_t_quantile(p: f64, df: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    // Search for |t| using the smaller tail, which is known exactly.
+    let (tail, sign) = if p < 0.5 { (p, -1.0) } else { (1.0 - p, 1.0) };
+    if tail == 0.0 {
+        return sign * f64::INFINITY;
+    }
+    let (mut lo, mut hi) = (0.0, 1.0);
+    while student_t_cdf(-hi, df) > tail {
+        lo = hi;
+        hi *= 2.0;
+    }
+    loop {
+        let mid = 0.5 * (lo + hi);
+        if mid == lo || mid == hi {
+            return sign * mid;
+        }
+        if student_t_cdf(-mid, df) > tail {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+}
+
+/// The survival function of the F distribution with `d1` and `d2` degrees of freedom.
+pub(crate) fn f_sf(f: f64, d1: f64, d2: f64) -> f64 {
+    if f <= 0.0 {
+        return 1.0;
+    }
+    beta_inc(0.5 * d2, 0.5 * d1, d2 / (d2 + d1 * f))
+}
@@ -0,0 +1,6 @@
+//! Statistical inference from merged summaries
+//!
+//! The summaries in this crate keep sufficient statistics, so classical tests can be run on data that was
+//! split across shards and combined with [`Semigroup::op`](crate::traits::Semigroup::op), without revisiting
+//! the raw points.
+pub mod tests;
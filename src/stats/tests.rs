@@ -0,0 +1,125 @@
+//! Hypothesis tests on [`Gaussian`], [`Proportion`] and [`Categorical`] summaries
+//!
+//! The two-sample p-values are two-sided, for the null hypothesis that both samples have the same mean (or
+//! success probability).
+use std::hash::Hash;
+
+use num_traits::Float;
+
+use crate::categorical::Categorical;
+use crate::gaussian::Gaussian;
+use crate::proportion::Proportion;
+use crate::special::{self, lit, wide};
+use crate::traits::Semigroup;
+
+/// The outcome of a hypothesis test
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestResult<T = f64> {
+    /// The test statistic
+    pub statistic: T,
+    /// The probability, under the null hypothesis, of a statistic at least this extreme
+    pub p_value: T,
+}
+
+/// The difference in means, and the squared standard errors of each mean.
+fn difference<T: Float>(a: &Gaussian<T>, b: &Gaussian<T>) -> (T, T, T) {
+    let sa = a.variance() / a.count();
+    let sb = b.variance() / b.count();
+    (a.mean() - b.mean(), sa, sb)
+}
+
+/// Welch's t-test, which doesn't assume equal variances; each sample needs at least two points.
+pub fn welch_t_test<T: Float>(a: &Gaussian<T>, b: &Gaussian<T>) -> TestResult<T> {
+    let (d, sa, sb) = difference(a, b);
+    let statistic = d / (sa + sb).sqrt();
+    // Welch–Satterthwaite approximation to the degrees of freedom
+    let df = (sa + sb).powi(2)
+        / (sa.powi(2) / (a.count() - T::one()) + sb.powi(2) / (b.count() - T::one()));
+    let p_value = lit(2.0 * special::student_t_cdf(-wide(statistic.abs()), wide(df)));
+    TestResult { statistic, p_value }
+}
+
+/// Two-sample z-test, treating the sample variances as known; appropriate for large samples.
+pub fn z_test<T: Float>(a: &Gaussian<T>, b: &Gaussian<T>) -> TestResult<T> {
+    let (d, sa, sb) = difference(a, b);
+    let statistic = d / (sa + sb).sqrt();
+    let p_value = lit(libm::erfc(wide(statistic.abs()) / std::f64::consts::SQRT_2));
+    TestResult { statistic, p_value }
+}
+
+/// Two-proportion z-test, pooling both samples to estimate the standard error under the null hypothesis;
+/// appropriate when each sample has at least a handful of successes and failures.
+pub fn two_proportion_z_test(a: &Proportion, b: &Proportion) -> TestResult {
+    let (na, nb) = (a.trials() as f64, b.trials() as f64);
+    let pooled = Semigroup::op(a, b).p_hat();
+    if pooled == 0.0 || pooled == 1.0 {
+        // Every trial had the same outcome, so there's no evidence of any difference.
+        return TestResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let se = (pooled * (1.0 - pooled) * (1.0 / na + 1.0 / nb)).sqrt();
+    let statistic = (a.p_hat() - b.p_hat()) / se;
+    let p_value = libm::erfc(statistic.abs() / std::f64::consts::SQRT_2);
+    TestResult { statistic, p_value }
+}
+
+/// The outcome of a chi-square test
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChiSquareResult {
+    /// Pearson's statistic `Σ (observed − expected)² / expected`
+    pub statistic: f64,
+    /// Degrees of freedom: one less than the number of categories
+    pub dof: u64,
+    /// The probability, under the null hypothesis, of a statistic at least this large
+    pub p_value: f64,
+}
+
+/// Pearson's chi-square goodness-of-fit test, for the null hypothesis that `observed` was drawn from the
+/// category probabilities of `expected`; appropriate when every expected count is at least 5 or so.
+///
+/// The categories are those with a positive expected probability; observing any other category gives an
+/// infinite statistic.
+pub fn chi_square_test<T: Eq + Hash>(
+    observed: &Categorical<T>,
+    expected: &Categorical<T>,
+) -> ChiSquareResult {
+    let n = observed.count() as f64;
+    assert!(n > 0.0, "Chi-square test requires at least 1 observation.");
+    assert!(
+        expected.count() > 0,
+        "Chi-square test requires at least 1 expected category."
+    );
+    let mut statistic: f64 = expected
+        .counts()
+        .iter()
+        .map(|(t, _)| {
+            let e = n * expected.prob(t);
+            (observed.counts().count(t) as f64 - e).powi(2) / e
+        })
+        .sum();
+    if observed
+        .counts()
+        .iter()
+        .any(|(t, _)| expected.counts().count(t) == 0)
+    {
+        statistic = f64::INFINITY;
+    }
+    let dof = expected.counts().categories() as u64 - 1;
+    let p_value = if dof == 0 {
+        // With a single category there's nothing to test, unless something impossible was observed.
+        if statistic.is_infinite() {
+            0.0
+        } else {
+            1.0
+        }
+    } else {
+        special::gamma_q(0.5 * dof as f64, 0.5 * statistic)
+    };
+    ChiSquareResult {
+        statistic,
+        dof,
+        p_value,
+    }
+}
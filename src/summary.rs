@@ -0,0 +1,138 @@
+//! The usual descriptive statistics in one mergeable value
+//!
+//! [`Summary`] is the direct product of [`Gaussian`], [`CompensatedSum`], [`MinF64`], and [`MaxF64`], so it
+//! merges exactly as the tuple would, but with named accessors instead of `.0.1`.
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use crate::compensated::CompensatedSum;
+use crate::gaussian::Gaussian;
+use crate::newtypes::{MaxF64, MinF64};
+use crate::traits::{Monoid, Semigroup, SemigroupAssign};
+
+/// Count, sum, mean, variance, min, and max of a stream of floats
+///
+/// NaNs are skipped by the extrema, but (as with [`Gaussian`]) poison the moments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    inner: (Gaussian, CompensatedSum, MinF64, MaxF64),
+}
+
+impl Default for Summary {
+    fn default() -> Self {
+        Summary {
+            inner: Monoid::zero(),
+        }
+    }
+}
+
+impl Summary {
+    /// Construct from a single data point.
+    pub fn new(x: f64) -> Summary {
+        Summary {
+            inner: (
+                Gaussian::new(x),
+                CompensatedSum::new(x),
+                MinF64::new(x),
+                MaxF64::new(x),
+            ),
+        }
+    }
+    /// The number of data points seen.
+    pub fn count(&self) -> f64 {
+        self.inner.0.count()
+    }
+    /// The (compensated) total.
+    pub fn sum(&self) -> f64 {
+        self.inner.1.value()
+    }
+    /// The mean.
+    pub fn mean(&self) -> f64 {
+        self.inner.0.mean()
+    }
+    /// The (sample) variance; see [`Gaussian::variance`].
+    pub fn variance(&self) -> f64 {
+        self.inner.0.variance()
+    }
+    /// The (sample) variance, or `None` with fewer than 2 samples.
+    pub fn try_variance(&self) -> Option<f64> {
+        self.inner.0.try_variance()
+    }
+    /// The (sample) standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        self.inner.0.std_dev()
+    }
+    /// The smallest value seen, or `+∞` if there are none.
+    pub fn min(&self) -> f64 {
+        self.inner.2.get()
+    }
+    /// The largest value seen, or `-∞` if there are none.
+    pub fn max(&self) -> f64 {
+        self.inner.3.get()
+    }
+    /// The distance between the extremes.
+    pub fn range(&self) -> f64 {
+        self.max() - self.min()
+    }
+    /// The fitted distribution.
+    pub fn gaussian(&self) -> &Gaussian {
+        &self.inner.0
+    }
+}
+
+/// We can add a new data point.
+impl Add<f64> for Summary {
+    type Output = Self;
+    fn add(mut self, x: f64) -> Self::Output {
+        self += x;
+        self
+    }
+}
+
+/// We can add a new data point.
+impl AddAssign<f64> for Summary {
+    fn add_assign(&mut self, x: f64) {
+        self.inner.op_assign(&Summary::new(x).inner);
+    }
+}
+
+/// Accumulate the points one at a time.
+impl FromIterator<f64> for Summary {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut s = Self::default();
+        for x in iter {
+            s += x
+        }
+        s
+    }
+}
+
+/// Accumulate the points one at a time.
+impl<'a> FromIterator<&'a f64> for Summary {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Merge componentwise.
+impl Semigroup for Summary {
+    fn op(x: &Self, y: &Self) -> Self {
+        Summary {
+            inner: Semigroup::op(&x.inner, &y.inner),
+        }
+    }
+}
+
+/// Merge componentwise, in place.
+impl SemigroupAssign for Summary {
+    fn op_assign(&mut self, other: &Self) {
+        self.inner.op_assign(&other.inner);
+    }
+}
+
+/// No data.
+impl Monoid for Summary {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
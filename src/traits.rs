@@ -1,10 +1,25 @@
 //! Core algebraic traits
-use std::{collections::HashMap, hash::Hash, num::NonZeroU64};
+use std::{any::Any, num::NonZeroU64};
 
 /// A set with a closed associative binary operation
 pub trait Semigroup {
+    /// Whether `op(x, x) == x` for every `x`; see [`Band`]
+    ///
+    /// This, not [`Band`], is what [`power_semigroup`] & co. read to skip their loops, since stable Rust
+    /// can't specialize on a trait.  Set it on every band, and only on bands: powers of anything else come
+    /// out wrong.
+    const IDEMPOTENT: bool = false;
     /// Associative operation
     fn op(x: &Self, y: &Self) -> Self;
+    /// Consuming version of [`Semigroup::op`], for when both operands are temporaries
+    ///
+    /// The default borrows; types owning buffers should override it to reuse them.
+    fn op_owned(x: Self, y: Self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::op(&x, &y)
+    }
 }
 
 /// A semigroup with an identity element (here named zero)
@@ -19,6 +34,83 @@ pub trait Monoid: Semigroup {
 /// A monoid whose operation is commutative
 pub trait CommutativeMonoid: Monoid {}
 
+/// A semigroup that can be combined in place, avoiding a fresh allocation per [`Semigroup::op`]
+///
+/// The default just replaces `self`; types owning buffers should override it.
+pub trait SemigroupAssign: Semigroup {
+    /// In-place version of [`Semigroup::op`], i.e. `*self = op(self, other)`
+    fn op_assign(&mut self, other: &Self)
+    where
+        Self: Sized,
+    {
+        *self = Self::op(self, other);
+    }
+}
+
+/// A monoid in which `op(x, y) == op(x, z)` implies `y == z` (and likewise on the right)
+pub trait CancellativeMonoid: Monoid {}
+
+/// A commutative monoid with truncated subtraction, e.g. natural numbers or multisets
+///
+/// `x` is "below" `y` whenever `op(x, monus(y, x)) == y`; subtracting more than is there bottoms out at `zero`.
+pub trait Monus: CommutativeMonoid {
+    /// Truncated subtraction ("monus"), written `x ∸ y`
+    fn monus(x: &Self, y: &Self) -> Self;
+}
+
+/// A monoid whose operation is monotone in both arguments, so that `x <= y` implies `op(x, z) <= op(y, z)`
+/// and `op(z, x) <= op(z, y)`
+pub trait OrderedMonoid: Monoid + PartialOrd {}
+
+/// A semigroup whose operation is idempotent, i.e. `op(x, x) == x`
+///
+/// This trait only states the law.  Exponentiation reads [`Semigroup::IDEMPOTENT`] alone, so implementors
+/// must set it to `true` as well, or [`power_semigroup`] & co. will loop as for any other semigroup;
+/// `band_properties!` and `semilattice_properties!` check that the two agree.
+pub trait Band: Semigroup {}
+
+/// A commutative monoid whose operation is idempotent (a bounded join-semilattice)
+pub trait Semilattice: Band + CommutativeMonoid {}
+
+/// A monoid acting on a carrier type `X`, compatibly with its operation
+///
+/// Acting by `op(m, n)` is the same as acting by `n` and then by `m`, and acting by `zero` does nothing.
+pub trait MonoidAction<X>: Monoid {
+    /// Apply this element to `x`
+    fn act(&self, x: &X) -> X;
+}
+
+/// A structure-preserving map between two monoids
+///
+/// Homomorphisms are what make divide-and-conquer work: mapping each chunk and combining the results is
+/// the same as mapping the combined input.
+pub trait MonoidHom<A: Monoid, B: Monoid> {
+    /// Map an element, sending `op` to `op` and `zero` to `zero`
+    fn apply(x: &A) -> B;
+}
+
+/// A set with two idempotent, commutative, associative operations linked by absorption
+pub trait Lattice {
+    /// Least upper bound
+    fn join(x: &Self, y: &Self) -> Self;
+    /// Greatest lower bound
+    fn meet(x: &Self, y: &Self) -> Self;
+}
+
+/// A lattice with a greatest and a least element
+pub trait BoundedLattice: Lattice {
+    /// Identity element for [`Lattice::meet`]
+    fn top() -> Self;
+    /// Identity element for [`Lattice::join`]
+    fn bottom() -> Self;
+}
+
+/// A monoid in which every element has an inverse
+pub trait Group: Monoid {
+    /// Inverse element with respect to [`Semigroup::op`]
+    fn invert(&self) -> Self;
+}
+
 /// A commutative monoid with an additional operation and identity element (one)
 pub trait Semiring: CommutativeMonoid {
     /// Additional associative binary operation
@@ -27,20 +119,82 @@ pub trait Semiring: CommutativeMonoid {
     fn one() -> Self;
 }
 
+/// A semiring whose multiplication is also commutative
+pub trait CommutativeSemiring: Semiring {}
+
+/// A semiring with a Kleene star (asteration), i.e. the closure `one + x + x·x + …`
+pub trait StarSemiring: Semiring {
+    /// Asteration, satisfying `star(x) == op(one, mul(x, star(x))) == op(one, mul(star(x), x))`
+    fn star(x: &Self) -> Self;
+}
+
+/// A commutative monoid that can be scaled by elements of a semiring
+pub trait Semimodule<R: Semiring>: CommutativeMonoid {
+    /// Scalar multiplication, distributing over both [`Semigroup::op`]s
+    fn scale(r: &R, x: &Self) -> Self;
+}
+
+/// A semiring whose additive monoid is a group
+pub trait Ring: Semiring + Group {}
+
+/// A commutative ring in which every nonzero element has a multiplicative inverse
+pub trait Field: Ring + CommutativeSemiring {
+    /// Inverse element with respect to [`Semiring::mul`]; only meaningful for nonzero elements
+    fn reciprocal(&self) -> Self;
+}
+
+/// An object-safe view of a [`Semigroup`], so that combiners can be chosen at runtime
+///
+/// Every `'static` semigroup gets this for free; combining values of two different types yields `None`.
+pub trait DynSemigroup: Any {
+    /// Dynamic version of [`Semigroup::op`]
+    fn op_dyn(&self, other: &dyn DynSemigroup) -> Option<Box<dyn DynSemigroup>>;
+    /// Upcast, so that we can recover the concrete type
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<S: Semigroup + Any> DynSemigroup for S {
+    fn op_dyn(&self, other: &dyn DynSemigroup) -> Option<Box<dyn DynSemigroup>> {
+        other
+            .as_any()
+            .downcast_ref::<S>()
+            .map(|y| Box::new(S::op(self, y)) as Box<dyn DynSemigroup>)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn DynSemigroup {
+    /// Recover a reference to the concrete value, if it has type `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
 /// Simultaneously map items to a monoid and accumulate them
 pub fn fold_map<T, M: Monoid>(xs: impl Iterator<Item = T>, f: impl Fn(T) -> M) -> M {
-    xs.fold(M::zero(), |m, t| M::op(&m, &f(t)))
+    xs.fold(M::zero(), |m, t| M::op_owned(m, f(t)))
 }
 
 /// This pops up _lots_ of places.
 pub fn power_semigroup<S: Semigroup + Clone>(x: S, n: NonZeroU64) -> S {
-    let mut y = x.clone();
+    if S::IDEMPOTENT {
+        return x;
+    }
+    let mut x = x;
     let mut m = n.get();
-    while m > 1 {
+    while m & 1 == 0 {
+        x = S::op(&x, &x);
+        m >>= 1;
+    }
+    let mut y = x.clone();
+    m >>= 1;
+    while m > 0 {
+        x = S::op(&x, &x);
         if m & 1 == 1 {
             y = S::op(&y, &x);
         }
-        y = S::op(&y, &y);
         m >>= 1;
     }
     y
@@ -53,54 +207,12 @@ pub fn power_monoid<M: Monoid + Clone>(x: M, n: u64) -> M {
         .unwrap_or_else(M::zero)
 }
 
-/// The direct product of two semigroups is a semigroup.
-impl<X: Semigroup, Y: Semigroup> Semigroup for (X, Y) {
-    fn op((a, x): &Self, (b, y): &Self) -> Self {
-        (X::op(a, b), Y::op(x, y))
-    }
-}
-
-/// The direct product of two monoids is a monoid.
-impl<X: Monoid, Y: Monoid> Monoid for (X, Y) {
-    fn zero() -> Self {
-        (X::zero(), Y::zero())
-    }
-}
-
-/// A Semigroup can be made into a monoid by adjoining a new identity element.
-impl<T: Semigroup + Clone> Semigroup for Option<T> {
-    fn op(x: &Self, y: &Self) -> Self {
-        match (x, y) {
-            (Some(a), Some(b)) => Some(T::op(a, b)),
-            (None, _) => y.clone(),
-            (_, None) => x.clone(),
-        }
-    }
-}
-
-/// A Semigroup can be made into a monoid by adjoining a new identity element.
-impl<T: Semigroup + Clone> Monoid for Option<T> {
-    fn zero() -> Self {
-        None
-    }
-}
-
-/// A map of {key ↦ value} is a semigroup if the values form one.
-impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Semigroup for HashMap<K, V> {
-    fn op(x: &Self, y: &Self) -> Self {
-        let mut h = HashMap::new();
-        for (k, v) in x.iter().chain(y.iter()) {
-            h.entry((*k).clone())
-                .and_modify(|w| *w = V::op(w, v))
-                .or_insert_with(|| v.clone());
-        }
-        h
-    }
-}
-
-/// A map of {key ↦ value} is a monoid if the values form a semigroup.
-impl<K: Clone + Eq + Hash, V: Semigroup + Clone> Monoid for HashMap<K, V> {
-    fn zero() -> Self {
-        HashMap::new()
+/// Group version, accepting negative exponents via inverses
+pub fn power_group<G: Group + Clone>(x: G, n: i64) -> G {
+    let y = power_monoid(x, n.unsigned_abs());
+    if n < 0 {
+        y.invert()
+    } else {
+        y
     }
 }
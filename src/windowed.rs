@@ -0,0 +1,171 @@
+//! Time series of any monoid, bucketed by fixed-width time intervals
+//!
+//! Each bucket summarizes the values whose timestamps fall in it, so two series merge bucket by bucket and
+//! any range of buckets rolls up with [`Semigroup::op`].  Old buckets can be expired as the series grows,
+//! either discarded or folded into a single archival summary.
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+
+use crate::traits::{CommutativeMonoid, Monoid, Semigroup, SemigroupAssign};
+
+/// Summaries of type `M` per time bucket of `WIDTH` units, keyed by the start of each bucket
+///
+/// Timestamps are integers in whatever unit suits (seconds since the epoch, say); bucket `b` covers
+/// `[b, b + WIDTH)`, with `b` a multiple of `WIDTH`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeWindowed<M, const WIDTH: i64> {
+    /// Summary of each non-empty bucket
+    buckets: BTreeMap<i64, M>,
+}
+
+impl<M, const WIDTH: i64> Default for TimeWindowed<M, WIDTH> {
+    fn default() -> Self {
+        const { assert!(WIDTH > 0, "buckets must have positive width") };
+        TimeWindowed {
+            buckets: BTreeMap::new(),
+        }
+    }
+}
+
+impl<M: Semigroup, const WIDTH: i64> TimeWindowed<M, WIDTH> {
+    /// Construct from a single summary `m` at time `t`.
+    pub fn new(t: i64, m: M) -> Self {
+        let mut w = Self::default();
+        w.insert(t, m);
+        w
+    }
+    /// The start of the bucket containing time `t`.
+    ///
+    /// Unless `WIDTH` divides 2⁶³, the first bucket would start before `i64::MIN`; it's cut short and keyed
+    /// at `i64::MIN` instead.
+    pub fn bucket_start(t: i64) -> i64 {
+        t.checked_sub(t.rem_euclid(WIDTH)).unwrap_or(i64::MIN)
+    }
+    /// The end of the bucket starting at `start`, saturating at `i64::MAX`.
+    fn bucket_end(start: i64) -> i64 {
+        // Only a cut-short first bucket starts off the grid.
+        start.saturating_add(WIDTH - start.rem_euclid(WIDTH))
+    }
+    /// Merge `m` into the bucket containing time `t`.
+    pub fn insert(&mut self, t: i64, m: M) {
+        let start = Self::bucket_start(t);
+        let m = match self.buckets.remove(&start) {
+            Some(old) => M::op_owned(old, m),
+            None => m,
+        };
+        self.buckets.insert(start, m);
+    }
+    /// The summary of the bucket containing time `t`, if it's non-empty.
+    pub fn get(&self, t: i64) -> Option<&M> {
+        self.buckets.get(&Self::bucket_start(t))
+    }
+    /// The number of non-empty buckets.
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+    /// Whether every bucket is empty.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+    /// The start of each non-empty bucket and its summary, in time order.
+    pub fn buckets(&self) -> btree_map::Iter<'_, i64, M> {
+        self.buckets.iter()
+    }
+    /// The span from the start of the earliest non-empty bucket to the end of the latest, if any; the end
+    /// saturates at `i64::MAX`.
+    pub fn span(&self) -> Option<(i64, i64)> {
+        let (&first, _) = self.buckets.first_key_value()?;
+        let (&last, _) = self.buckets.last_key_value()?;
+        Some((first, Self::bucket_end(last)))
+    }
+    /// Remove every bucket that ends at or before `cutoff`, returning their combined summary.
+    ///
+    /// Drop the result to simply enforce retention, or keep it as an archive of everything expired.
+    pub fn expire(&mut self, cutoff: i64) -> M
+    where
+        M: Monoid,
+    {
+        // Buckets before the one containing `cutoff` end at or before it.
+        let keep = self.buckets.split_off(&Self::bucket_start(cutoff));
+        let expired = std::mem::replace(&mut self.buckets, keep);
+        expired.into_values().fold(M::zero(), M::op_owned)
+    }
+    /// The combined summary of every bucket whose start lies in `range`, e.g. `t0..t1` for the buckets
+    /// starting in `[t0, t1)`.
+    pub fn rollup<R: RangeBounds<i64>>(&self, range: R) -> M
+    where
+        M: Monoid,
+    {
+        // An inverted range covers nothing, but would make BTreeMap::range panic.
+        let empty = match (range.start_bound(), range.end_bound()) {
+            (Bound::Included(a), Bound::Included(b)) => a > b,
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Excluded(b))
+            | (Bound::Excluded(a), Bound::Included(b)) => a >= b,
+            _ => false,
+        };
+        if empty {
+            return M::zero();
+        }
+        self.buckets
+            .range(range)
+            .fold(M::zero(), |acc, (_, m)| M::op(&acc, m))
+    }
+    /// The combined summary of every bucket.
+    pub fn total(&self) -> M
+    where
+        M: Monoid,
+    {
+        self.rollup(..)
+    }
+}
+
+/// Insert the timestamped summaries one at a time.
+impl<M: Semigroup, const WIDTH: i64> FromIterator<(i64, M)> for TimeWindowed<M, WIDTH> {
+    fn from_iter<I: IntoIterator<Item = (i64, M)>>(iter: I) -> Self {
+        let mut w = Self::default();
+        w.extend(iter);
+        w
+    }
+}
+
+/// Insert the timestamped summaries one at a time.
+impl<M: Semigroup, const WIDTH: i64> Extend<(i64, M)> for TimeWindowed<M, WIDTH> {
+    fn extend<I: IntoIterator<Item = (i64, M)>>(&mut self, iter: I) {
+        for (t, m) in iter {
+            self.insert(t, m)
+        }
+    }
+}
+
+/// Merge the series bucket by bucket.
+impl<M: Semigroup + Clone, const WIDTH: i64> Semigroup for TimeWindowed<M, WIDTH> {
+    fn op(x: &Self, y: &Self) -> Self {
+        TimeWindowed {
+            buckets: Semigroup::op(&x.buckets, &y.buckets),
+        }
+    }
+    fn op_owned(x: Self, y: Self) -> Self {
+        TimeWindowed {
+            buckets: Semigroup::op_owned(x.buckets, y.buckets),
+        }
+    }
+}
+
+/// Merge in place.
+impl<M: SemigroupAssign + Clone, const WIDTH: i64> SemigroupAssign for TimeWindowed<M, WIDTH> {
+    fn op_assign(&mut self, other: &Self) {
+        self.buckets.op_assign(&other.buckets);
+    }
+}
+
+/// No buckets.
+impl<M: Semigroup + Clone, const WIDTH: i64> Monoid for TimeWindowed<M, WIDTH> {
+    fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Series merge in any order if their summaries do.
+impl<M: CommutativeMonoid + Clone, const WIDTH: i64> CommutativeMonoid for TimeWindowed<M, WIDTH> {}